@@ -17,10 +17,15 @@
 //!
 //! # Delivery flow (host responsibility)
 //!
-//! 1. Call [`AgentAddress::well_known_url`] to get the discovery URL.
-//! 2. `GET` that URL and pass the response body to [`NodeSession::from_discovery`].
-//! 3. Call [`AgentAddress::inbox_url`] with the `api_base` from the session to
-//!    get the final delivery endpoint.
+//! 1. Call [`AgentAddress::webfinger_url`] to get the WebFinger URL.
+//! 2. `GET` that URL and pass the response body to
+//!    [`resolve_jrd`](crate::resolve_jrd), which yields the inbox URL the
+//!    agent's node advertises.
+//!
+//! Nodes that predate WebFinger can still be reached by convention: fetch
+//! [`AgentAddress::well_known_url`], pass the body to
+//! [`NodeSession::from_discovery`], and call [`AgentAddress::inbox_url`] with
+//! the session's `api_base`.
 
 use thiserror::Error;
 use urlencoding::encode;
@@ -97,6 +102,13 @@ impl AgentAddress {
         format!("https://{}/.well-known/semanticweft", self.hostname)
     }
 
+    /// `https://hostname/.well-known/webfinger?resource=acct:…` — fetch this
+    /// and pass the body to [`resolve_jrd`](crate::resolve_jrd) to learn the
+    /// agent's profile and inbox URLs as its node advertises them.
+    pub fn webfinger_url(&self) -> String {
+        crate::webfinger::webfinger_url(&self.node_url(), &self.did, &self.hostname)
+    }
+
     /// The agent's inbox URL, given the `api_base` from the discovery document.
     ///
     /// The DID is percent-encoded for safe use in the URL path.
@@ -171,6 +183,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn webfinger_url_encodes_resource() {
+        let a = AgentAddress::new("did:key:z6MkFoo", "sweft.example.com");
+        assert_eq!(
+            a.webfinger_url(),
+            "https://sweft.example.com/.well-known/webfinger?resource=acct%3Adid%3Akey%3Az6MkFoo%40sweft.example.com"
+        );
+    }
+
     #[test]
     fn inbox_url_encodes_did() {
         let a = AgentAddress::new("did:key:z6MkFoo", "sweft.example.com");
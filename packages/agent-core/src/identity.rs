@@ -19,8 +19,11 @@
 //!   // same DID as before
 //! ```
 
+use std::time::SystemTime;
+
 use ed25519_dalek::{SigningKey, VerifyingKey};
 use rand::rngs::OsRng;
+use semanticweft::{Delegation, DelegationScope, SemanticUnit, SigningError};
 
 /// An agent's cryptographic identity.
 ///
@@ -76,6 +79,31 @@ impl AgentIdentity {
         use ed25519_dalek::Signer;
         self.signing_key.sign(message).to_bytes().to_vec()
     }
+
+    /// Grant the key `audience` (a `did:key`) the right to act for this
+    /// identity within `scope` from `not_before` until `expires`.
+    ///
+    /// The host hands the returned token to the delegate, which presents it
+    /// with its own signatures; the root seed never leaves this process.
+    pub fn delegate(
+        &self,
+        audience: impl Into<String>,
+        scope: DelegationScope,
+        not_before: SystemTime,
+        expires: SystemTime,
+    ) -> Delegation {
+        Delegation::new(self.did(), audience, scope, not_before, expires).sign(&self.signing_key)
+    }
+
+    /// Sign `unit` in place on behalf of `delegation.issuer`, using this
+    /// identity as the delegate key. See [`semanticweft::sign_unit_delegated`].
+    pub fn sign_unit_delegated(
+        &self,
+        unit: &mut SemanticUnit,
+        delegation: Delegation,
+    ) -> Result<(), SigningError> {
+        semanticweft::sign_unit_delegated(unit, &self.signing_key, delegation)
+    }
 }
 
 // ── Helpers ──────────────────────────────────────────────────────────────────
@@ -123,4 +151,25 @@ mod tests {
         let sig = id.sign(b"hello semanticweft");
         assert_eq!(sig.len(), 64);
     }
+
+    #[test]
+    fn delegate_signs_for_sub_key() {
+        use std::time::Duration;
+        let root = AgentIdentity::generate();
+        let worker = AgentIdentity::generate();
+        let now = SystemTime::now();
+        let token = root.delegate(
+            worker.did(),
+            DelegationScope::default(),
+            now,
+            now + Duration::from_secs(600),
+        );
+        assert_eq!(token.issuer, root.did());
+        assert_eq!(token.verify(now + Duration::from_secs(1)), Ok(()));
+
+        let mut unit = SemanticUnit::new(semanticweft::UnitType::Assertion, "x", root.did());
+        worker.sign_unit_delegated(&mut unit, token).unwrap();
+        assert_eq!(semanticweft::verify_proof(&unit), Ok(()));
+        assert_eq!(semanticweft::proof_author(&unit), Some(root.did().as_str()));
+    }
 }
@@ -4,21 +4,37 @@
 //! runtime.  It compiles to native Rust and to WebAssembly without any
 //! changes.  The host environment (native binary, browser, Deno, Python
 //! via wasmtime, …) is responsible for all HTTP calls and key persistence;
-//! this crate handles identity, address parsing, URL construction, and
-//! signing.
+//! this crate handles identity, address parsing, WebFinger resolution, URL
+//! construction, signing, key rotation and delegation, the submission
+//! outbox, and SSE parsing and cursor tracking for `/v1/sync`.
 //!
 //! # Crates that use this
 //!
 //! | Environment | How it's used |
 //! |---|---|
-//! | Native Rust agent | Depend on this crate directly; add `reqwest` for I/O |
+//! | Native Rust agent | Via `semanticweft-client`, or directly with any HTTP client |
 //! | Browser / Node.js | Via `semanticweft-wasm` (WASM bindings) + `fetch()` |
 //! | Any other language | Via `semanticweft-wasm` loaded in a WASM runtime |
 
 pub mod address;
+pub mod http_signature;
 pub mod identity;
+pub mod outbox;
 pub mod session;
+pub mod succession;
+pub mod sync;
+pub mod webfinger;
 
 pub use address::{AddressError, AgentAddress};
+pub use http_signature::{
+    body_digest, SignableRequest, SignatureError, SignatureHeaders, SignatureParams,
+};
 pub use identity::AgentIdentity;
+pub use outbox::{Attempt, Backoff, EntryState, Outbox, OutboxEntry, OutboxError, OutboxSummary};
 pub use session::{NodeSession, SessionError};
+pub use succession::{sign_succession, verify_succession, SuccessionError};
+pub use sync::{
+    SseEvent, SseParser, SyncCursor, SyncError, SyncEvent, SyncPhase, SyncRequest,
+    DEFAULT_PAGE_LIMIT,
+};
+pub use webfinger::{resolve_jrd, ResolvedAgent, WebFingerError};
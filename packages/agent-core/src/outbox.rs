@@ -0,0 +1,549 @@
+//! Outbox — a durable submission queue for agents on unreliable links.
+//!
+//! An [`Outbox`] holds units the agent has authored (and normally signed)
+//! until a node accepts them. The host drives it:
+//!
+//! ```text
+//! outbox.enqueue(unit)?;
+//! // whenever the link may be up:
+//! for unit in outbox.due(SystemTime::now()) {
+//!     let attempt = match http_post(session.units_url(), unit) {
+//!         Ok(resp) => Attempt::Response { status, retry_after, message },
+//!         Err(e) => Attempt::Unreachable(e.to_string()),
+//!     };
+//!     outbox.record(&unit.id, attempt, SystemTime::now());
+//!     persist(&outbox);
+//! }
+//! // otherwise sleep until outbox.next_due()
+//! ```
+//!
+//! The rules:
+//!
+//! - **Reference order.** A unit is only due once every unit it references
+//!   that was queued *before* it has been delivered, so a node never sees a
+//!   reply before its premise. Later-queued references are not waited for,
+//!   which breaks reference cycles in queue order.
+//! - **Idempotency.** `201` and `200` both mean delivered; the spec answers
+//!   a re-submission of identical content with `200`, so a unit whose first
+//!   response was lost is delivered on the retry.
+//! - **Back-off.** Unreachable nodes, `408`, `429` and `5xx` schedule a
+//!   retry after an exponentially growing delay, or after `Retry-After`
+//!   when the node sends one.
+//! - **Permanent failures.** Any other `4xx` fails the unit for good and
+//!   blocks every queued unit that depends on it, until the host calls
+//!   [`Outbox::retry_failed`]. A unit enqueued later that references a
+//!   failed or blocked unit starts out blocked.
+//!
+//! There is no I/O and no clock: the host passes the current time, and the
+//! whole outbox serialises with serde so the host can persist it after each
+//! change.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use semanticweft::SemanticUnit;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors from [`Outbox::enqueue`].
+#[derive(Debug, Error, PartialEq)]
+pub enum OutboxError {
+    #[error("unit {0} is already queued with different content")]
+    Conflict(String),
+}
+
+/// How long to wait between attempts at a unit the node did not take.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backoff {
+    /// Wait after the first failed attempt; doubled after each further one.
+    pub initial: Duration,
+    /// Longest wait, whatever the attempt count or `Retry-After`.
+    pub max: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_secs(2),
+            max: Duration::from_secs(600),
+        }
+    }
+}
+
+impl Backoff {
+    /// The wait after `attempts` failed attempts (at least 1).
+    pub fn delay(&self, attempts: u32) -> Duration {
+        let factor = 1u32 << attempts.saturating_sub(1).min(20);
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+}
+
+/// The result of one attempt to submit a unit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Attempt {
+    /// The node answered.
+    Response {
+        status: u16,
+        /// Parsed `Retry-After`, if the response carried one.
+        retry_after: Option<Duration>,
+        /// Error message from the body, for failures.
+        message: String,
+    },
+    /// The request never got an answer: DNS, connection, TLS or timeout.
+    Unreachable(String),
+}
+
+/// Where a queued unit stands.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum EntryState {
+    /// Waiting to be (re)submitted.
+    Queued,
+    /// Accepted by the node.
+    Delivered,
+    /// Rejected by the node; will not be retried automatically.
+    Failed { status: u16, reason: String },
+    /// Not submitted because a unit it references failed.
+    Blocked { on: String },
+}
+
+/// One unit in the outbox.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub unit: SemanticUnit,
+    #[serde(flatten)]
+    pub state: EntryState,
+    /// Attempts since the unit was (re)queued.
+    #[serde(default)]
+    pub attempts: u32,
+    /// Why the last attempt did not deliver the unit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Earliest next attempt, in milliseconds since the Unix epoch.
+    #[serde(default)]
+    due_ms: u64,
+}
+
+impl OutboxEntry {
+    /// When the next attempt is due.
+    pub fn due(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.due_ms)
+    }
+}
+
+/// Counts of entries by state.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutboxSummary {
+    pub queued: usize,
+    pub delivered: usize,
+    pub failed: usize,
+    pub blocked: usize,
+}
+
+/// A queue of units waiting for a node to accept them. See the module docs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Outbox {
+    entries: Vec<OutboxEntry>,
+    #[serde(skip)]
+    backoff: Backoff,
+}
+
+impl Outbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `backoff` instead of the default retry schedule.
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Every entry, in queue order.
+    pub fn entries(&self) -> &[OutboxEntry] {
+        &self.entries
+    }
+
+    /// The entry for unit `id`.
+    pub fn get(&self, id: &str) -> Option<&OutboxEntry> {
+        self.entries.iter().find(|e| e.unit.id == id)
+    }
+
+    pub fn summary(&self) -> OutboxSummary {
+        let mut s = OutboxSummary::default();
+        for e in &self.entries {
+            match e.state {
+                EntryState::Queued => s.queued += 1,
+                EntryState::Delivered => s.delivered += 1,
+                EntryState::Failed { .. } => s.failed += 1,
+                EntryState::Blocked { .. } => s.blocked += 1,
+            }
+        }
+        s
+    }
+
+    /// Queue `unit` for immediate submission, or block it straight away if
+    /// it references a failed or blocked entry. Returns `false` if the same
+    /// unit was already queued, which is a no-op.
+    pub fn enqueue(&mut self, unit: SemanticUnit) -> Result<bool, OutboxError> {
+        if let Some(existing) = self.get(&unit.id) {
+            return if existing.unit == unit {
+                Ok(false)
+            } else {
+                Err(OutboxError::Conflict(unit.id))
+            };
+        }
+        let on = references(&unit)
+            .find(|&id| {
+                self.get(id).is_some_and(|e| {
+                    matches!(
+                        e.state,
+                        EntryState::Failed { .. } | EntryState::Blocked { .. }
+                    )
+                })
+            })
+            .map(str::to_string);
+        let state = match on {
+            Some(on) => EntryState::Blocked { on },
+            None => EntryState::Queued,
+        };
+        self.entries.push(OutboxEntry {
+            unit,
+            state,
+            attempts: 0,
+            last_error: None,
+            due_ms: 0,
+        });
+        Ok(true)
+    }
+
+    /// Units to submit now, in queue order: queued, due by `now`, and with
+    /// every earlier-queued unit they reference delivered.
+    pub fn due(&self, now: SystemTime) -> Vec<&SemanticUnit> {
+        let now = millis(now);
+        (0..self.entries.len())
+            .filter(|&i| self.is_ready(i) && self.entries[i].due_ms <= now)
+            .map(|i| &self.entries[i].unit)
+            .collect()
+    }
+
+    /// When the next queued unit becomes due, if any is waiting only on
+    /// time. `None` when nothing can progress without an attempt finishing
+    /// or the host intervening.
+    pub fn next_due(&self) -> Option<SystemTime> {
+        (0..self.entries.len())
+            .filter(|&i| self.is_ready(i))
+            .map(|i| self.entries[i].due())
+            .min()
+    }
+
+    /// Record the outcome of submitting unit `id` and return its new state.
+    /// Unknown ids are ignored.
+    pub fn record(&mut self, id: &str, attempt: Attempt, now: SystemTime) -> Option<&EntryState> {
+        let i = self.entries.iter().position(|e| e.unit.id == id)?;
+        let backoff = self.backoff.clone();
+        let entry = &mut self.entries[i];
+        entry.attempts += 1;
+
+        match attempt {
+            Attempt::Response { status, .. } if (200..300).contains(&status) => {
+                entry.state = EntryState::Delivered;
+                entry.last_error = None;
+            }
+            Attempt::Response {
+                status,
+                retry_after,
+                message,
+            } if status == 408 || status == 429 || status >= 500 => {
+                let wait = retry_after.unwrap_or_else(|| backoff.delay(entry.attempts));
+                entry.due_ms = millis(now + wait.min(backoff.max));
+                entry.last_error = Some(format!("{status}: {message}"));
+            }
+            Attempt::Response {
+                status, message, ..
+            } => {
+                entry.last_error = Some(format!("{status}: {message}"));
+                entry.state = EntryState::Failed {
+                    status,
+                    reason: message,
+                };
+                self.block_dependents(i);
+            }
+            Attempt::Unreachable(message) => {
+                entry.due_ms = millis(now + backoff.delay(entry.attempts));
+                entry.last_error = Some(message);
+            }
+        }
+        Some(&self.entries[i].state)
+    }
+
+    /// Requeue failed and blocked units for immediate submission.
+    pub fn retry_failed(&mut self) -> usize {
+        let mut n = 0;
+        for e in &mut self.entries {
+            if matches!(
+                e.state,
+                EntryState::Failed { .. } | EntryState::Blocked { .. }
+            ) {
+                e.state = EntryState::Queued;
+                e.attempts = 0;
+                e.due_ms = 0;
+                n += 1;
+            }
+        }
+        n
+    }
+
+    /// Drop delivered units; returns how many were removed.
+    pub fn prune_delivered(&mut self) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.state != EntryState::Delivered);
+        before - self.entries.len()
+    }
+
+    /// `true` if entry `i` is queued and every earlier entry it references
+    /// has been delivered.
+    fn is_ready(&self, i: usize) -> bool {
+        let entry = &self.entries[i];
+        entry.state == EntryState::Queued
+            && references(&entry.unit).all(|id| {
+                self.entries[..i]
+                    .iter()
+                    .find(|e| e.unit.id == id)
+                    .is_none_or(|e| e.state == EntryState::Delivered)
+            })
+    }
+
+    /// Block every later queued entry that depends, directly or through
+    /// other queued entries, on entry `failed`.
+    fn block_dependents(&mut self, failed: usize) {
+        let mut bad = vec![self.entries[failed].unit.id.clone()];
+        for j in failed + 1..self.entries.len() {
+            if self.entries[j].state != EntryState::Queued {
+                continue;
+            }
+            let on = references(&self.entries[j].unit)
+                .find(|r| bad.iter().any(|b| b == r))
+                .map(str::to_string);
+            if let Some(on) = on {
+                self.entries[j].state = EntryState::Blocked { on };
+                bad.push(self.entries[j].unit.id.clone());
+            }
+        }
+    }
+}
+
+fn references(unit: &SemanticUnit) -> impl Iterator<Item = &str> {
+    unit.references.iter().flatten().map(|r| r.id.as_str())
+}
+
+fn millis(t: SystemTime) -> u64 {
+    t.duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semanticweft::{Reference, RelType, UnitType};
+
+    const AUTHOR: &str = "did:key:z6MkAgent";
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_771_329_600 + secs)
+    }
+
+    fn unit(content: &str, refs: &[&SemanticUnit]) -> SemanticUnit {
+        let mut u = SemanticUnit::new(UnitType::Assertion, content, AUTHOR);
+        if !refs.is_empty() {
+            u.references = Some(
+                refs.iter()
+                    .map(|r| Reference {
+                        id: r.id.clone(),
+                        rel: RelType::Supports,
+                    })
+                    .collect(),
+            );
+        }
+        u
+    }
+
+    fn response(status: u16) -> Attempt {
+        Attempt::Response {
+            status,
+            retry_after: None,
+            message: "msg".into(),
+        }
+    }
+
+    fn due_ids(outbox: &Outbox, now: SystemTime) -> Vec<String> {
+        outbox.due(now).iter().map(|u| u.id.clone()).collect()
+    }
+
+    #[test]
+    fn waits_for_referenced_units_to_be_delivered() {
+        let premise = unit("premise", &[]);
+        let reply = unit("reply", &[&premise]);
+        let other = unit("other", &[]);
+        let mut outbox = Outbox::new();
+        for u in [&premise, &reply, &other] {
+            assert!(outbox.enqueue(u.clone()).unwrap());
+        }
+
+        assert_eq!(
+            due_ids(&outbox, at(0)),
+            [premise.id.clone(), other.id.clone()]
+        );
+        outbox.record(&premise.id, response(201), at(0));
+        outbox.record(&other.id, response(201), at(0));
+        assert_eq!(due_ids(&outbox, at(0)), [reply.id.as_str()]);
+    }
+
+    #[test]
+    fn identical_resubmission_counts_as_delivered() {
+        let u = unit("once", &[]);
+        let mut outbox = Outbox::new();
+        outbox.enqueue(u.clone()).unwrap();
+        assert_eq!(outbox.enqueue(u.clone()), Ok(false));
+
+        let mut changed = u.clone();
+        changed.content = "twice".into();
+        assert_eq!(
+            outbox.enqueue(changed),
+            Err(OutboxError::Conflict(u.id.clone()))
+        );
+
+        outbox.record(&u.id, Attempt::Unreachable("timed out".into()), at(0));
+        outbox.record(&u.id, response(200), at(10));
+        assert_eq!(outbox.get(&u.id).unwrap().state, EntryState::Delivered);
+        assert_eq!(outbox.get(&u.id).unwrap().last_error, None);
+    }
+
+    #[test]
+    fn backs_off_and_honours_retry_after() {
+        let u = unit("flaky", &[]);
+        let mut outbox = Outbox::new();
+        outbox.enqueue(u.clone()).unwrap();
+
+        outbox.record(&u.id, Attempt::Unreachable("refused".into()), at(0));
+        assert_eq!(outbox.next_due(), Some(at(2)));
+        assert!(outbox.due(at(1)).is_empty());
+
+        outbox.record(&u.id, response(503), at(2));
+        assert_eq!(outbox.next_due(), Some(at(6)));
+
+        outbox.record(
+            &u.id,
+            Attempt::Response {
+                status: 429,
+                retry_after: Some(Duration::from_secs(30)),
+                message: "slow down".into(),
+            },
+            at(6),
+        );
+        assert_eq!(outbox.next_due(), Some(at(36)));
+        assert_eq!(due_ids(&outbox, at(36)), [u.id.as_str()]);
+        assert_eq!(outbox.get(&u.id).unwrap().attempts, 3);
+        assert_eq!(Backoff::default().delay(40), Duration::from_secs(600));
+    }
+
+    #[test]
+    fn permanent_failure_blocks_dependents() {
+        let bad = unit("bad", &[]);
+        let reply = unit("reply", &[&bad]);
+        let reply_to_reply = unit("reply to reply", &[&reply]);
+        let unrelated = unit("unrelated", &[]);
+        let mut outbox = Outbox::new();
+        for u in [&bad, &reply, &reply_to_reply, &unrelated] {
+            outbox.enqueue(u.clone()).unwrap();
+        }
+
+        outbox.record(&bad.id, response(422), at(0));
+        assert!(matches!(
+            outbox.get(&bad.id).unwrap().state,
+            EntryState::Failed { status: 422, .. }
+        ));
+        assert_eq!(
+            outbox.get(&reply_to_reply.id).unwrap().state,
+            EntryState::Blocked {
+                on: reply.id.clone()
+            }
+        );
+        assert_eq!(due_ids(&outbox, at(0)), [unrelated.id.as_str()]);
+        let summary = outbox.summary();
+        assert_eq!((summary.failed, summary.blocked, summary.queued), (1, 2, 1));
+
+        assert_eq!(outbox.retry_failed(), 3);
+        assert_eq!(
+            due_ids(&outbox, at(0)),
+            [bad.id.clone(), unrelated.id.clone()]
+        );
+    }
+
+    #[test]
+    fn enqueue_behind_a_failed_unit_blocks() {
+        let bad = unit("bad", &[]);
+        let reply = unit("reply", &[&bad]);
+        let mut outbox = Outbox::new();
+        outbox.enqueue(bad.clone()).unwrap();
+        outbox.record(&bad.id, response(422), at(0));
+
+        outbox.enqueue(reply.clone()).unwrap();
+        assert_eq!(
+            outbox.get(&reply.id).unwrap().state,
+            EntryState::Blocked { on: bad.id.clone() }
+        );
+        let reply_to_reply = unit("reply to reply", &[&reply]);
+        outbox.enqueue(reply_to_reply.clone()).unwrap();
+        assert_eq!(
+            outbox.get(&reply_to_reply.id).unwrap().state,
+            EntryState::Blocked { on: reply.id.clone() }
+        );
+        assert!(outbox.due(at(0)).is_empty());
+
+        assert_eq!(outbox.retry_failed(), 3);
+        assert_eq!(due_ids(&outbox, at(0)), [bad.id.as_str()]);
+    }
+
+    #[test]
+    fn later_queued_references_do_not_wait() {
+        let mut first = unit("first", &[]);
+        let second = unit("second", &[&first]);
+        first.references = Some(vec![Reference {
+            id: second.id.clone(),
+            rel: RelType::Refines,
+        }]);
+        let mut outbox = Outbox::new();
+        outbox.enqueue(first.clone()).unwrap();
+        outbox.enqueue(second.clone()).unwrap();
+        assert_eq!(due_ids(&outbox, at(0)), [first.id.clone()]);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let u = unit("persisted", &[]);
+        let mut outbox = Outbox::new();
+        outbox.enqueue(u.clone()).unwrap();
+        outbox.record(&u.id, response(500), at(0));
+
+        let json = serde_json::to_string(&outbox).unwrap();
+        assert!(json.contains(r#""state":"queued""#), "{json}");
+        let restored: Outbox = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, outbox);
+        assert_eq!(restored.next_due(), Some(at(2)));
+    }
+
+    #[test]
+    fn prunes_delivered_units() {
+        let a = unit("a", &[]);
+        let b = unit("b", &[&a]);
+        let mut outbox = Outbox::new();
+        outbox.enqueue(a.clone()).unwrap();
+        outbox.enqueue(b.clone()).unwrap();
+        outbox.record(&a.id, response(201), at(0));
+        assert_eq!(outbox.prune_delivered(), 1);
+        // With its premise gone from the outbox, the reply no longer waits.
+        assert_eq!(due_ids(&outbox, at(0)), [b.id.as_str()]);
+    }
+}
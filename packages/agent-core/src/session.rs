@@ -1,7 +1,8 @@
 //! Node session — URL helpers for a resolved agent↔node connection.
 //!
 //! A [`NodeSession`] is constructed once the host has fetched the node's
-//! discovery document and extracted the `api_base`.  It provides URL
+//! discovery document.  It keeps that document — capabilities, protocol
+//! version, signing and proof-of-work requirements — and provides URL
 //! computation for every node API endpoint the agent might call.  There is
 //! no I/O here — the host supplies the discovery JSON, the session returns
 //! strings, and the host makes the HTTP calls.
@@ -15,17 +16,24 @@
 //! // 2. Host fetches the discovery document (I/O happens here).
 //! let discovery_json = http_get(addr.well_known_url()).await?;
 //!
-//! // 3. Build the session — pure computation from here on.
+//! // 3. Build the session — pure computation from here on.  Fails if the
+//! //    node speaks an incompatible protocol version.
 //! let session = NodeSession::from_discovery(&discovery_json, &identity.did())?;
 //!
-//! // 4. Use URL helpers for all subsequent calls.
+//! // 4. Fail early if the node lacks something the agent relies on.
+//! session.require(&[Capability::Sse, Capability::Subgraph])?;
+//!
+//! // 5. Use URL helpers for all subsequent calls.
 //! let body = http_post(session.register_url(), register_payload).await?;
 //! let inbox = http_get(session.inbox_url()).await?;
 //! ```
 
+use semanticweft_node_api::{Capability, NodeInfo};
 use thiserror::Error;
 use urlencoding::encode;
 
+use crate::AgentAddress;
+
 /// Errors that can occur when building a [`NodeSession`].
 #[derive(Debug, Error, PartialEq)]
 pub enum SessionError {
@@ -34,20 +42,35 @@ pub enum SessionError {
 
     #[error("discovery document is missing 'api_base' field")]
     MissingApiBase,
+
+    /// The node speaks a protocol version with a different major version.
+    #[error(
+        "node speaks protocol {0}, which is incompatible with {}",
+        NodeInfo::PROTOCOL_VERSION
+    )]
+    IncompatibleProtocol(String),
+
+    /// The node does not advertise a capability the agent requires.
+    #[error("node does not support the '{0}' capability")]
+    MissingCapability(Capability),
 }
 
 /// A resolved agent↔node session.
 ///
 /// Constructed from the node's discovery document and the agent's own DID.
-/// All methods return URL strings; the host makes the actual HTTP requests.
+/// URL methods return strings; the host makes the actual HTTP requests.
 #[derive(Debug, Clone, PartialEq)]
 pub struct NodeSession {
-    /// The node's current API base URL, e.g. `https://sweft.example.com/v1`.
-    /// Sourced from the `api_base` field in the discovery document.
+    /// The node's versioned API base URL, e.g. `https://sweft.example.com/v1`.
+    /// Built from the `api_base` host URL in the discovery document.
     pub api_base: String,
 
     /// The acting agent's DID.
     pub own_did: String,
+
+    /// The node's discovery document. `None` for sessions built with
+    /// [`NodeSession::new`].
+    pub node_info: Option<NodeInfo>,
 }
 
 impl NodeSession {
@@ -57,21 +80,70 @@ impl NodeSession {
         Self {
             api_base: api_base.into().trim_end_matches('/').to_string(),
             own_did: own_did.into(),
+            node_info: None,
         }
     }
 
-    /// Parse the node's `/.well-known/semanticweft` response JSON to
-    /// extract `api_base`, then build a session.
+    /// Parse the node's `/.well-known/semanticweft` response JSON and build
+    /// a session with [`NodeSession::from_node_info`].
     pub fn from_discovery(discovery_json: &str, own_did: &str) -> Result<Self, SessionError> {
         let doc: serde_json::Value = serde_json::from_str(discovery_json)
             .map_err(|e| SessionError::ParseError(e.to_string()))?;
 
-        let api_base = doc
+        if !doc
             .get("api_base")
-            .and_then(|v| v.as_str())
-            .ok_or(SessionError::MissingApiBase)?;
+            .is_some_and(serde_json::Value::is_string)
+        {
+            return Err(SessionError::MissingApiBase);
+        }
+        let info: NodeInfo =
+            serde_json::from_value(doc).map_err(|e| SessionError::ParseError(e.to_string()))?;
+
+        Self::from_node_info(info, own_did)
+    }
+
+    /// Build a session from an already-parsed discovery document.
+    ///
+    /// Rejects nodes whose `protocol_version` has a different major version
+    /// than [`NodeInfo::PROTOCOL_VERSION`]. `api_base` is the node's host URL
+    /// (spec §3); `/v1` is appended unless the node already included it.
+    pub fn from_node_info(
+        info: NodeInfo,
+        own_did: impl Into<String>,
+    ) -> Result<Self, SessionError> {
+        if !info.is_compatible() {
+            return Err(SessionError::IncompatibleProtocol(info.protocol_version));
+        }
+        let origin = info.api_base.trim_end_matches('/').trim_end_matches("/v1");
+        let mut session = Self::new(format!("{origin}/v1"), own_did);
+        session.node_info = Some(info);
+        Ok(session)
+    }
+
+    // ── Node capabilities ─────────────────────────────────────────────────────
+
+    /// Whether the node advertises `capability`. Always `false` when the
+    /// session has no discovery document.
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.node_info
+            .as_ref()
+            .is_some_and(|info| info.supports(capability))
+    }
+
+    /// Check that the node advertises every capability in `capabilities`,
+    /// failing with the first one it lacks.
+    pub fn require(&self, capabilities: &[Capability]) -> Result<(), SessionError> {
+        match capabilities.iter().find(|c| !self.supports(**c)) {
+            Some(missing) => Err(SessionError::MissingCapability(*missing)),
+            None => Ok(()),
+        }
+    }
 
-        Ok(Self::new(api_base, own_did))
+    /// Whether the node rejects unsigned units.
+    pub fn signing_required(&self) -> bool {
+        self.node_info
+            .as_ref()
+            .is_some_and(|info| info.signing_required)
     }
 
     // ── Agent endpoints ───────────────────────────────────────────────────────
@@ -83,7 +155,67 @@ impl NodeSession {
 
     /// `{api_base}/agents/{own_did_encoded}`
     pub fn register_url(&self) -> String {
-        format!("{}/agents/{}", self.api_base, encode(&self.own_did))
+        self.agent_url(&self.own_did)
+    }
+
+    /// `{api_base}/agents/{own_did_encoded}/apply`
+    pub fn apply_url(&self) -> String {
+        format!("{}/apply", self.register_url())
+    }
+
+    /// `{api_base}/agents/{did_encoded}` — any agent's profile.
+    pub fn agent_url(&self, did: &str) -> String {
+        format!("{}/agents/{}", self.api_base, encode(did))
+    }
+
+    /// `{api_base}/agents/{did_encoded}/succession` — `POST` a succession
+    /// statement retiring `did`, or `GET` the one already accepted.
+    pub fn succession_url(&self, did: &str) -> String {
+        format!("{}/succession", self.agent_url(did))
+    }
+
+    /// `{origin}/.well-known/webfinger?resource=acct:{did}@{host}` — resolve
+    /// `did` on this node, with origin and host taken from `api_base`. Pass
+    /// the response to [`resolve_jrd`](crate::resolve_jrd) with
+    /// [`address`](Self::address).
+    pub fn webfinger_url(&self, did: &str) -> String {
+        let (start, end) = self.authority();
+        crate::webfinger::webfinger_url(&self.api_base[..end], did, &self.api_base[start..end])
+    }
+
+    /// `did` as an address on this node, with the host taken from
+    /// `api_base`.
+    pub fn address(&self, did: &str) -> AgentAddress {
+        let (start, end) = self.authority();
+        AgentAddress::new(did, &self.api_base[start..end])
+    }
+
+    /// Byte range of the authority (`host[:port]`) in `api_base`.
+    fn authority(&self) -> (usize, usize) {
+        let start = self.api_base.find("://").map_or(0, |i| i + 3);
+        let end = self.api_base[start..]
+            .find('/')
+            .map_or(self.api_base.len(), |i| start + i);
+        (start, end)
+    }
+
+    // ── Follow endpoints ──────────────────────────────────────────────────────
+
+    /// `{api_base}/agents/{did_encoded}/following` — `POST` with the own DID
+    /// to follow someone.
+    pub fn following_url(&self, did: &str) -> String {
+        format!("{}/following", self.agent_url(did))
+    }
+
+    /// `{api_base}/agents/{did_encoded}/followers`
+    pub fn followers_url(&self, did: &str) -> String {
+        format!("{}/followers", self.agent_url(did))
+    }
+
+    /// `{api_base}/agents/{own_did_encoded}/following/{target_encoded}` —
+    /// `DELETE` to unfollow.
+    pub fn unfollow_url(&self, target: &str) -> String {
+        format!("{}/{}", self.following_url(&self.own_did), encode(target))
     }
 
     // ── Unit endpoints ────────────────────────────────────────────────────────
@@ -93,6 +225,19 @@ impl NodeSession {
         format!("{}/units", self.api_base)
     }
 
+    /// `{api_base}/units/{id_encoded}`
+    pub fn unit_url(&self, id: &str) -> String {
+        format!("{}/units/{}", self.api_base, encode(id))
+    }
+
+    /// `{api_base}/units/{id_encoded}/subgraph[?depth={depth}]`
+    pub fn subgraph_url(&self, id: &str, depth: Option<u32>) -> String {
+        match depth {
+            Some(d) => format!("{}/subgraph?depth={d}", self.unit_url(id)),
+            None => format!("{}/subgraph", self.unit_url(id)),
+        }
+    }
+
     // ── Sync endpoint ─────────────────────────────────────────────────────────
 
     /// `{api_base}/sync?limit={limit}[&after={cursor}]`
@@ -108,6 +253,12 @@ impl NodeSession {
         }
     }
 
+    /// `{api_base}/sync` — the SSE stream; resume with `Last-Event-ID`
+    /// rather than `?after=`. See [`SyncCursor`](crate::SyncCursor).
+    pub fn sync_stream_url(&self) -> String {
+        format!("{}/sync", self.api_base)
+    }
+
     // ── Peer endpoints ────────────────────────────────────────────────────────
 
     /// `{api_base}/peers`
@@ -147,6 +298,66 @@ mod tests {
         assert_eq!(session().units_url(), "https://sweft.example.com/v1/units");
     }
 
+    #[test]
+    fn agent_and_apply_urls() {
+        assert_eq!(
+            session().apply_url(),
+            "https://sweft.example.com/v1/agents/did%3Akey%3Az6MkFoo/apply"
+        );
+        assert_eq!(
+            session().agent_url("did:key:z6MkBar"),
+            "https://sweft.example.com/v1/agents/did%3Akey%3Az6MkBar"
+        );
+        assert_eq!(
+            session().succession_url("did:key:z6MkFoo"),
+            "https://sweft.example.com/v1/agents/did%3Akey%3Az6MkFoo/succession"
+        );
+    }
+
+    #[test]
+    fn webfinger_url_uses_api_base_origin() {
+        assert_eq!(
+            session().webfinger_url("did:key:z6MkBar"),
+            "https://sweft.example.com/.well-known/webfinger?resource=acct%3Adid%3Akey%3Az6MkBar%40sweft.example.com"
+        );
+        let local = NodeSession::new("http://127.0.0.1:3000/v1", "did:key:z6MkFoo");
+        assert_eq!(
+            local.webfinger_url("did:key:z6MkFoo"),
+            "http://127.0.0.1:3000/.well-known/webfinger?resource=acct%3Adid%3Akey%3Az6MkFoo%40127.0.0.1%3A3000"
+        );
+        assert_eq!(
+            local.address("did:key:z6MkFoo"),
+            AgentAddress::new("did:key:z6MkFoo", "127.0.0.1:3000")
+        );
+    }
+
+    #[test]
+    fn follow_urls() {
+        let s = session();
+        assert_eq!(
+            s.followers_url("did:key:z6MkBar"),
+            "https://sweft.example.com/v1/agents/did%3Akey%3Az6MkBar/followers"
+        );
+        assert_eq!(
+            s.unfollow_url("did:key:z6MkBar"),
+            "https://sweft.example.com/v1/agents/did%3Akey%3Az6MkFoo/following/did%3Akey%3Az6MkBar"
+        );
+    }
+
+    #[test]
+    fn unit_and_subgraph_urls() {
+        let s = session();
+        assert_eq!(s.unit_url("abc-123"), "https://sweft.example.com/v1/units/abc-123");
+        assert_eq!(
+            s.subgraph_url("abc-123", Some(2)),
+            "https://sweft.example.com/v1/units/abc-123/subgraph?depth=2"
+        );
+        assert_eq!(
+            s.subgraph_url("abc-123", None),
+            "https://sweft.example.com/v1/units/abc-123/subgraph"
+        );
+    }
+
     #[test]
     fn peers_url() {
         assert_eq!(session().peers_url(), "https://sweft.example.com/v1/peers");
@@ -184,6 +395,60 @@ mod tests {
         assert_eq!(s.api_base, "https://sweft.example.com/v1");
     }
 
+    #[test]
+    fn from_discovery_appends_version_to_host_url() {
+        let json = r#"{
+            "node_id": "did:key:z6MkNode",
+            "api_base": "https://sweft.example.com",
+            "protocol_version": "1.0",
+            "capabilities": ["sync", "sse"],
+            "signing_required": true,
+            "public_key": "z6MkNode"
+        }"#;
+        let s = NodeSession::from_discovery(json, "did:key:z6MkFoo").unwrap();
+        assert_eq!(s.api_base, "https://sweft.example.com/v1");
+        assert!(s.signing_required());
+        let info = s.node_info.as_ref().unwrap();
+        assert_eq!(info.public_key.as_deref(), Some("z6MkNode"));
+        assert_eq!(info.pow_required, None);
+    }
+
+    #[test]
+    fn capability_checks() {
+        let json = r#"{"node_id":"x","api_base":"https://h","protocol_version":"1.0","capabilities":["sync","sse"]}"#;
+        let s = NodeSession::from_discovery(json, "did:key:z6MkFoo").unwrap();
+        assert!(s.supports(Capability::Sse));
+        assert!(!s.supports(Capability::Subgraph));
+        assert_eq!(s.require(&[Capability::Sync, Capability::Sse]), Ok(()));
+        assert_eq!(
+            s.require(&[Capability::Sse, Capability::Subgraph]),
+            Err(SessionError::MissingCapability(Capability::Subgraph))
+        );
+        assert!(!session().supports(Capability::Sync));
+    }
+
+    #[test]
+    fn from_discovery_tolerates_unknown_capabilities() {
+        let json = r#"{"node_id":"x","api_base":"https://h","protocol_version":"1.2","capabilities":["sync","holograms"]}"#;
+        let s = NodeSession::from_discovery(json, "did:key:z6MkFoo").unwrap();
+        assert!(s.supports(Capability::Sync));
+        assert_eq!(
+            s.require(&[Capability::Sse]),
+            Err(SessionError::MissingCapability(Capability::Sse))
+        );
+    }
+
+    #[test]
+    fn from_discovery_rejects_other_major_versions() {
+        let json = r#"{"node_id":"x","api_base":"https://h","protocol_version":"2.0","capabilities":[]}"#;
+        assert_eq!(
+            NodeSession::from_discovery(json, "did:key:z6MkFoo"),
+            Err(SessionError::IncompatibleProtocol("2.0".into()))
+        );
+        let minor = json.replace("2.0", "1.4");
+        assert!(NodeSession::from_discovery(&minor, "did:key:z6MkFoo").is_ok());
+    }
+
     #[test]
     fn from_discovery_missing_api_base() {
         let json = r#"{"node_id":"did:key:z6MkNode"}"#;
@@ -0,0 +1,164 @@
+//! Key rotation — signed succession statements (spec §8.7).
+//!
+//! A `did:key` *is* its public key, so an agent that retires or loses a key
+//! has to move to a new DID. [`sign_succession`] produces the
+//! [`SuccessionStatement`] that links the two: the old key signs first, then
+//! the new key countersigns over the old key's signature. Nodes check it
+//! with [`verify_succession`] before moving the agent's profile, reputation
+//! and follows to the new DID.
+//!
+//! ```text
+//! statement = sign_succession(&old, &new, now_rfc3339, Some("key compromised"))
+//! POST session.succession_url(&old.did()) with statement as JSON
+//! // from now on, sign requests with `new`
+//! ```
+//!
+//! Both keys are needed at once. An attacker holding only the old key cannot
+//! hand the identity to a DID they do not control without its
+//! countersignature, and holding only a new key proves nothing at all.
+
+use semanticweft_node_api::SuccessionStatement;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::identity::AgentIdentity;
+
+/// Errors returned by [`verify_succession`].
+#[derive(Debug, Error, PartialEq)]
+pub enum SuccessionError {
+    /// The statement names the same DID on both sides.
+    #[error("predecessor and successor are the same DID")]
+    SameDid,
+
+    /// The old key's signature is malformed or does not verify.
+    #[error("predecessor signature invalid: {0}")]
+    PredecessorSignature(String),
+
+    /// The new key's countersignature is malformed or does not verify.
+    #[error("successor signature invalid: {0}")]
+    SuccessorSignature(String),
+}
+
+/// Build a succession statement from `old` to `new`, signed by both.
+///
+/// `created_at` is an ISO 8601 timestamp supplied by the host, since this
+/// crate has no clock.
+pub fn sign_succession(
+    old: &AgentIdentity,
+    new: &AgentIdentity,
+    created_at: impl Into<String>,
+    reason: Option<String>,
+) -> SuccessionStatement {
+    let mut statement = SuccessionStatement {
+        predecessor: old.did(),
+        successor: new.did(),
+        created_at: created_at.into(),
+        reason,
+        predecessor_signature: String::new(),
+        successor_signature: String::new(),
+    };
+    statement.predecessor_signature = encode(&old.sign(&predecessor_payload(&statement)));
+    statement.successor_signature = encode(&new.sign(&successor_payload(&statement)));
+    statement
+}
+
+/// Check both signatures on `statement` against the keys embedded in its
+/// `did:key` DIDs.
+pub fn verify_succession(statement: &SuccessionStatement) -> Result<(), SuccessionError> {
+    if statement.predecessor == statement.successor {
+        return Err(SuccessionError::SameDid);
+    }
+    semanticweft::verify_detached(
+        &statement.predecessor,
+        &predecessor_payload(statement),
+        &statement.predecessor_signature,
+    )
+    .map_err(|e| SuccessionError::PredecessorSignature(e.to_string()))?;
+    semanticweft::verify_detached(
+        &statement.successor,
+        &successor_payload(statement),
+        &statement.successor_signature,
+    )
+    .map_err(|e| SuccessionError::SuccessorSignature(e.to_string()))
+}
+
+/// The bytes the old key signs: the statement without either signature.
+fn predecessor_payload(statement: &SuccessionStatement) -> Vec<u8> {
+    canonical(statement, &["predecessor_signature", "successor_signature"])
+}
+
+/// The bytes the new key signs: the statement including the old key's
+/// signature, so the countersignature endorses that exact consent.
+fn successor_payload(statement: &SuccessionStatement) -> Vec<u8> {
+    canonical(statement, &["successor_signature"])
+}
+
+/// JCS (RFC 8785) form of `statement` with `omit` fields removed.
+fn canonical(statement: &SuccessionStatement, omit: &[&str]) -> Vec<u8> {
+    let mut value = serde_json::to_value(statement).expect("statement serializes");
+    if let Value::Object(map) = &mut value {
+        for key in omit {
+            map.remove(*key);
+        }
+    }
+    serde_jcs::to_vec(&value).expect("JSON value canonicalizes")
+}
+
+fn encode(signature: &[u8]) -> String {
+    format!("z{}", bs58::encode(signature).into_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: &str = "2026-03-01T12:00:00Z";
+
+    #[test]
+    fn signed_statement_verifies() {
+        let old = AgentIdentity::generate();
+        let new = AgentIdentity::generate();
+        let s = sign_succession(&old, &new, NOW, Some("scheduled".into()));
+        assert_eq!(s.predecessor, old.did());
+        assert_eq!(s.successor, new.did());
+        assert_eq!(verify_succession(&s), Ok(()));
+    }
+
+    #[test]
+    fn tampered_fields_fail() {
+        let old = AgentIdentity::generate();
+        let new = AgentIdentity::generate();
+        let s = sign_succession(&old, &new, NOW, None);
+
+        let mut hijacked = s.clone();
+        hijacked.successor = AgentIdentity::generate().did();
+        assert!(matches!(
+            verify_succession(&hijacked),
+            Err(SuccessionError::PredecessorSignature(_))
+        ));
+
+        let mut reasoned = s.clone();
+        reasoned.reason = Some("added later".into());
+        assert!(verify_succession(&reasoned).is_err());
+    }
+
+    #[test]
+    fn countersignature_from_another_key_fails() {
+        let old = AgentIdentity::generate();
+        let new = AgentIdentity::generate();
+        let mut s = sign_succession(&old, &new, NOW, None);
+        let other = sign_succession(&old, &AgentIdentity::generate(), NOW, None);
+        s.successor_signature = other.successor_signature;
+        assert!(matches!(
+            verify_succession(&s),
+            Err(SuccessionError::SuccessorSignature(_))
+        ));
+    }
+
+    #[test]
+    fn same_did_is_rejected() {
+        let key = AgentIdentity::generate();
+        let s = sign_succession(&key, &key, NOW, None);
+        assert_eq!(verify_succession(&s), Err(SuccessionError::SameDid));
+    }
+}
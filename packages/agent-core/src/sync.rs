@@ -0,0 +1,585 @@
+//! Sync stream consumption — following `/v1/sync` over SSE (spec §5.5.2).
+//!
+//! Two layers, both fed by the host:
+//!
+//! - [`SseParser`] turns Server-Sent Events bytes into [`SseEvent`]s. Chunks
+//!   may split lines, UTF-8 sequences and `\r\n` pairs anywhere.
+//! - [`SyncCursor`] tracks the id of the last unit received and says which
+//!   request to make next. A node's SSE replay covers only one page of
+//!   history, and a consumer that falls behind the live channel gets an
+//!   `event: lag` instead of the units it missed, so the cursor alternates
+//!   between polling `/v1/sync` in JSON mode until `has_more` is false and
+//!   following the stream from there.
+//!
+//! ```text
+//! let mut sync = SyncCursor::new(load_cursor());
+//! loop {
+//!     match sync.next_request(&session) {
+//!         SyncRequest::Poll { url } => {
+//!             let body = http_get(url, "application/json").await?;
+//!             handle(sync.on_page(&body)?);
+//!         }
+//!         SyncRequest::Stream { url, last_event_id } => {
+//!             let mut body = http_get_stream(url, "text/event-stream", last_event_id).await?;
+//!             while let Some(chunk) = body.next().await {
+//!                 handle(sync.on_chunk(&chunk));
+//!                 if sync.phase() == SyncPhase::CatchingUp { break } // lagged
+//!             }
+//!             sync.on_disconnect();
+//!         }
+//!     }
+//! }
+//! // handle: deliver SyncEvent::Unit, persist SyncEvent::Cursor
+//! ```
+//!
+//! Catch-up and stream overlap, so the cursor drops units it has already
+//! delivered. There is no I/O and no clock; after a failed request the host
+//! waits as long as it likes (see [`SseParser::retry`]) before asking for
+//! the next one.
+
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+
+use semanticweft::SemanticUnit;
+use semanticweft_node_api::ListResponse;
+use thiserror::Error;
+
+use crate::session::NodeSession;
+
+/// Page size requested while catching up, the maximum the spec allows.
+pub const DEFAULT_PAGE_LIMIT: usize = 500;
+
+/// Upper bound on the ids remembered for de-duplication.
+const SEEN_CAPACITY: usize = 10_000;
+
+/// Errors returned by [`SyncCursor::on_page`].
+#[derive(Debug, Error, PartialEq)]
+pub enum SyncError {
+    #[error("failed to parse sync page: {0}")]
+    ParseError(String),
+}
+
+// ── SSE framing ───────────────────────────────────────────────────────────────
+
+/// One dispatched Server-Sent Event.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SseEvent {
+    /// The last event ID in effect when the event was dispatched — the
+    /// value to send as `Last-Event-ID` after it.
+    pub id: Option<String>,
+
+    /// The `event:` type. `None` means the default, `message`.
+    pub event: Option<String>,
+
+    /// The `data:` lines, joined with `\n`.
+    pub data: String,
+}
+
+/// Incremental Server-Sent Events parser (WHATWG HTML §9.2.6).
+///
+/// Use one parser per connection.
+#[derive(Debug, Clone, Default)]
+pub struct SseParser {
+    /// Bytes of the line being read.
+    line: Vec<u8>,
+    /// The previous chunk ended in `\r`; drop a leading `\n`.
+    after_cr: bool,
+    /// At least one line has been read, so a BOM can no longer appear.
+    started: bool,
+    event: Option<String>,
+    data: String,
+    has_data: bool,
+    last_event_id: Option<String>,
+    retry: Option<Duration>,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse `chunk` and return the events it completes.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            if std::mem::take(&mut self.after_cr) && byte == b'\n' {
+                continue;
+            }
+            match byte {
+                b'\r' => {
+                    self.after_cr = true;
+                    self.end_line(&mut events);
+                }
+                b'\n' => self.end_line(&mut events),
+                _ => self.line.push(byte),
+            }
+        }
+        events
+    }
+
+    /// The stream's last event ID, to send as `Last-Event-ID` on reconnect.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// The reconnection delay the node asked for with a `retry:` field.
+    pub fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    fn end_line(&mut self, events: &mut Vec<SseEvent>) {
+        let bytes = std::mem::take(&mut self.line);
+        let text = String::from_utf8_lossy(&bytes);
+        let mut line: &str = &text;
+        if !std::mem::replace(&mut self.started, true) {
+            line = line.strip_prefix('\u{feff}').unwrap_or(line);
+        }
+
+        if line.is_empty() {
+            self.dispatch(events);
+            return;
+        }
+        if line.starts_with(':') {
+            return; // comment / keep-alive
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((f, v)) => (f, v.strip_prefix(' ').unwrap_or(v)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => {
+                if self.has_data {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value);
+                self.has_data = true;
+            }
+            "id" if !value.contains('\0') => {
+                self.last_event_id = (!value.is_empty()).then(|| value.to_string());
+            }
+            "retry" => {
+                if let Ok(ms) = value.parse::<u64>() {
+                    self.retry = Some(Duration::from_millis(ms));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn dispatch(&mut self, events: &mut Vec<SseEvent>) {
+        let event = self.event.take();
+        let data = std::mem::take(&mut self.data);
+        if std::mem::take(&mut self.has_data) {
+            events.push(SseEvent {
+                id: self.last_event_id.clone(),
+                event: event.filter(|e| !e.is_empty()),
+                data,
+            });
+        }
+    }
+}
+
+// ── Sync state machine ────────────────────────────────────────────────────────
+
+/// What a [`SyncCursor`] is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncPhase {
+    /// Polling `/v1/sync` in JSON mode until the node has nothing more.
+    CatchingUp,
+    /// Following the SSE stream.
+    Streaming,
+}
+
+/// The request the host should make next.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncRequest {
+    /// `GET url` with `Accept: application/json`. Pass the body to
+    /// [`SyncCursor::on_page`].
+    Poll { url: String },
+
+    /// `GET url` with `Accept: text/event-stream`, and `Last-Event-ID` when
+    /// set. Pass the body to [`SyncCursor::on_chunk`] as it arrives and call
+    /// [`SyncCursor::on_disconnect`] when the connection ends.
+    Stream {
+        url: String,
+        last_event_id: Option<String>,
+    },
+}
+
+/// Output of a [`SyncCursor`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncEvent {
+    /// A unit not delivered before.
+    Unit(Box<SemanticUnit>),
+
+    /// The cursor moved; persist it so a restarted host resumes here.
+    /// Emitted at most once per page or chunk, after its units.
+    Cursor(String),
+
+    /// The node reported that this consumer fell behind. Close the stream;
+    /// [`SyncCursor::next_request`] catches up from the cursor.
+    Resync,
+
+    /// An event whose data is not a unit, with the parse error. Skipped.
+    Malformed(String),
+}
+
+/// Follows a node's public unit stream from a cursor. See the module docs.
+#[derive(Debug, Clone)]
+pub struct SyncCursor {
+    cursor: Option<String>,
+    phase: SyncPhase,
+    parser: SseParser,
+    /// Recently delivered ids; the stream may repeat units that the
+    /// catch-up poll already returned.
+    seen: Seen,
+    page_limit: usize,
+}
+
+impl SyncCursor {
+    /// Start after `cursor` (a unit id), or from the beginning.
+    pub fn new(cursor: Option<String>) -> Self {
+        Self {
+            cursor,
+            phase: SyncPhase::CatchingUp,
+            parser: SseParser::new(),
+            seen: Seen::new(SEEN_CAPACITY),
+            page_limit: DEFAULT_PAGE_LIMIT,
+        }
+    }
+
+    /// Request `limit` units per catch-up page instead of
+    /// [`DEFAULT_PAGE_LIMIT`].
+    pub fn with_page_limit(mut self, limit: usize) -> Self {
+        self.page_limit = limit;
+        self
+    }
+
+    /// The id of the last unit delivered.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    pub fn phase(&self) -> SyncPhase {
+        self.phase
+    }
+
+    /// The reconnection delay the node asked for on the current stream.
+    pub fn retry(&self) -> Option<Duration> {
+        self.parser.retry()
+    }
+
+    /// The next request to make against `session`'s node.
+    pub fn next_request(&self, session: &NodeSession) -> SyncRequest {
+        match self.phase {
+            SyncPhase::CatchingUp => SyncRequest::Poll {
+                url: session.sync_url(self.cursor(), self.page_limit),
+            },
+            SyncPhase::Streaming => SyncRequest::Stream {
+                url: session.sync_stream_url(),
+                last_event_id: self.cursor.clone(),
+            },
+        }
+    }
+
+    /// Take a JSON `/v1/sync` response body. Once the node reports no more
+    /// units, the cursor switches to [`SyncPhase::Streaming`].
+    pub fn on_page(&mut self, body: &str) -> Result<Vec<SyncEvent>, SyncError> {
+        let page: ListResponse =
+            serde_json::from_str(body).map_err(|e| SyncError::ParseError(e.to_string()))?;
+        Ok(self.on_list(page))
+    }
+
+    /// [`on_page`](Self::on_page) for a page the host has already decoded.
+    pub fn on_list(&mut self, page: ListResponse) -> Vec<SyncEvent> {
+        let before = self.cursor.clone();
+        // An empty page cannot move the cursor, so asking again would loop.
+        let caught_up = !page.has_more || page.units.is_empty();
+
+        // Pages hold units after the cursor in id order, so the last one is
+        // the new cursor even if every unit on the page was a duplicate;
+        // otherwise the next poll would ask for the same page again.
+        let last = page.units.last().map(|u| u.id.clone());
+        let mut events = Vec::new();
+        for unit in page.units {
+            self.deliver(unit, &mut events);
+        }
+        if last.is_some() {
+            self.cursor = last;
+        }
+        if caught_up {
+            self.phase = SyncPhase::Streaming;
+            self.parser = SseParser::new();
+        }
+        self.push_cursor(before, &mut events);
+        events
+    }
+
+    /// Take the next bytes of the SSE stream. Ignored unless streaming, so
+    /// bytes that arrive after a [`SyncEvent::Resync`] are dropped.
+    pub fn on_chunk(&mut self, chunk: &[u8]) -> Vec<SyncEvent> {
+        let mut events = Vec::new();
+        if self.phase != SyncPhase::Streaming {
+            return events;
+        }
+        let before = self.cursor.clone();
+        let mut lagged = false;
+        for event in self.parser.feed(chunk) {
+            match event.event.as_deref() {
+                Some("lag") => {
+                    lagged = true;
+                    break;
+                }
+                None | Some("message") => match serde_json::from_str(&event.data) {
+                    Ok(unit) => self.deliver(unit, &mut events),
+                    Err(e) => events.push(SyncEvent::Malformed(e.to_string())),
+                },
+                Some(_) => {}
+            }
+        }
+        self.push_cursor(before, &mut events);
+        if lagged {
+            self.phase = SyncPhase::CatchingUp;
+            events.push(SyncEvent::Resync);
+        }
+        events
+    }
+
+    /// The stream ended, cleanly or not. Units may have been submitted
+    /// since the node's replay, so the next request catches up again.
+    pub fn on_disconnect(&mut self) {
+        self.phase = SyncPhase::CatchingUp;
+        self.parser = SseParser::new();
+    }
+
+    fn deliver(&mut self, unit: SemanticUnit, events: &mut Vec<SyncEvent>) {
+        if !self.seen.insert(&unit.id) {
+            return;
+        }
+        self.cursor = Some(unit.id.clone());
+        events.push(SyncEvent::Unit(Box::new(unit)));
+    }
+
+    fn push_cursor(&self, before: Option<String>, events: &mut Vec<SyncEvent>) {
+        if self.cursor != before {
+            if let Some(cursor) = &self.cursor {
+                events.push(SyncEvent::Cursor(cursor.clone()));
+            }
+        }
+    }
+}
+
+/// A set of the most recently inserted ids, evicting the oldest first.
+#[derive(Debug, Clone)]
+struct Seen {
+    ids: HashSet<String>,
+    order: VecDeque<String>,
+    capacity: usize,
+}
+
+impl Seen {
+    fn new(capacity: usize) -> Self {
+        Self {
+            ids: HashSet::new(),
+            order: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Remember `id`; `false` if it was already remembered.
+    fn insert(&mut self, id: &str) -> bool {
+        if !self.ids.insert(id.to_string()) {
+            return false;
+        }
+        self.order.push_back(id.to_string());
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semanticweft::UnitType;
+
+    fn unit(content: &str) -> SemanticUnit {
+        SemanticUnit::new(UnitType::Assertion, content, "did:key:z6MkFoo")
+    }
+
+    fn frame(unit: &SemanticUnit) -> String {
+        format!(
+            "id: {}\ndata: {}\n\n",
+            unit.id,
+            serde_json::to_string(unit).unwrap()
+        )
+    }
+
+    fn page(units: &[&SemanticUnit], has_more: bool) -> String {
+        serde_json::json!({ "units": units, "has_more": has_more }).to_string()
+    }
+
+    fn session() -> NodeSession {
+        NodeSession::new("https://sweft.example.com/v1", "")
+    }
+
+    #[test]
+    fn parser_handles_split_chunks_and_line_endings() {
+        let stream = "\u{feff}: keepalive\r\nid: 1\r\nevent: lag\r\ndata: a\r\ndata: é\r\n\r\ndata: b\n\n";
+        let mut parser = SseParser::new();
+        let mut events = Vec::new();
+        for byte in stream.as_bytes() {
+            events.extend(parser.feed(std::slice::from_ref(byte)));
+        }
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    id: Some("1".into()),
+                    event: Some("lag".into()),
+                    data: "a\né".into(),
+                },
+                SseEvent {
+                    id: Some("1".into()),
+                    event: None,
+                    data: "b".into(),
+                },
+            ]
+        );
+        assert_eq!(parser.last_event_id(), Some("1"));
+    }
+
+    #[test]
+    fn parser_skips_events_without_data() {
+        let mut parser = SseParser::new();
+        assert!(parser.feed(b"event: lag\nid: 7\nretry: 1500\n\n").is_empty());
+        assert_eq!(parser.last_event_id(), Some("7"));
+        assert_eq!(parser.retry(), Some(Duration::from_millis(1500)));
+        assert_eq!(parser.feed(b"data:x\n\n")[0].event, None);
+    }
+
+    #[test]
+    fn catches_up_then_streams_from_cursor() {
+        let (a, b) = (unit("a"), unit("b"));
+        let mut sync = SyncCursor::new(None).with_page_limit(1);
+        assert_eq!(
+            sync.next_request(&session()),
+            SyncRequest::Poll {
+                url: "https://sweft.example.com/v1/sync?limit=1".into()
+            }
+        );
+        let events = sync.on_page(&page(&[&a], true)).unwrap();
+        assert_eq!(
+            events,
+            vec![SyncEvent::Unit(Box::new(a.clone())), SyncEvent::Cursor(a.id.clone())]
+        );
+        assert_eq!(sync.phase(), SyncPhase::CatchingUp);
+
+        sync.on_page(&page(&[&b], false)).unwrap();
+        assert_eq!(
+            sync.next_request(&session()),
+            SyncRequest::Stream {
+                url: "https://sweft.example.com/v1/sync".into(),
+                last_event_id: Some(b.id.clone()),
+            }
+        );
+    }
+
+    #[test]
+    fn all_duplicate_page_still_advances() {
+        let (a, b, c) = (unit("a"), unit("b"), unit("c"));
+        let mut sync = SyncCursor::new(None);
+        sync.on_page(&page(&[], false)).unwrap();
+        // The live channel runs in arrival order, so `c` can come after a
+        // newer unit and leave the cursor behind units already delivered.
+        let stream = format!("{}{}{}", frame(&a), frame(&c), frame(&b));
+        sync.on_chunk(stream.as_bytes());
+        sync.on_disconnect();
+        assert_eq!(sync.cursor(), Some(b.id.as_str()));
+
+        let events = sync.on_page(&page(&[&c], true)).unwrap();
+        assert_eq!(events, vec![SyncEvent::Cursor(c.id.clone())]);
+        assert!(matches!(
+            sync.next_request(&session()),
+            SyncRequest::Poll { url } if url.contains(&format!("after={}", c.id))
+        ));
+    }
+
+    #[test]
+    fn seen_evicts_oldest_first() {
+        let mut seen = Seen::new(2);
+        assert!(seen.insert("a"));
+        assert!(seen.insert("b"));
+        assert!(!seen.insert("a"));
+        assert!(seen.insert("c"));
+        assert!(seen.insert("a"));
+        assert!(!seen.insert("c"));
+    }
+
+    #[test]
+    fn stream_drops_units_already_polled() {
+        let (a, b) = (unit("a"), unit("b"));
+        let mut sync = SyncCursor::new(None);
+        sync.on_page(&page(&[&a], false)).unwrap();
+
+        let stream = format!("{}{}", frame(&a), frame(&b));
+        let (head, tail) = stream.as_bytes().split_at(stream.len() / 2);
+        let mut events = sync.on_chunk(head);
+        events.extend(sync.on_chunk(tail));
+        assert_eq!(
+            events,
+            vec![SyncEvent::Unit(Box::new(b.clone())), SyncEvent::Cursor(b.id.clone())]
+        );
+    }
+
+    #[test]
+    fn lag_and_disconnect_return_to_catch_up() {
+        let (a, b) = (unit("a"), unit("b"));
+        let mut sync = SyncCursor::new(Some("0".into()));
+        sync.on_page(&page(&[], false)).unwrap();
+
+        let chunk = format!(
+            "{}event: lag\ndata: {{}}\n\n{}",
+            frame(&a),
+            frame(&b)
+        );
+        assert_eq!(
+            sync.on_chunk(chunk.as_bytes()),
+            vec![
+                SyncEvent::Unit(Box::new(a.clone())),
+                SyncEvent::Cursor(a.id.clone()),
+                SyncEvent::Resync
+            ]
+        );
+        assert!(sync.on_chunk(frame(&b).as_bytes()).is_empty());
+        assert!(matches!(
+            sync.next_request(&session()),
+            SyncRequest::Poll { url } if url.ends_with(&format!("after={}&limit=500", a.id))
+        ));
+
+        sync.on_page(&page(&[], false)).unwrap();
+        sync.on_disconnect();
+        assert_eq!(sync.phase(), SyncPhase::CatchingUp);
+    }
+
+    #[test]
+    fn malformed_events_and_pages() {
+        let mut sync = SyncCursor::new(None);
+        assert!(matches!(
+            sync.on_page("<html>"),
+            Err(SyncError::ParseError(_))
+        ));
+        sync.on_page(&page(&[], false)).unwrap();
+        assert!(matches!(
+            sync.on_chunk(b"data: not json\n\n").as_slice(),
+            [SyncEvent::Malformed(_)]
+        ));
+        assert_eq!(sync.cursor(), None);
+    }
+}
@@ -0,0 +1,177 @@
+//! WebFinger resolution — from an agent address to its endpoints (spec §8.4).
+//!
+//! Rather than assuming that a remote node lays out its paths like this one,
+//! a host can ask the node where an agent lives. [`AgentAddress::webfinger_url`]
+//! (or [`NodeSession::webfinger_url`] for the session's own node) gives the
+//! URL to fetch; [`resolve_jrd`] turns the response into a
+//! [`ResolvedAgent`].
+//!
+//! ```text
+//! let addr = AgentAddress::parse("did:key:z6Mk…@sweft.example.com")?;
+//! let jrd_json = http_get(addr.webfinger_url()).await?;
+//! let agent = resolve_jrd(&addr, &jrd_json)?;
+//! http_post(agent.inbox_url, signed_unit).await?;
+//! ```
+//!
+//! [`AgentAddress::webfinger_url`]: crate::AgentAddress::webfinger_url
+//! [`NodeSession::webfinger_url`]: crate::NodeSession::webfinger_url
+
+use semanticweft_node_api::{
+    webfinger::{REL_INBOX, REL_PROFILE},
+    Jrd,
+};
+use thiserror::Error;
+
+use crate::AgentAddress;
+
+/// Errors returned by [`resolve_jrd`].
+#[derive(Debug, Error, PartialEq)]
+pub enum WebFingerError {
+    /// The response is not a JRD.
+    #[error("failed to parse JRD: {0}")]
+    ParseError(String),
+
+    /// The JRD describes a different agent than the one asked for.
+    #[error("JRD subject {subject} does not name {did}")]
+    SubjectMismatch { did: String, subject: String },
+
+    /// The JRD has no profile (`self`) link.
+    #[error("JRD has no profile link")]
+    MissingProfileLink,
+}
+
+/// Where a remote node says an agent lives.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedAgent {
+    /// The agent's DID.
+    pub did: String,
+
+    /// The agent's profile URL (`GET` for its `AgentProfile`).
+    pub profile_url: String,
+
+    /// The agent's inbox URL. Taken from the JRD's inbox link; nodes that do
+    /// not advertise one serve the inbox at `{profile_url}/inbox`.
+    pub inbox_url: String,
+}
+
+/// Parse a WebFinger response for `address` into a [`ResolvedAgent`].
+///
+/// The JRD's `subject` (`acct:` prefix optional) or one of its `aliases`
+/// must name the address — its DID, and its host when the identifier has
+/// one — so a node cannot answer for a different agent or node.
+pub fn resolve_jrd(address: &AgentAddress, jrd_json: &str) -> Result<ResolvedAgent, WebFingerError> {
+    let jrd: Jrd =
+        serde_json::from_str(jrd_json).map_err(|e| WebFingerError::ParseError(e.to_string()))?;
+    let did = address.did.as_str();
+
+    if !names(&jrd.subject, address) && !jrd.aliases.iter().any(|alias| names(alias, address)) {
+        return Err(WebFingerError::SubjectMismatch {
+            did: did.to_string(),
+            subject: jrd.subject,
+        });
+    }
+
+    let profile_url = jrd
+        .href(REL_PROFILE)
+        .ok_or(WebFingerError::MissingProfileLink)?
+        .to_string();
+    let inbox_url = jrd
+        .href(REL_INBOX)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}/inbox", profile_url.trim_end_matches('/')));
+
+    Ok(ResolvedAgent {
+        did: did.to_string(),
+        profile_url,
+        inbox_url,
+    })
+}
+
+/// `{origin}/.well-known/webfinger?resource=acct:{did}@{host}`, with the
+/// resource percent-encoded.
+pub(crate) fn webfinger_url(origin: &str, did: &str, host: &str) -> String {
+    format!(
+        "{}/.well-known/webfinger?resource={}",
+        origin.trim_end_matches('/'),
+        urlencoding::encode(&format!("acct:{did}@{host}"))
+    )
+}
+
+/// Whether a JRD identifier — `acct:{did}@{host}`, `{did}@{host}` or a bare
+/// DID — names `address`.
+fn names(identifier: &str, address: &AgentAddress) -> bool {
+    let identifier = identifier.strip_prefix("acct:").unwrap_or(identifier);
+    match identifier.rsplit_once('@') {
+        Some((did, host)) => did == address.did && host.eq_ignore_ascii_case(&address.hostname),
+        None => identifier == address.did,
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DID: &str = "did:key:z6MkFoo";
+
+    fn addr() -> AgentAddress {
+        AgentAddress::new(DID, "sweft.example.com")
+    }
+
+    #[test]
+    fn follows_advertised_links() {
+        let json = r#"{
+            "subject": "acct:did:key:z6MkFoo@sweft.example.com",
+            "links": [
+                { "rel": "self", "href": "https://api.example.com/agents/foo" },
+                { "rel": "http://www.w3.org/ns/ldp#inbox", "href": "https://inbox.example.com/foo" }
+            ]
+        }"#;
+        let agent = resolve_jrd(&addr(), json).unwrap();
+        assert_eq!(agent.profile_url, "https://api.example.com/agents/foo");
+        assert_eq!(agent.inbox_url, "https://inbox.example.com/foo");
+    }
+
+    #[test]
+    fn inbox_defaults_next_to_profile() {
+        let json = r#"{
+            "subject": "did:key:z6MkFoo@sweft.example.com",
+            "links": [{ "rel": "self", "href": "https://sweft.example.com/v1/agents/x/" }]
+        }"#;
+        assert_eq!(
+            resolve_jrd(&addr(), json).unwrap().inbox_url,
+            "https://sweft.example.com/v1/agents/x/inbox"
+        );
+    }
+
+    #[test]
+    fn rejects_other_subjects_and_missing_links() {
+        let other = r#"{"subject":"acct:did:key:z6MkBar@h","links":[{"rel":"self","href":"x"}]}"#;
+        assert!(matches!(
+            resolve_jrd(&addr(), other),
+            Err(WebFingerError::SubjectMismatch { .. })
+        ));
+
+        let elsewhere =
+            r#"{"subject":"acct:did:key:z6MkFoo@evil.example","links":[{"rel":"self","href":"x"}]}"#;
+        assert!(matches!(
+            resolve_jrd(&addr(), elsewhere),
+            Err(WebFingerError::SubjectMismatch { .. })
+        ));
+        let upper =
+            r#"{"subject":"acct:did:key:z6MkFoo@SWEFT.example.com","links":[{"rel":"self","href":"x"}]}"#;
+        assert!(resolve_jrd(&addr(), upper).is_ok());
+
+        let aliased = r#"{"subject":"https://h/a","aliases":["did:key:z6MkFoo"],"links":[]}"#;
+        assert_eq!(
+            resolve_jrd(&addr(), aliased),
+            Err(WebFingerError::MissingProfileLink)
+        );
+
+        assert!(matches!(
+            resolve_jrd(&addr(), "<html>"),
+            Err(WebFingerError::ParseError(_))
+        ));
+    }
+}
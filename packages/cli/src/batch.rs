@@ -0,0 +1,846 @@
+//! `sweft submit --batch` — submit a whole graph in dependency order.
+//!
+//! Units are read from an NDJSON file (one unit per line; a JSON array is
+//! also accepted) and ordered so that every unit is submitted after the
+//! batch units it references. A unit is only dispatched once all of its
+//! in-batch references have been accepted; if one is rejected, everything
+//! that depends on it is skipped rather than submitted with a dangling
+//! premise. Reference cycles, which the spec permits, are broken in file
+//! order.
+//!
+//! Several workers submit concurrently. A 429 pauses every worker for the
+//! `Retry-After` interval, and `--rate` optionally caps the request rate so
+//! the node's limit is never hit in the first place.
+//!
+//! Every accepted unit is appended to a journal (`<batch>.journal` unless
+//! given) as `node<TAB>id<TAB>status`. A re-run against the same node skips
+//! journaled units, so an interrupted batch resumes where it stopped. A unit
+//! the node already holds with identical content (the spec's idempotent 200)
+//! counts as accepted, as does one that differs only by a proof this run
+//! attached, which happens when the journal missed a unit accepted just
+//! before a crash.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ed25519_dalek::SigningKey;
+use semanticweft::{sign_unit, validate_unit, Delegation, SemanticUnit};
+use semanticweft_client::{Client, Error, RetryPolicy, Submitted};
+
+use crate::{block_on, fatal};
+
+/// Attempts per unit for 429 responses before giving up.
+const MAX_RATE_LIMITED_ATTEMPTS: u32 = 10;
+
+/// Attempts per unit for transport errors and 5xx responses.
+const MAX_TRANSIENT_ATTEMPTS: u32 = 3;
+
+/// Pause applied when a 429 carries no usable `Retry-After`.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Options for [`run`], mirroring the `submit --batch` flags.
+pub struct BatchOptions {
+    pub node: String,
+    pub file: PathBuf,
+    pub key: Option<SigningKey>,
+    pub did: Option<String>,
+    /// Act for the issuer with `key` as the delegate. Units must already
+    /// carry their proofs; only requests are signed under the token.
+    pub delegation: Option<Delegation>,
+    pub concurrency: usize,
+    pub rate_per_minute: Option<u32>,
+    pub journal: Option<PathBuf>,
+}
+
+/// What happened to one unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    /// 201: stored for the first time.
+    Created,
+    /// 200, or 409 resolved as our own earlier submission.
+    Exists,
+    /// Recorded in the journal by an earlier run.
+    Journaled,
+    /// Rejected; the reason is shown to the user.
+    Failed(String),
+    /// Not submitted because this referenced unit was not accepted.
+    Skipped(String),
+}
+
+impl Outcome {
+    fn accepted(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Created | Outcome::Exists | Outcome::Journaled
+        )
+    }
+}
+
+/// Submit the batch and return `true` if every unit was accepted.
+pub fn run(opts: BatchOptions) -> bool {
+    let node = opts.node.trim_end_matches('/').to_string();
+    let mut units = read_batch(&opts.file);
+    for unit in &units {
+        if let Err(e) = validate_unit(unit) {
+            fatal(&format!("unit {} is invalid: {e}", unit.id));
+        }
+    }
+
+    let mut signed_here = vec![false; units.len()];
+    if let (Some(key), Some(did)) = (&opts.key, &opts.did) {
+        for (unit, signed) in units.iter_mut().zip(signed_here.iter_mut()) {
+            if unit.proof.is_some() {
+                continue;
+            }
+            if unit.author != *did {
+                eprintln!(
+                    "warning: unit {} is authored by {}, not the signing key {did}",
+                    unit.id, unit.author
+                );
+            }
+            if let Err(e) = sign_unit(unit, key, did) {
+                fatal(&format!("cannot sign unit {}: {e}", unit.id));
+            }
+            *signed = true;
+        }
+    }
+
+    let journal_path = opts.journal.clone().unwrap_or_else(|| {
+        let mut name = opts.file.clone().into_os_string();
+        name.push(".journal");
+        PathBuf::from(name)
+    });
+    let done = load_journal(&journal_path, &node);
+    let journal = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&journal_path)
+        .unwrap_or_else(|e| {
+            fatal(&format!(
+                "cannot open journal {}: {e}",
+                journal_path.display()
+            ))
+        });
+
+    let plan = Plan::new(&units);
+    let state = State::new(&plan, &units, &done);
+
+    let interval = opts
+        .rate_per_minute
+        .filter(|&r| r > 0)
+        .map(|r| Duration::from_secs(60) / r);
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .unwrap_or_else(|e| fatal(&format!("cannot build HTTP client: {e}")));
+    // A 429 pauses every worker, which only this module can do, so the
+    // client reports it at once instead of waiting it out itself.
+    let mut client = crate::node_client(&node, opts.key.as_ref())
+        .with_http_client(http)
+        .with_retry(RetryPolicy {
+            max_retries: 0,
+            ..Default::default()
+        });
+    if let Some(delegation) = opts.delegation.clone() {
+        client = client.with_delegation(delegation);
+    }
+
+    let shared = Shared {
+        node,
+        units: &units,
+        signed_here: &signed_here,
+        plan: &plan,
+        client,
+        interval,
+        state: Mutex::new(state),
+        wake: Condvar::new(),
+        journal: Mutex::new(journal),
+    };
+
+    thread::scope(|scope| {
+        for _ in 0..opts.concurrency.max(1) {
+            scope.spawn(|| shared.worker());
+        }
+    });
+
+    let state = shared.state.into_inner().expect("no worker panicked");
+    let count = |f: fn(&Outcome) -> bool| state.outcomes.iter().flatten().filter(|o| f(o)).count();
+    let created = count(|o| *o == Outcome::Created);
+    let exists = count(|o| *o == Outcome::Exists);
+    let journaled = count(|o| *o == Outcome::Journaled);
+    let failed = count(|o| matches!(o, Outcome::Failed(_)));
+    let skipped = count(|o| matches!(o, Outcome::Skipped(_)));
+    println!(
+        "{} units: {created} created, {exists} already present, {journaled} from journal, \
+         {failed} failed, {skipped} skipped",
+        units.len()
+    );
+    failed == 0 && skipped == 0
+}
+
+/// Read NDJSON (or a JSON array) into units.
+fn read_batch(path: &Path) -> Vec<SemanticUnit> {
+    let text = crate::read_input(&path.to_path_buf());
+    let units: Vec<SemanticUnit> = if text.trim_start().starts_with('[') {
+        crate::parse_units(&text)
+    } else {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(n, line)| {
+                serde_json::from_str(line)
+                    .unwrap_or_else(|e| fatal(&format!("{}:{}: {e}", path.display(), n + 1)))
+            })
+            .collect()
+    };
+    if units.is_empty() {
+        fatal(&format!("{} contains no units", path.display()));
+    }
+    let mut ids = HashSet::new();
+    for unit in &units {
+        if !ids.insert(unit.id.as_str()) {
+            fatal(&format!(
+                "unit {} appears more than once in the batch",
+                unit.id
+            ));
+        }
+    }
+    units
+}
+
+/// Ids already accepted by `node` according to the journal.
+fn load_journal(path: &Path, node: &str) -> HashSet<String> {
+    let Ok(text) = fs::read_to_string(path) else {
+        return HashSet::new();
+    };
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            match (fields.next(), fields.next()) {
+                (Some(n), Some(id)) if n == node => Some(id.to_string()),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// The dependency structure of the batch.
+struct Plan {
+    /// Position of each unit in submission order.
+    rank: Vec<usize>,
+    /// For each unit, the batch units it waits for.
+    deps: Vec<Vec<usize>>,
+    /// For each unit, the batch units waiting for it.
+    dependents: Vec<Vec<usize>>,
+}
+
+impl Plan {
+    /// Order the units topologically, preferring file order among units
+    /// that are ready together. A cycle is broken at its earliest unit once
+    /// everything the cycle references from outside has been placed.
+    fn new(units: &[SemanticUnit]) -> Self {
+        let index: HashMap<&str, usize> = units
+            .iter()
+            .enumerate()
+            .map(|(i, u)| (u.id.as_str(), i))
+            .collect();
+        let refs: Vec<Vec<usize>> = units
+            .iter()
+            .enumerate()
+            .map(|(i, u)| {
+                let mut targets: Vec<usize> = u
+                    .references
+                    .iter()
+                    .flatten()
+                    .filter_map(|r| index.get(r.id.as_str()).copied())
+                    .filter(|&t| t != i)
+                    .collect();
+                targets.sort_unstable();
+                targets.dedup();
+                targets
+            })
+            .collect();
+
+        let mut referrers = vec![Vec::new(); units.len()];
+        let mut missing: Vec<usize> = refs.iter().map(Vec::len).collect();
+        for (i, targets) in refs.iter().enumerate() {
+            for &t in targets {
+                referrers[t].push(i);
+            }
+        }
+
+        let component = components(&refs);
+        let mut members = vec![Vec::new(); units.len()];
+        for (i, &c) in component.iter().enumerate() {
+            members[c].push(i);
+        }
+
+        let mut rank = vec![usize::MAX; units.len()];
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..units.len())
+            .filter(|&i| missing[i] == 0)
+            .map(Reverse)
+            .collect();
+        let mut next = 0;
+        while next < units.len() {
+            let i = match ready.pop() {
+                Some(Reverse(i)) => i,
+                None => {
+                    // Every unplaced unit waits on a cycle: release the
+                    // earliest member of one that waits on nothing else.
+                    let unblocked = |c: usize| {
+                        members[c].iter().all(|&j| {
+                            refs[j]
+                                .iter()
+                                .all(|&t| rank[t] != usize::MAX || component[t] == c)
+                        })
+                    };
+                    let i = (0..units.len())
+                        .find(|&i| rank[i] == usize::MAX && unblocked(component[i]))
+                        .expect("a cycle with its outside references placed");
+                    eprintln!("warning: unit {} is part of a reference cycle", units[i].id);
+                    i
+                }
+            };
+            if rank[i] != usize::MAX {
+                continue;
+            }
+            rank[i] = next;
+            next += 1;
+            for &r in &referrers[i] {
+                missing[r] -= 1;
+                if missing[r] == 0 && rank[r] == usize::MAX {
+                    ready.push(Reverse(r));
+                }
+            }
+        }
+
+        // Keep only edges that point earlier in the order, so cycle members
+        // never wait on each other.
+        let deps: Vec<Vec<usize>> = refs
+            .iter()
+            .enumerate()
+            .map(|(i, targets)| {
+                targets
+                    .iter()
+                    .copied()
+                    .filter(|&t| rank[t] < rank[i])
+                    .collect()
+            })
+            .collect();
+        let mut dependents = vec![Vec::new(); units.len()];
+        for (i, targets) in deps.iter().enumerate() {
+            for &t in targets {
+                dependents[t].push(i);
+            }
+        }
+        Self {
+            rank,
+            deps,
+            dependents,
+        }
+    }
+}
+
+/// Label each unit with its strongly connected component in the graph of
+/// `edges` (Tarjan's algorithm, iterative so long reference chains cannot
+/// overflow the stack).
+fn components(edges: &[Vec<usize>]) -> Vec<usize> {
+    const UNVISITED: usize = usize::MAX;
+    let mut index = vec![UNVISITED; edges.len()];
+    let mut low = vec![0; edges.len()];
+    let mut on_stack = vec![false; edges.len()];
+    let mut stack = Vec::new();
+    let mut component = vec![UNVISITED; edges.len()];
+    let (mut next_index, mut next_component) = (0, 0);
+    for root in 0..edges.len() {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        // Each frame is a node and the position of its next edge.
+        let mut frames = vec![(root, 0)];
+        index[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        while let Some(frame) = frames.last_mut() {
+            let v = frame.0;
+            if let Some(&w) = edges[v].get(frame.1) {
+                frame.1 += 1;
+                if index[w] == UNVISITED {
+                    index[w] = next_index;
+                    low[w] = next_index;
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    frames.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(index[w]);
+                }
+                continue;
+            }
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+            if low[v] == index[v] {
+                loop {
+                    let w = stack.pop().expect("v is on the stack");
+                    on_stack[w] = false;
+                    component[w] = next_component;
+                    if w == v {
+                        break;
+                    }
+                }
+                next_component += 1;
+            }
+        }
+    }
+    component
+}
+
+/// Scheduler state guarded by [`Shared::state`].
+struct State {
+    /// Units whose dependencies are all accepted, smallest rank first.
+    ready: BinaryHeap<Reverse<(usize, usize)>>,
+    /// Dependencies each unit is still waiting for.
+    waiting: Vec<usize>,
+    outcomes: Vec<Option<Outcome>>,
+    in_flight: usize,
+    /// No request may start before this instant (set by 429 responses).
+    paused_until: Instant,
+    /// Earliest start of the next request under `--rate`.
+    next_slot: Instant,
+}
+
+impl State {
+    fn new(plan: &Plan, units: &[SemanticUnit], done: &HashSet<String>) -> Self {
+        let outcomes: Vec<Option<Outcome>> = units
+            .iter()
+            .map(|u| done.contains(&u.id).then_some(Outcome::Journaled))
+            .collect();
+        let waiting: Vec<usize> = plan
+            .deps
+            .iter()
+            .map(|deps| deps.iter().filter(|&&d| outcomes[d].is_none()).count())
+            .collect();
+        let ready = (0..units.len())
+            .filter(|&i| outcomes[i].is_none() && waiting[i] == 0)
+            .map(|i| Reverse((plan.rank[i], i)))
+            .collect();
+        Self {
+            ready,
+            waiting,
+            outcomes,
+            in_flight: 0,
+            paused_until: Instant::now(),
+            next_slot: Instant::now(),
+        }
+    }
+
+    /// Record unit `i`'s outcome. An accepted unit releases the dependents
+    /// it was the last wait for; a rejected one skips everything that
+    /// depends on it, transitively. Returns the skipped units.
+    fn record(
+        &mut self,
+        plan: &Plan,
+        units: &[SemanticUnit],
+        i: usize,
+        outcome: Outcome,
+    ) -> Vec<usize> {
+        let accepted = outcome.accepted();
+        self.outcomes[i] = Some(outcome);
+        let mut skipped = Vec::new();
+        if accepted {
+            for &d in &plan.dependents[i] {
+                self.waiting[d] -= 1;
+                if self.waiting[d] == 0 && self.outcomes[d].is_none() {
+                    self.ready.push(Reverse((plan.rank[d], d)));
+                }
+            }
+        } else {
+            let mut stack = vec![i];
+            while let Some(j) = stack.pop() {
+                for &d in &plan.dependents[j] {
+                    if self.outcomes[d].is_none() {
+                        self.outcomes[d] = Some(Outcome::Skipped(units[j].id.clone()));
+                        skipped.push(d);
+                        stack.push(d);
+                    }
+                }
+            }
+        }
+        skipped
+    }
+}
+
+/// Everything the workers share.
+struct Shared<'a> {
+    node: String,
+    units: &'a [SemanticUnit],
+    signed_here: &'a [bool],
+    plan: &'a Plan,
+    client: Client,
+    interval: Option<Duration>,
+    state: Mutex<State>,
+    wake: Condvar,
+    journal: Mutex<File>,
+}
+
+impl Shared<'_> {
+    fn worker(&self) {
+        while let Some(i) = self.take() {
+            let outcome = self.submit(i);
+            self.finish(i, outcome);
+        }
+    }
+
+    /// Claim the next ready unit, waiting while others are in flight.
+    /// Returns `None` once nothing is ready and nothing can become ready.
+    fn take(&self) -> Option<usize> {
+        let mut state = self.state.lock().expect("lock");
+        loop {
+            if let Some(Reverse((_, i))) = state.ready.pop() {
+                state.in_flight += 1;
+                return Some(i);
+            }
+            if state.in_flight == 0 {
+                return None;
+            }
+            state = self.wake.wait(state).expect("lock");
+        }
+    }
+
+    /// Record an outcome, release or skip dependents, and wake idle workers.
+    fn finish(&self, i: usize, outcome: Outcome) {
+        let id = &self.units[i].id;
+        match &outcome {
+            Outcome::Created => println!("created   {id}"),
+            Outcome::Exists => println!("exists    {id}"),
+            Outcome::Failed(reason) => println!("FAILED    {id}  {reason}"),
+            Outcome::Journaled | Outcome::Skipped(_) => {}
+        }
+        if outcome.accepted() {
+            let mut journal = self.journal.lock().expect("lock");
+            let status = if outcome == Outcome::Created {
+                201
+            } else {
+                200
+            };
+            if let Err(e) =
+                writeln!(journal, "{}\t{id}\t{status}", self.node).and_then(|()| journal.flush())
+            {
+                eprintln!("sweft: cannot write journal: {e}");
+            }
+        }
+
+        let mut state = self.state.lock().expect("lock");
+        state.in_flight -= 1;
+        for d in state.record(self.plan, self.units, i, outcome) {
+            if let Some(Outcome::Skipped(referenced)) = &state.outcomes[d] {
+                println!("skipped   {}  (references {referenced})", self.units[d].id);
+            }
+        }
+        self.wake.notify_all();
+    }
+
+    /// Block until the rate limit and any 429 pause allow another request.
+    fn pace(&self) {
+        let wait = {
+            let mut state = self.state.lock().expect("lock");
+            let now = Instant::now();
+            let start = now.max(state.paused_until).max(state.next_slot);
+            if let Some(interval) = self.interval {
+                state.next_slot = start + interval;
+            }
+            start - now
+        };
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+    }
+
+    /// Submit one unit and classify the node's answer.
+    fn submit(&self, i: usize) -> Outcome {
+        let unit = &self.units[i];
+        let result = self.call(|| self.client.submit(unit));
+        classify(result, self.signed_here[i], || self.matches_stored(unit))
+    }
+
+    /// Run a client call within the rate limit. 429s pause every worker and
+    /// are retried, as are transport errors and 5xx responses; any other
+    /// result is returned as is.
+    fn call<T, F>(&self, request: impl Fn() -> F) -> Result<T, Error>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        let mut rate_limited = 0;
+        let mut transient = 0;
+        loop {
+            self.pace();
+            match block_on(request()) {
+                Err(Error::RateLimited { retry_after }) => {
+                    rate_limited += 1;
+                    if rate_limited >= MAX_RATE_LIMITED_ATTEMPTS {
+                        return Err(Error::RateLimited { retry_after });
+                    }
+                    let delay = retry_after.unwrap_or(DEFAULT_RETRY_AFTER);
+                    let mut state = self.state.lock().expect("lock");
+                    state.paused_until = state.paused_until.max(Instant::now() + delay);
+                }
+                Err(e) if is_transient(&e) => {
+                    transient += 1;
+                    if transient >= MAX_TRANSIENT_ATTEMPTS {
+                        return Err(e);
+                    }
+                    thread::sleep(Duration::from_secs(1 << transient));
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// `true` if the node holds `unit` as an earlier run submitted it.
+    fn matches_stored(&self, unit: &SemanticUnit) -> bool {
+        self.call(|| self.client.get(&unit.id))
+            .is_ok_and(|stored| same_submission(&stored, unit))
+    }
+}
+
+/// Classify the node's answer to a submission. `matches_stored` is only
+/// asked about a 409 on a unit this run signed.
+fn classify(
+    result: Result<Submitted, Error>,
+    signed_here: bool,
+    matches_stored: impl FnOnce() -> bool,
+) -> Outcome {
+    match result {
+        Ok(Submitted { created: true, .. }) => Outcome::Created,
+        Ok(Submitted { created: false, .. }) => Outcome::Exists,
+        Err(Error::Api { status: 409, .. }) if signed_here && matches_stored() => Outcome::Exists,
+        Err(Error::Api {
+            status, message, ..
+        }) => Outcome::Failed(format!("{status}: {message}")),
+        Err(Error::RateLimited { .. }) => {
+            Outcome::Failed("still rate limited after retries".into())
+        }
+        Err(e) => Outcome::Failed(e.to_string()),
+    }
+}
+
+/// Transport errors and 5xx responses, which are worth another attempt.
+fn is_transient(e: &Error) -> bool {
+    matches!(
+        e,
+        Error::Transport(_)
+            | Error::Api {
+                status: 500..=599,
+                ..
+            }
+    )
+}
+
+/// `true` if `stored` has the same content and signer as `unit` but may
+/// differ in its proof — i.e. an earlier run's submission, re-signed now.
+fn same_submission(stored: &SemanticUnit, unit: &SemanticUnit) -> bool {
+    let strip = |u: &SemanticUnit| SemanticUnit {
+        proof: None,
+        ..u.clone()
+    };
+    strip(stored) == strip(unit)
+        && stored.proof.as_ref().map(|p| p.method.as_str())
+            == unit.proof.as_ref().map(|p| p.method.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semanticweft::{Reference, RelType, UnitType};
+
+    fn unit(content: &str) -> SemanticUnit {
+        SemanticUnit::new(UnitType::Assertion, content, "did:key:z6MkAuthor")
+    }
+
+    fn link(from: &mut SemanticUnit, to: &SemanticUnit) {
+        from.references
+            .get_or_insert_with(Vec::new)
+            .push(Reference {
+                id: to.id.clone(),
+                rel: RelType::DerivesFrom,
+            });
+    }
+
+    /// `[a, b → a, c → b, d]`
+    fn chain() -> Vec<SemanticUnit> {
+        let a = unit("a");
+        let mut b = unit("b");
+        link(&mut b, &a);
+        let mut c = unit("c");
+        link(&mut c, &b);
+        vec![a, b, c, unit("d")]
+    }
+
+    fn submission_order(plan: &Plan) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..plan.rank.len()).collect();
+        order.sort_by_key(|&i| plan.rank[i]);
+        order
+    }
+
+    fn ready(state: &mut State) -> Vec<usize> {
+        std::iter::from_fn(|| state.ready.pop().map(|Reverse((_, i))| i)).collect()
+    }
+
+    #[test]
+    fn plan_submits_references_first_and_otherwise_keeps_file_order() {
+        let mut units = chain();
+        units.reverse(); // [d, c → b, b → a, a]
+        let plan = Plan::new(&units);
+        assert_eq!(submission_order(&plan), [0, 3, 2, 1]);
+        assert_eq!(plan.deps, [vec![], vec![2], vec![3], vec![]]);
+    }
+
+    #[test]
+    fn plan_breaks_cycles_at_the_earliest_unit() {
+        let mut a = unit("a");
+        let mut b = unit("b");
+        link(&mut a, &b);
+        link(&mut b, &a);
+        let mut c = unit("c");
+        link(&mut c, &a);
+        let plan = Plan::new(&[a, b, c]);
+        assert_eq!(submission_order(&plan), [0, 1, 2]);
+        assert_eq!(plan.deps, [vec![], vec![0], vec![0]]);
+    }
+
+    #[test]
+    fn plan_places_a_cycle_before_units_that_depend_on_it() {
+        let mut c = unit("c");
+        let mut a = unit("a");
+        let mut b = unit("b");
+        link(&mut c, &a);
+        link(&mut a, &b);
+        link(&mut b, &a);
+        let plan = Plan::new(&[c, a, b]);
+        assert_eq!(submission_order(&plan), [1, 0, 2]);
+        assert_eq!(plan.deps, [vec![1], vec![], vec![1]]);
+    }
+
+    #[test]
+    fn accepted_units_release_their_dependents() {
+        let units = chain();
+        let plan = Plan::new(&units);
+        let mut state = State::new(&plan, &units, &HashSet::new());
+        assert_eq!(ready(&mut state), [0, 3]);
+        assert!(state.record(&plan, &units, 0, Outcome::Created).is_empty());
+        assert_eq!(ready(&mut state), [1]);
+        assert!(state.record(&plan, &units, 1, Outcome::Exists).is_empty());
+        assert_eq!(ready(&mut state), [2]);
+    }
+
+    #[test]
+    fn failed_units_skip_their_dependents_transitively() {
+        let units = chain();
+        let plan = Plan::new(&units);
+        let mut state = State::new(&plan, &units, &HashSet::new());
+        ready(&mut state);
+        let skipped = state.record(&plan, &units, 0, Outcome::Failed("422".into()));
+        assert_eq!(skipped, [1, 2]);
+        assert_eq!(
+            state.outcomes[1],
+            Some(Outcome::Skipped(units[0].id.clone()))
+        );
+        assert_eq!(
+            state.outcomes[2],
+            Some(Outcome::Skipped(units[1].id.clone()))
+        );
+        assert_eq!(state.outcomes[3], None);
+        assert!(ready(&mut state).is_empty());
+    }
+
+    #[test]
+    fn classify_maps_node_answers_to_outcomes() {
+        let submitted = |created| {
+            Ok(Submitted {
+                unit: unit("a"),
+                created,
+            })
+        };
+        let api = |status| {
+            Err(Error::Api {
+                status,
+                code: semanticweft_client::ErrorCode::IdConflict,
+                message: "different content".into(),
+            })
+        };
+        let unasked = || panic!("only a 409 on a unit signed here is compared");
+
+        assert_eq!(classify(submitted(true), true, unasked), Outcome::Created);
+        assert_eq!(classify(submitted(false), true, unasked), Outcome::Exists);
+        assert_eq!(classify(api(409), true, || true), Outcome::Exists);
+        let failed = Outcome::Failed("409: different content".into());
+        assert_eq!(classify(api(409), true, || false), failed);
+        assert_eq!(classify(api(409), false, unasked), failed);
+        assert_eq!(
+            classify(
+                Err(Error::RateLimited { retry_after: None }),
+                false,
+                unasked
+            ),
+            Outcome::Failed("still rate limited after retries".into())
+        );
+    }
+
+    #[test]
+    fn same_submission_ignores_the_proof_but_not_the_signer() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let (did, _) = crate::derive_did_and_pubkey(&key);
+        let mut stored = unit("a");
+        sign_unit(&mut stored, &key, &did).unwrap();
+
+        let mut resigned = stored.clone();
+        resigned.proof.as_mut().unwrap().created = "2020-01-01T00:00:00Z".into();
+        assert!(same_submission(&stored, &resigned));
+
+        let mut edited = stored.clone();
+        edited.content = "b".into();
+        assert!(!same_submission(&stored, &edited));
+
+        let other = SigningKey::from_bytes(&[8; 32]);
+        let (other_did, _) = crate::derive_did_and_pubkey(&other);
+        let mut foreign = SemanticUnit {
+            proof: None,
+            ..stored.clone()
+        };
+        sign_unit(&mut foreign, &other, &other_did).unwrap();
+        assert!(!same_submission(&stored, &foreign));
+    }
+
+    #[test]
+    fn journal_resumes_per_node() {
+        let units = chain();
+        let path = std::env::temp_dir().join(format!("sweft-batch-{}.journal", std::process::id()));
+        fs::write(
+            &path,
+            format!(
+                "https://a.example\t{}\t201\nhttps://b.example\t{}\t201\n",
+                units[0].id, units[1].id
+            ),
+        )
+        .unwrap();
+        let done = load_journal(&path, "https://a.example");
+        fs::remove_file(&path).unwrap();
+        assert_eq!(done, HashSet::from([units[0].id.clone()]));
+
+        let plan = Plan::new(&units);
+        let mut state = State::new(&plan, &units, &done);
+        assert_eq!(state.outcomes[0], Some(Outcome::Journaled));
+        assert_eq!(ready(&mut state), [1, 3]);
+    }
+}
@@ -0,0 +1,621 @@
+//! `sweft browse` — full-screen terminal browser for a unit graph.
+//!
+//! The graph comes from a JSON file, a mirror database, or a live node. The
+//! screen is split into the unit list on the left and, on the right, the
+//! selected unit with its outgoing references (what it builds on) and
+//! incoming references (what builds on it, including challenges). Following
+//! a reference pushes the current unit onto a history stack so `Backspace`
+//! retraces the path.
+//!
+//! When browsing a node, following a reference to a unit that has not been
+//! loaded yet fetches its neighbourhood with `GET /v1/units/{id}/subgraph`
+//! and merges it into the graph.
+//!
+//! Traversal uses the core [`Graph`]; list and detail text reuse the
+//! [`semanticweft::render`] helpers.
+
+use std::io;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use semanticweft::render::{render_source, short_id, truncate};
+use semanticweft::{proof_author, proof_signer, verify_proof, Graph, RelType, SemanticUnit, UnitType};
+use semanticweft_client::Client;
+
+use crate::block_on;
+
+/// The live node a graph was loaded from; missing references are fetched
+/// from it when followed.
+pub struct NodeBacking {
+    pub client: Client,
+}
+
+/// Options for [`run`], mirroring the `browse` subcommand's flags.
+pub struct BrowseOptions {
+    pub units: Vec<SemanticUnit>,
+    /// `None` for a file or mirror, whose graph is fixed.
+    pub backing: Option<NodeBacking>,
+    /// Unit to select first.
+    pub start: Option<String>,
+    pub unit_type: Option<UnitType>,
+    pub author: Option<String>,
+}
+
+/// Run the browser until the user quits.
+pub fn run(opts: BrowseOptions) -> io::Result<()> {
+    let mut app = App::new(opts);
+    let mut terminal = ratatui::init();
+    let result = app.event_loop(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+/// The pane that receives movement keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Units,
+    Outgoing,
+    Incoming,
+}
+
+/// One edge shown in the reference panes.
+struct Link {
+    rel: RelType,
+    id: String,
+}
+
+/// Unit types in the order `t` cycles through them.
+const TYPE_CYCLE: [UnitType; 5] = [
+    UnitType::Assertion,
+    UnitType::Question,
+    UnitType::Inference,
+    UnitType::Challenge,
+    UnitType::Constraint,
+];
+
+struct App {
+    graph: Graph,
+    backing: Option<NodeBacking>,
+    type_filter: Option<UnitType>,
+    author_filter: Option<String>,
+    /// Ids passing the filters, in id order.
+    visible: Vec<String>,
+    units_state: ListState,
+    /// The unit shown on the right; may be hidden by the list filters.
+    current: Option<String>,
+    history: Vec<String>,
+    focus: Focus,
+    outgoing_state: ListState,
+    incoming_state: ListState,
+    show_assumptions: bool,
+    show_source: bool,
+    /// Author filter being typed, while `/` is active.
+    editing_author: Option<String>,
+    status: String,
+    quit: bool,
+}
+
+impl App {
+    fn new(opts: BrowseOptions) -> Self {
+        let mut app = Self {
+            graph: Graph::from_units(opts.units),
+            backing: opts.backing,
+            type_filter: opts.unit_type,
+            author_filter: opts.author,
+            visible: Vec::new(),
+            units_state: ListState::default(),
+            current: None,
+            history: Vec::new(),
+            focus: Focus::Units,
+            outgoing_state: ListState::default(),
+            incoming_state: ListState::default(),
+            show_assumptions: false,
+            show_source: false,
+            editing_author: None,
+            status: String::new(),
+            quit: false,
+        };
+        app.refilter();
+        match opts.start {
+            Some(id) if app.graph.get(&id).is_some() => app.show(id),
+            Some(id) => app.status = format!("unit {id} is not in the graph"),
+            None => {}
+        }
+        app
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.on_key(key.code);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // --- state ---------------------------------------------------------------
+
+    fn current_unit(&self) -> Option<&SemanticUnit> {
+        self.current.as_deref().and_then(|id| self.graph.get(id))
+    }
+
+    /// Recompute the unit list after a filter or graph change, keeping the
+    /// current unit selected when it is still listed.
+    fn refilter(&mut self) {
+        let mut visible: Vec<&SemanticUnit> = self
+            .graph
+            .units()
+            .filter(|u| self.type_filter.as_ref().is_none_or(|t| u.unit_type == *t))
+            .filter(|u| {
+                self.author_filter
+                    .as_ref()
+                    .is_none_or(|a| u.author.contains(a.as_str()))
+            })
+            .collect();
+        visible.sort_by_key(|u| u.id.as_str());
+        self.visible = visible.into_iter().map(|u| u.id.clone()).collect();
+
+        let position = self
+            .current
+            .as_ref()
+            .and_then(|c| self.visible.iter().position(|id| id == c));
+        match position {
+            Some(i) => self.units_state.select(Some(i)),
+            None if self.visible.is_empty() => self.units_state.select(None),
+            None => {
+                self.units_state.select(Some(0));
+                if self.current.is_none() {
+                    self.set_current(self.visible[0].clone());
+                }
+            }
+        }
+    }
+
+    fn set_current(&mut self, id: String) {
+        self.current = Some(id);
+        self.outgoing_state.select(None);
+        self.incoming_state.select(None);
+    }
+
+    /// Show `id` and select it in the list if the filters allow.
+    fn show(&mut self, id: String) {
+        match self.visible.iter().position(|v| *v == id) {
+            Some(i) => self.units_state.select(Some(i)),
+            None => self.status = "selected unit is hidden by the list filters".into(),
+        }
+        self.set_current(id);
+    }
+
+    /// Follow a reference to `id`, fetching it first when browsing a node.
+    fn follow(&mut self, id: String) {
+        if self.graph.get(&id).is_none() {
+            match self.fetch_around(&id) {
+                Ok(units) => {
+                    for unit in units {
+                        self.graph.add(unit);
+                    }
+                    self.refilter();
+                }
+                Err(e) => {
+                    self.status = e;
+                    return;
+                }
+            }
+            if self.graph.get(&id).is_none() {
+                self.status = format!("unit {id} is not available");
+                return;
+            }
+        }
+        if let Some(current) = self.current.take() {
+            self.history.push(current);
+        }
+        self.status.clear();
+        self.show(id);
+    }
+
+    fn back(&mut self) {
+        match self.history.pop() {
+            Some(id) => {
+                self.status.clear();
+                self.show(id);
+            }
+            None => self.status = "no earlier unit".into(),
+        }
+    }
+
+    fn fetch_around(&self, id: &str) -> Result<Vec<SemanticUnit>, String> {
+        let Some(NodeBacking { client }) = &self.backing else {
+            return Err(format!("unit {id} is not in this file"));
+        };
+        block_on(client.subgraph(id, Some(1))).map_err(|e| e.to_string())
+    }
+
+    /// References from the current unit, in the order the unit lists them.
+    fn outgoing(&self) -> Vec<Link> {
+        self.current_unit()
+            .and_then(|u| u.references.as_ref())
+            .map(|refs| {
+                refs.iter()
+                    .map(|r| Link {
+                        rel: r.rel.clone(),
+                        id: r.id.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Units that reference the current unit, in id order.
+    fn incoming(&self) -> Vec<Link> {
+        let Some(current) = self.current.as_deref() else {
+            return Vec::new();
+        };
+        let mut units = self.graph.incoming(current);
+        units.sort_by_key(|u| u.id.as_str());
+        units
+            .into_iter()
+            .filter_map(|u| {
+                let r = u.references.iter().flatten().find(|r| r.id == current)?;
+                Some(Link {
+                    rel: r.rel.clone(),
+                    id: u.id.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Positions in [`incoming`](Self::incoming) of the units that
+    /// challenge the current unit: rebuttals and `challenge` units.
+    fn challenges(&self) -> Vec<usize> {
+        self.incoming()
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| {
+                l.rel == RelType::Rebuts
+                    || self
+                        .graph
+                        .get(&l.id)
+                        .is_some_and(|u| u.unit_type == UnitType::Challenge)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    // --- input ---------------------------------------------------------------
+
+    fn on_key(&mut self, code: KeyCode) {
+        if let Some(ref mut buffer) = self.editing_author {
+            match code {
+                KeyCode::Enter => {
+                    let author = buffer.trim().to_string();
+                    self.author_filter = (!author.is_empty()).then_some(author);
+                    self.editing_author = None;
+                    self.refilter();
+                }
+                KeyCode::Esc => self.editing_author = None,
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                KeyCode::Char(c) => buffer.push(c),
+                _ => {}
+            }
+            return;
+        }
+
+        self.status.clear();
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::Units => Focus::Outgoing,
+                    Focus::Outgoing => Focus::Incoming,
+                    Focus::Incoming => Focus::Units,
+                };
+                self.move_selection(0);
+            }
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => self.activate(),
+            KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h') => self.back(),
+            KeyCode::Char('c') => {
+                // Select the next challenge in the incoming pane, wrapping.
+                let challenges = self.challenges();
+                let selected = match self.focus {
+                    Focus::Incoming => self.incoming_state.selected(),
+                    _ => None,
+                };
+                let next = challenges
+                    .iter()
+                    .find(|&&i| selected.is_none_or(|s| i > s))
+                    .or(challenges.first());
+                match next {
+                    Some(&i) => {
+                        self.focus = Focus::Incoming;
+                        self.incoming_state.select(Some(i));
+                    }
+                    None => self.status = "no challenges to this unit".into(),
+                }
+            }
+            KeyCode::Char('a') => self.show_assumptions = !self.show_assumptions,
+            KeyCode::Char('s') => self.show_source = !self.show_source,
+            KeyCode::Char('t') => {
+                self.type_filter = match self.type_filter {
+                    None => Some(TYPE_CYCLE[0].clone()),
+                    Some(ref t) => TYPE_CYCLE
+                        .iter()
+                        .position(|c| c == t)
+                        .and_then(|i| TYPE_CYCLE.get(i + 1))
+                        .cloned(),
+                };
+                self.refilter();
+            }
+            KeyCode::Char('/') => {
+                self.editing_author = Some(self.author_filter.clone().unwrap_or_default());
+            }
+            _ => {}
+        }
+    }
+
+    /// Move the selection in the focused pane by `delta` (0 just clamps).
+    fn move_selection(&mut self, delta: isize) {
+        let len = match self.focus {
+            Focus::Units => self.visible.len(),
+            Focus::Outgoing => self.outgoing().len(),
+            Focus::Incoming => self.incoming().len(),
+        };
+        let state = match self.focus {
+            Focus::Units => &mut self.units_state,
+            Focus::Outgoing => &mut self.outgoing_state,
+            Focus::Incoming => &mut self.incoming_state,
+        };
+        if len == 0 {
+            state.select(None);
+            return;
+        }
+        let i = state.selected().unwrap_or(0) as isize + delta;
+        let i = i.clamp(0, len as isize - 1) as usize;
+        state.select(Some(i));
+        if self.focus == Focus::Units && delta != 0 {
+            let id = self.visible[i].clone();
+            self.set_current(id);
+        }
+    }
+
+    /// `Enter`: from the list, move into the references; from a reference
+    /// pane, follow the selected reference.
+    fn activate(&mut self) {
+        let (links, state) = match self.focus {
+            Focus::Units => {
+                self.focus = Focus::Outgoing;
+                self.move_selection(0);
+                return;
+            }
+            Focus::Outgoing => (self.outgoing(), &self.outgoing_state),
+            Focus::Incoming => (self.incoming(), &self.incoming_state),
+        };
+        if let Some(link) = state.selected().and_then(|i| links.get(i)) {
+            let id = link.id.clone();
+            self.follow(id);
+            self.move_selection(0);
+        }
+    }
+
+    // --- drawing -------------------------------------------------------------
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(main);
+
+        let outgoing = self.outgoing();
+        let incoming = self.incoming();
+        let pane_height = |n: usize| Constraint::Length((n.clamp(1, 6) + 2) as u16);
+        let [detail, out_area, in_area] = Layout::vertical([
+            Constraint::Min(6),
+            pane_height(outgoing.len()),
+            pane_height(incoming.len()),
+        ])
+        .areas(right);
+
+        self.draw_units(frame, left);
+        self.draw_detail(frame, detail);
+        let out_title = format!("Outgoing ({})", outgoing.len());
+        let in_title = format!("Incoming ({})", incoming.len());
+        let out_list = self.link_list(&outgoing, out_title, Focus::Outgoing);
+        frame.render_stateful_widget(out_list, out_area, &mut self.outgoing_state);
+        let in_list = self.link_list(&incoming, in_title, Focus::Incoming);
+        frame.render_stateful_widget(in_list, in_area, &mut self.incoming_state);
+
+        let footer_text = match (&self.editing_author, self.status.is_empty()) {
+            (Some(buffer), _) => format!("author contains: {buffer}▏  (Enter apply, Esc cancel)"),
+            (None, false) => self.status.clone(),
+            (None, true) => "↑↓ move  Tab pane  ⏎ follow  ⌫ back  c challenge  \
+                             a assumptions  s source  t type  / author  q quit"
+                .to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(footer_text).style(Style::default().add_modifier(Modifier::DIM)),
+            footer,
+        );
+    }
+
+    fn pane_block(&self, title: String, pane: Focus) -> Block<'static> {
+        let border = if self.focus == pane {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(border)
+            .title(title)
+    }
+
+    fn draw_units(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let mut title = format!("Units ({}/{})", self.visible.len(), self.graph.len());
+        if let Some(ref t) = self.type_filter {
+            title.push_str(&format!("  type:{t}"));
+        }
+        if let Some(ref a) = self.author_filter {
+            title.push_str(&format!("  author:{}", truncate(a, 24)));
+        }
+        let items: Vec<ListItem> = self
+            .visible
+            .iter()
+            .filter_map(|id| self.graph.get(id))
+            .map(|u| ListItem::new(unit_line(u, area.width as usize)))
+            .collect();
+        let list = List::new(items)
+            .block(self.pane_block(title, Focus::Units))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut self.units_state);
+    }
+
+    fn draw_detail(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let block = Block::default().borders(Borders::ALL).title("Unit");
+        let Some(unit) = self.current_unit() else {
+            frame.render_widget(Paragraph::new("No units.").block(block), area);
+            return;
+        };
+
+        let mut header = vec![
+            Span::styled(
+                format!("[{}]", unit.unit_type),
+                Style::default()
+                    .fg(type_color(&unit.unit_type))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(" {}", unit.author)),
+        ];
+        if let Some(c) = unit.confidence {
+            header.push(Span::raw(format!("  confidence: {c:.2}")));
+        }
+        let mut lines = vec![
+            Line::from(header),
+            Line::raw(""),
+            Line::raw(unit.content.clone()),
+            Line::raw(""),
+            proof_line(unit),
+        ];
+        if let Some(ref v) = unit.visibility {
+            lines.push(Line::raw(format!("Visibility: {v}")));
+        }
+
+        match unit.assumptions.as_deref() {
+            Some(assumptions) if self.show_assumptions => {
+                lines.push(Line::raw("▾ Assumptions"));
+                for a in assumptions {
+                    lines.push(Line::raw(format!("  • {a}")));
+                }
+            }
+            Some(assumptions) => {
+                lines.push(Line::raw(format!("▸ Assumptions ({})", assumptions.len())));
+            }
+            None => {}
+        }
+        match unit.source.as_ref() {
+            Some(source) if self.show_source => {
+                lines.push(Line::raw(format!("▾ Source: {}", render_source(source))));
+            }
+            Some(_) => lines.push(Line::raw("▸ Source")),
+            None => {}
+        }
+
+        let challenges = self.challenges().len();
+        if challenges > 0 {
+            lines.push(Line::styled(
+                format!("⚠ {challenges} challenge(s) — press c to select"),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!("id: {}  created: {}", unit.id, unit.created_at),
+            Style::default().add_modifier(Modifier::DIM),
+        ));
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+
+    fn link_list(&self, links: &[Link], title: String, pane: Focus) -> List<'static> {
+        let items: Vec<ListItem> = links
+            .iter()
+            .map(|link| {
+                let target = match self.graph.get(&link.id) {
+                    Some(u) => format!(
+                        "[{}] {}  {}",
+                        short_id(&u.id),
+                        u.unit_type,
+                        truncate(&u.content, 60)
+                    ),
+                    None => format!("[{}] (not loaded)", short_id(&link.id)),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<13}", link.rel.to_string()),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::raw(target),
+                ]))
+            })
+            .collect();
+        List::new(items)
+            .block(self.pane_block(title, pane))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+    }
+}
+
+/// A one-line list entry for `unit`, sized to a pane `width` columns wide.
+fn unit_line(unit: &SemanticUnit, width: usize) -> Line<'static> {
+    let prefix = format!("{} {:<10} ", short_id(&unit.id), unit.unit_type.to_string());
+    let room = width.saturating_sub(prefix.chars().count() + 4).max(8);
+    Line::from(vec![
+        Span::styled(prefix, Style::default().fg(type_color(&unit.unit_type))),
+        Span::raw(truncate(&unit.content, room)),
+    ])
+}
+
+/// Proof status of `unit`, coloured by outcome.
+fn proof_line(unit: &SemanticUnit) -> Line<'static> {
+    let (text, color) = match (unit.proof.as_ref(), verify_proof(unit)) {
+        (None, _) => ("Proof: unsigned".to_string(), Color::Yellow),
+        (Some(_), Ok(())) => match proof_signer(unit) {
+            Some(signer) if signer == unit.author => (
+                "Proof: valid, signed by the author".to_string(),
+                Color::Green,
+            ),
+            Some(signer) if proof_author(unit) == Some(unit.author.as_str()) => (
+                format!("Proof: valid, signed by {signer} for the author"),
+                Color::Green,
+            ),
+            Some(signer) => (format!("Proof: valid, signed by {signer}"), Color::Yellow),
+            None => ("Proof: valid".to_string(), Color::Green),
+        },
+        (Some(_), Err(e)) => (format!("Proof: INVALID ({e})"), Color::Red),
+    };
+    Line::styled(text, Style::default().fg(color))
+}
+
+fn type_color(unit_type: &UnitType) -> Color {
+    match unit_type {
+        UnitType::Assertion => Color::Blue,
+        UnitType::Question => Color::Yellow,
+        UnitType::Inference => Color::Green,
+        UnitType::Challenge => Color::Red,
+        UnitType::Constraint => Color::Gray,
+    }
+}
@@ -0,0 +1,555 @@
+//! Evidence bundles — self-contained, signed archives for offline audit.
+//!
+//! A bundle is an uncompressed tar archive with this layout:
+//!
+//! ```text
+//! manifest.json        what the bundle contains (see [`Manifest`])
+//! manifest.sig         detached signature over manifest.json by the exporter
+//! authors.json         every author DID, with its node profile when known
+//! units/<id>.json      one file per unit in the justification subgraph
+//! ```
+//!
+//! The manifest lists every other file with its SHA-256 digest, so signing
+//! the manifest bytes covers the whole bundle. Verification needs nothing but
+//! the archive: digests are recomputed, the manifest signature and each
+//! unit's proof are checked with the core library's `did:key` verification,
+//! and units are validated against the spec.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Cursor, Read};
+
+use ed25519_dalek::SigningKey;
+use semanticweft::{
+    did_key_to_verifying_key, proof_author, proof_signer, sign_detached, validate_unit,
+    verify_detached, verify_proof, ProofError, SemanticUnit,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Format identifier written to, and required in, every manifest.
+pub const FORMAT: &str = "semanticweft-bundle/1";
+
+const MANIFEST_PATH: &str = "manifest.json";
+const SIGNATURE_PATH: &str = "manifest.sig";
+const AUTHORS_PATH: &str = "authors.json";
+
+/// The bundle's table of contents.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Always [`FORMAT`].
+    pub format: String,
+    /// ID of the unit whose justification the bundle captures.
+    pub root: String,
+    /// Base URL of the node the units were exported from.
+    pub source_node: String,
+    /// When the bundle was created (RFC 3339).
+    pub created_at: String,
+    /// DID of the key that signed `manifest.sig`.
+    pub exporter: String,
+    /// Every distinct unit author, sorted.
+    pub authors: Vec<String>,
+    /// Every file in the bundle except the manifest and its signature,
+    /// sorted by path.
+    pub files: Vec<FileEntry>,
+}
+
+/// One file listed in the [`Manifest`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileEntry {
+    /// Path within the archive.
+    pub path: String,
+    /// Lowercase hex SHA-256 of the file's bytes.
+    pub sha256: String,
+    /// File size in bytes.
+    pub bytes: u64,
+}
+
+/// The detached signature stored in `manifest.sig`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ManifestSignature {
+    /// DID of the signing key; must equal the manifest's `exporter`.
+    pub signer: String,
+    /// Always `"ed25519"`.
+    pub algorithm: String,
+    /// Base58btc (`z`-prefix) signature over the exact manifest.json bytes.
+    pub signature: String,
+}
+
+/// An entry in `authors.json`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuthorEntry {
+    /// The author's DID.
+    pub did: String,
+    /// The author's profile on the source node, if registered there.
+    pub profile: Option<serde_json::Value>,
+}
+
+// ---------------------------------------------------------------------------
+// Create
+// ---------------------------------------------------------------------------
+
+/// Everything needed to write a bundle.
+pub struct BundleInput<'a> {
+    pub root: &'a str,
+    pub source_node: &'a str,
+    pub units: &'a [SemanticUnit],
+    pub authors: Vec<AuthorEntry>,
+    pub key: &'a SigningKey,
+    pub exporter: &'a str,
+}
+
+/// Build a signed bundle archive and return its bytes.
+pub fn create(input: BundleInput<'_>) -> Result<Vec<u8>, String> {
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    for unit in input.units {
+        let json = serde_json::to_vec_pretty(unit).map_err(|e| e.to_string())?;
+        files.insert(format!("units/{}.json", unit.id), json);
+    }
+    let authors_json = serde_json::to_vec_pretty(&input.authors).map_err(|e| e.to_string())?;
+    files.insert(AUTHORS_PATH.into(), authors_json);
+
+    let manifest = Manifest {
+        format: FORMAT.into(),
+        root: input.root.into(),
+        source_node: input.source_node.into(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        exporter: input.exporter.into(),
+        authors: input.authors.iter().map(|a| a.did.clone()).collect(),
+        files: files
+            .iter()
+            .map(|(path, bytes)| FileEntry {
+                path: path.clone(),
+                sha256: sha256_hex(bytes),
+                bytes: bytes.len() as u64,
+            })
+            .collect(),
+    };
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    let signature = ManifestSignature {
+        signer: input.exporter.into(),
+        algorithm: "ed25519".into(),
+        signature: sign_detached(input.key, &manifest_bytes),
+    };
+    let signature_bytes = serde_json::to_vec_pretty(&signature).map_err(|e| e.to_string())?;
+
+    let mut builder = tar::Builder::new(Vec::new());
+    append(&mut builder, MANIFEST_PATH, &manifest_bytes)?;
+    append(&mut builder, SIGNATURE_PATH, &signature_bytes)?;
+    for (path, bytes) in &files {
+        append(&mut builder, path, bytes)?;
+    }
+    builder.into_inner().map_err(|e| e.to_string())
+}
+
+fn append(builder: &mut tar::Builder<Vec<u8>>, path: &str, bytes: &[u8]) -> Result<(), String> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, path, bytes)
+        .map_err(|e| format!("failed to write {path} to archive: {e}"))
+}
+
+// ---------------------------------------------------------------------------
+// Verify
+// ---------------------------------------------------------------------------
+
+/// Verification status of one unit in a bundle.
+#[derive(Debug)]
+pub enum UnitStatus {
+    /// The proof verifies and was made by the unit's author.
+    Signed,
+    /// The proof verifies but was made by a key other than the author's.
+    SignedByOther(String),
+    /// The unit carries no proof.
+    Unsigned,
+    /// The unit is invalid or its proof does not verify.
+    Failed(String),
+}
+
+/// The outcome of [`verify`].
+#[derive(Debug, Default)]
+pub struct Report {
+    pub root: String,
+    pub exporter: String,
+    pub source_node: String,
+    pub created_at: String,
+    /// Bundle-level problems: digests, signature, structure.
+    pub errors: Vec<String>,
+    /// Non-fatal observations, e.g. references to units outside the bundle.
+    pub warnings: Vec<String>,
+    /// Per-unit results, ordered by unit id.
+    pub units: Vec<(SemanticUnit, UnitStatus)>,
+}
+
+impl Report {
+    /// `true` if the bundle is intact and every unit is valid with a
+    /// verifying proof by its author (or no proof, unless `require_proofs`).
+    /// Proofs by other keys fail unless `allow_foreign_signers`.
+    pub fn passed(&self, require_proofs: bool, allow_foreign_signers: bool) -> bool {
+        self.errors.is_empty()
+            && self.units.iter().all(|(_, status)| match status {
+                UnitStatus::Signed => true,
+                UnitStatus::SignedByOther(_) => allow_foreign_signers,
+                UnitStatus::Unsigned => !require_proofs,
+                UnitStatus::Failed(_) => false,
+            })
+    }
+
+    /// Print a human-readable summary to stdout.
+    pub fn print(&self) {
+        println!("root      {}", self.root);
+        println!("source    {}", self.source_node);
+        println!("created   {}", self.created_at);
+        println!("exporter  {}", self.exporter);
+        println!();
+        for (unit, status) in &self.units {
+            let verdict = match status {
+                UnitStatus::Signed => format!("signed by author {}", unit.author),
+                UnitStatus::SignedByOther(signer) => {
+                    format!("signed by {signer} (author is {})", unit.author)
+                }
+                UnitStatus::Unsigned => "unsigned".to_string(),
+                UnitStatus::Failed(e) => format!("FAILED: {e}"),
+            };
+            println!("  {}  {:<10}  {verdict}", unit.id, unit.unit_type.to_string());
+        }
+        for w in &self.warnings {
+            println!("warning: {w}");
+        }
+        for e in &self.errors {
+            println!("error: {e}");
+        }
+    }
+}
+
+/// Verify a bundle archive entirely offline.
+///
+/// Returns `Err` only if the archive cannot be read at all; every other
+/// problem is recorded in the [`Report`].
+pub fn verify(archive: &[u8]) -> Result<Report, String> {
+    let mut entries: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    let mut reader = tar::Archive::new(Cursor::new(archive));
+    for entry in reader.entries().map_err(|e| format!("not a bundle archive: {e}"))? {
+        let mut entry = entry.map_err(|e| format!("corrupt archive entry: {e}"))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .map_err(|e| format!("corrupt archive path: {e}"))?
+            .to_string_lossy()
+            .into_owned();
+        let mut bytes = Vec::new();
+        entry
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("failed to read {path}: {e}"))?;
+        entries.insert(path, bytes);
+    }
+
+    let manifest_bytes = entries
+        .get(MANIFEST_PATH)
+        .ok_or("archive has no manifest.json")?;
+    let manifest: Manifest = serde_json::from_slice(manifest_bytes)
+        .map_err(|e| format!("manifest.json is malformed: {e}"))?;
+
+    let mut report = Report {
+        root: manifest.root.clone(),
+        exporter: manifest.exporter.clone(),
+        source_node: manifest.source_node.clone(),
+        created_at: manifest.created_at.clone(),
+        ..Default::default()
+    };
+
+    if manifest.format != FORMAT {
+        report
+            .errors
+            .push(format!("unsupported bundle format {:?}", manifest.format));
+    }
+
+    // Manifest signature.
+    match entries
+        .get(SIGNATURE_PATH)
+        .map(|b| serde_json::from_slice::<ManifestSignature>(b))
+    {
+        None => report.errors.push("manifest is not signed (no manifest.sig)".into()),
+        Some(Err(e)) => report.errors.push(format!("manifest.sig is malformed: {e}")),
+        Some(Ok(sig)) => {
+            if sig.signer != manifest.exporter {
+                report.errors.push(format!(
+                    "manifest signed by {} but exporter is {}",
+                    sig.signer, manifest.exporter
+                ));
+            } else if let Err(e) = verify_detached(&sig.signer, manifest_bytes, &sig.signature) {
+                report.errors.push(format!("manifest signature is invalid: {e}"));
+            }
+        }
+    }
+
+    // File digests: every listed file must match, and nothing may be unlisted.
+    let listed: BTreeSet<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+    for file in &manifest.files {
+        match entries.get(&file.path) {
+            None => report.errors.push(format!("{} is listed but missing", file.path)),
+            Some(bytes) if sha256_hex(bytes) != file.sha256 => {
+                report.errors.push(format!("{} does not match its digest", file.path))
+            }
+            Some(_) => {}
+        }
+    }
+    for path in entries.keys() {
+        if path != MANIFEST_PATH && path != SIGNATURE_PATH && !listed.contains(path.as_str()) {
+            report.errors.push(format!("{path} is not listed in the manifest"));
+        }
+    }
+
+    // Units.
+    let mut authors_seen = BTreeSet::new();
+    for (path, bytes) in entries.iter().filter(|(p, _)| p.starts_with("units/")) {
+        let unit: SemanticUnit = match serde_json::from_slice(bytes) {
+            Ok(u) => u,
+            Err(e) => {
+                report.errors.push(format!("{path} is not a valid unit: {e}"));
+                continue;
+            }
+        };
+        if *path != format!("units/{}.json", unit.id) {
+            report.errors.push(format!("{path} holds unit {}", unit.id));
+        }
+        authors_seen.insert(unit.author.clone());
+        let status = unit_status(&unit);
+        report.units.push((unit, status));
+    }
+    report.units.sort_by(|a, b| a.0.id.cmp(&b.0.id));
+
+    let ids: BTreeSet<&str> = report.units.iter().map(|(u, _)| u.id.as_str()).collect();
+    if !ids.contains(manifest.root.as_str()) {
+        report.errors.push(format!("root unit {} is not in the bundle", manifest.root));
+    }
+    for (unit, _) in &report.units {
+        for r in unit.references.iter().flatten() {
+            if !ids.contains(r.id.as_str()) {
+                report.warnings.push(format!(
+                    "unit {} references {} which is not in the bundle",
+                    unit.id, r.id
+                ));
+            }
+        }
+    }
+
+    let declared: BTreeSet<String> = manifest.authors.iter().cloned().collect();
+    if declared != authors_seen {
+        report
+            .errors
+            .push("manifest authors do not match the authors of the bundled units".into());
+    }
+    check_author_profiles(&entries, &mut report);
+
+    Ok(report)
+}
+
+/// Classify a single unit: valid, and if signed, whose signature it carries.
+fn unit_status(unit: &SemanticUnit) -> UnitStatus {
+    if let Err(e) = validate_unit(unit) {
+        return UnitStatus::Failed(format!("invalid unit: {e}"));
+    }
+    match verify_proof(unit) {
+        Ok(()) => {
+            let signer = proof_signer(unit).unwrap_or_default();
+            // A delegate's signature counts as the author's (ADR-0014).
+            if proof_author(unit) == Some(unit.author.as_str()) {
+                UnitStatus::Signed
+            } else {
+                UnitStatus::SignedByOther(signer.to_string())
+            }
+        }
+        Err(ProofError::ProofMissing) => UnitStatus::Unsigned,
+        Err(e) => UnitStatus::Failed(format!("proof: {e}")),
+    }
+}
+
+/// Where an author's node profile records a public key, it must be the key
+/// their `did:key` DID encodes.
+fn check_author_profiles(entries: &BTreeMap<String, Vec<u8>>, report: &mut Report) {
+    let Some(bytes) = entries.get(AUTHORS_PATH) else {
+        report.errors.push("authors.json is missing".into());
+        return;
+    };
+    let authors: Vec<AuthorEntry> = match serde_json::from_slice(bytes) {
+        Ok(a) => a,
+        Err(e) => {
+            report.errors.push(format!("authors.json is malformed: {e}"));
+            return;
+        }
+    };
+    for author in authors {
+        let recorded = author
+            .profile
+            .as_ref()
+            .and_then(|p| p.get("public_key"))
+            .and_then(|k| k.as_str());
+        let (Some(recorded), Ok(key)) = (recorded, did_key_to_verifying_key(&author.did)) else {
+            continue;
+        };
+        // The profile key is the multibase part of a did:key, so core's
+        // resolver decodes it too.
+        let recorded = did_key_to_verifying_key(&format!("did:key:{recorded}"));
+        if recorded.ok() != Some(key) {
+            report.errors.push(format!(
+                "profile public key for {} does not match its DID",
+                author.did
+            ));
+        }
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    crate::hex_encode(&Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semanticweft::{sign_unit, UnitType};
+
+    fn key(seed: u8) -> (SigningKey, String) {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let (did, _) = crate::derive_did_and_pubkey(&key);
+        (key, did)
+    }
+
+    fn bundle(unit: &SemanticUnit, exporter: &SigningKey, exporter_did: &str) -> Vec<u8> {
+        create(BundleInput {
+            root: &unit.id,
+            source_node: "https://node.example.com",
+            units: std::slice::from_ref(unit),
+            authors: vec![AuthorEntry {
+                did: unit.author.clone(),
+                profile: None,
+            }],
+            key: exporter,
+            exporter: exporter_did,
+        })
+        .unwrap()
+    }
+
+    fn signed_unit(author: &(SigningKey, String)) -> SemanticUnit {
+        let mut unit = SemanticUnit::new(UnitType::Assertion, "bundled", &author.1);
+        sign_unit(&mut unit, &author.0, &author.1).unwrap();
+        unit
+    }
+
+    /// Rewrite the archive's files with `edit` applied.
+    fn repack(archive: &[u8], edit: impl FnOnce(&mut BTreeMap<String, Vec<u8>>)) -> Vec<u8> {
+        let mut files = BTreeMap::new();
+        let mut reader = tar::Archive::new(Cursor::new(archive));
+        for entry in reader.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().to_string_lossy().into_owned();
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes).unwrap();
+            files.insert(path, bytes);
+        }
+        edit(&mut files);
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, bytes) in &files {
+            append(&mut builder, path, bytes).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn intact_bundle_passes() {
+        let author = key(1);
+        let unit = signed_unit(&author);
+        let report = verify(&bundle(&unit, &author.0, &author.1)).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(report.passed(true, false));
+    }
+
+    #[test]
+    fn foreign_signer_fails_unless_allowed() {
+        let (author, other) = (key(1), key(2));
+        let mut unit = SemanticUnit::new(UnitType::Assertion, "bundled", &author.1);
+        sign_unit(&mut unit, &other.0, &other.1).unwrap();
+        let report = verify(&bundle(&unit, &author.0, &author.1)).unwrap();
+        assert!(matches!(report.units[0].1, UnitStatus::SignedByOther(_)));
+        assert!(!report.passed(false, false));
+        assert!(report.passed(false, true));
+    }
+
+    #[test]
+    fn changed_file_fails_its_digest() {
+        let author = key(1);
+        let unit = signed_unit(&author);
+        let path = format!("units/{}.json", unit.id);
+        let archive = repack(&bundle(&unit, &author.0, &author.1), |files| {
+            files.get_mut(&path).unwrap().push(b'\n');
+        });
+        let report = verify(&archive).unwrap();
+        assert!(report.errors.contains(&format!("{path} does not match its digest")));
+        assert!(!report.passed(false, true));
+    }
+
+    #[test]
+    fn bad_manifest_signature_fails() {
+        let (author, other) = (key(1), key(2));
+        let unit = signed_unit(&author);
+        let archive = repack(&bundle(&unit, &author.0, &author.1), |files| {
+            let mut sig: ManifestSignature =
+                serde_json::from_slice(&files[SIGNATURE_PATH]).unwrap();
+            sig.signature = sign_detached(&other.0, &files[MANIFEST_PATH]);
+            files.insert(SIGNATURE_PATH.into(), serde_json::to_vec(&sig).unwrap());
+        });
+        let report = verify(&archive).unwrap();
+        assert!(
+            report.errors.iter().any(|e| e.starts_with("manifest signature is invalid")),
+            "{:?}",
+            report.errors
+        );
+        assert!(!report.passed(false, true));
+    }
+
+    #[test]
+    fn profile_key_must_match_the_did() {
+        let (author, other) = (key(1), key(2));
+        let unit = signed_unit(&author);
+        let with_profile = |public_key: &str| {
+            let archive = create(BundleInput {
+                root: &unit.id,
+                source_node: "https://node.example.com",
+                units: std::slice::from_ref(&unit),
+                authors: vec![AuthorEntry {
+                    did: author.1.clone(),
+                    profile: Some(serde_json::json!({ "public_key": public_key })),
+                }],
+                key: &author.0,
+                exporter: &author.1,
+            })
+            .unwrap();
+            verify(&archive).unwrap()
+        };
+        let mismatch = format!("profile public key for {} does not match its DID", author.1);
+
+        let own = with_profile(author.1.strip_prefix("did:key:").unwrap());
+        assert!(own.errors.is_empty(), "{:?}", own.errors);
+        let foreign = with_profile(other.1.strip_prefix("did:key:").unwrap());
+        assert!(foreign.errors.contains(&mismatch));
+        assert!(with_profile("not-a-key").errors.contains(&mismatch));
+    }
+
+    #[test]
+    fn unlisted_file_fails() {
+        let author = key(1);
+        let unit = signed_unit(&author);
+        let archive = repack(&bundle(&unit, &author.0, &author.1), |files| {
+            files.insert("notes.txt".into(), b"smuggled".to_vec());
+        });
+        let report = verify(&archive).unwrap();
+        assert!(report
+            .errors
+            .contains(&"notes.txt is not listed in the manifest".to_string()));
+        assert!(!report.passed(false, true));
+    }
+}
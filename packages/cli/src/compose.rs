@@ -0,0 +1,364 @@
+//! `sweft compose` — build a graph from a YAML or TOML outline.
+//!
+//! A plan lists units under local labels, and references point at those
+//! labels instead of UUIDs:
+//!
+//! ```yaml
+//! author: did:key:z6Mk...      # default for every unit
+//! visibility: network          # default for every unit
+//! units:
+//!   - label: q
+//!     type: question
+//!     content: Does the cache need invalidating on deploy?
+//!   - label: a
+//!     type: assertion
+//!     content: Cached entries embed the build hash.
+//!     confidence: 0.9
+//!     references:
+//!       - { to: q, rel: questions }
+//!   - label: i
+//!     type: inference
+//!     content: No invalidation is needed.
+//!     references:
+//!       - { to: a, rel: derives-from }
+//!       - { to: 019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6c, rel: supports }
+//! ```
+//!
+//! The TOML form uses the same keys with `[[units]]` tables. A reference
+//! target that is not a label must be the UUID of an existing unit.
+//!
+//! Units are created in dependency order — everything a unit references
+//! within the plan is created first — so the UUIDv7 ids and timestamps
+//! ascend along the argument. Reference cycles among labels are rejected,
+//! since no such order exists.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use semanticweft::{validate_unit, Reference, RelType, SemanticUnit, Source, UnitType, Visibility};
+use serde::Deserialize;
+
+/// A parsed plan file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Plan {
+    /// Author of units that do not name one.
+    #[serde(default)]
+    pub author: Option<String>,
+
+    /// Visibility of units that do not set one.
+    #[serde(default)]
+    pub visibility: Option<Visibility>,
+
+    pub units: Vec<PlanUnit>,
+}
+
+/// One unit of a plan.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlanUnit {
+    /// Name other units use to reference this one.
+    pub label: String,
+
+    #[serde(rename = "type")]
+    pub unit_type: UnitType,
+
+    pub content: String,
+
+    #[serde(default)]
+    pub author: Option<String>,
+
+    #[serde(default)]
+    pub confidence: Option<f64>,
+
+    #[serde(default)]
+    pub assumptions: Vec<String>,
+
+    #[serde(default)]
+    pub source: Option<Source>,
+
+    #[serde(default)]
+    pub references: Vec<PlanReference>,
+
+    #[serde(default)]
+    pub visibility: Option<Visibility>,
+
+    #[serde(default)]
+    pub audience: Vec<String>,
+}
+
+/// A reference by label, or by UUID for a unit outside the plan.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlanReference {
+    pub to: String,
+    pub rel: RelType,
+}
+
+/// Parse a plan, choosing TOML for `.toml` files and YAML (which also
+/// accepts JSON) for everything else, including stdin.
+pub fn parse(path: &Path, text: &str) -> Result<Plan, String> {
+    let is_toml = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("toml"));
+    let plan: Plan = if is_toml {
+        toml::from_str(text).map_err(|e| format!("{}: {e}", path.display()))?
+    } else {
+        serde_yaml::from_str(text).map_err(|e| format!("{}: {e}", path.display()))?
+    };
+    if plan.units.is_empty() {
+        return Err(format!("{} defines no units", path.display()));
+    }
+    Ok(plan)
+}
+
+/// Create the plan's units in dependency order and validate them.
+///
+/// `default_author` is used for units when neither the unit nor the plan
+/// names an author (normally the signing key's DID). Returns the units in
+/// creation order, each paired with its label.
+pub fn build(
+    plan: Plan,
+    default_author: Option<&str>,
+) -> Result<Vec<(String, SemanticUnit)>, String> {
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (i, unit) in plan.units.iter().enumerate() {
+        if unit.label.trim().is_empty() {
+            return Err(format!("unit #{} has an empty label", i + 1));
+        }
+        if index.insert(unit.label.as_str(), i).is_some() {
+            return Err(format!("label {:?} is used more than once", unit.label));
+        }
+    }
+
+    // deps[i]: plan indices unit i references.
+    let mut deps: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); plan.units.len()];
+    for (i, unit) in plan.units.iter().enumerate() {
+        for r in &unit.references {
+            match index.get(r.to.as_str()) {
+                Some(&j) if j == i => {
+                    return Err(format!("unit {:?} references itself", unit.label));
+                }
+                Some(&j) => {
+                    deps[i].insert(j);
+                }
+                None if uuid_like(&r.to) => {}
+                None => {
+                    return Err(format!(
+                        "unit {:?} references unknown label {:?}",
+                        unit.label, r.to
+                    ));
+                }
+            }
+        }
+    }
+
+    let order = dependency_order(&deps).map_err(|cycle| {
+        let labels: Vec<&str> = cycle
+            .iter()
+            .map(|&i| plan.units[i].label.as_str())
+            .collect();
+        format!(
+            "reference cycle among labels {}; compose needs an order in which \
+             every unit follows the units it references",
+            labels.join(", ")
+        )
+    })?;
+
+    let mut ids: HashMap<String, String> = HashMap::new();
+    let mut slots: Vec<Option<PlanUnit>> = plan.units.into_iter().map(Some).collect();
+    let mut out = Vec::with_capacity(slots.len());
+    for i in order {
+        let p = slots[i].take().expect("each unit is ordered once");
+        let author = p
+            .author
+            .or_else(|| plan.author.clone())
+            .or_else(|| default_author.map(str::to_string))
+            .ok_or_else(|| {
+                format!(
+                    "unit {:?} has no author; set `author` in the plan or sign with --key",
+                    p.label
+                )
+            })?;
+
+        let mut unit = SemanticUnit::new(p.unit_type, p.content, author);
+        unit.confidence = p.confidence;
+        unit.assumptions = (!p.assumptions.is_empty()).then_some(p.assumptions);
+        unit.source = p.source;
+        let references: Vec<Reference> = p
+            .references
+            .into_iter()
+            .map(|r| Reference {
+                id: ids.get(&r.to).cloned().unwrap_or(r.to),
+                rel: r.rel,
+            })
+            .collect();
+        unit.references = (!references.is_empty()).then_some(references);
+        // Public is the default; leave the field absent rather than explicit.
+        unit.visibility = p
+            .visibility
+            .or_else(|| plan.visibility.clone())
+            .filter(|v| *v != Visibility::Public);
+        unit.audience = (!p.audience.is_empty()).then_some(p.audience);
+
+        validate_unit(&unit).map_err(|e| format!("unit {:?} is invalid: {e}", p.label))?;
+        ids.insert(p.label.clone(), unit.id.clone());
+        out.push((p.label, unit));
+    }
+    Ok(out)
+}
+
+/// Kahn's algorithm over `deps`, taking ready units in plan order. On a
+/// cycle, returns the indices that could not be ordered.
+fn dependency_order(deps: &[BTreeSet<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); deps.len()];
+    for (i, d) in deps.iter().enumerate() {
+        for &j in d {
+            dependents[j].push(i);
+        }
+    }
+    let mut waiting: Vec<usize> = deps.iter().map(BTreeSet::len).collect();
+    let mut ready: BTreeSet<usize> = (0..deps.len()).filter(|&i| waiting[i] == 0).collect();
+    let mut order = Vec::with_capacity(deps.len());
+    while let Some(i) = ready.pop_first() {
+        order.push(i);
+        for &d in &dependents[i] {
+            waiting[d] -= 1;
+            if waiting[d] == 0 {
+                ready.insert(d);
+            }
+        }
+    }
+    if order.len() == deps.len() {
+        Ok(order)
+    } else {
+        Err((0..deps.len()).filter(|&i| waiting[i] > 0).collect())
+    }
+}
+
+/// `true` if `s` has the shape of a UUID; validation checks the version.
+fn uuid_like(s: &str) -> bool {
+    s.len() == 36
+        && s.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTHOR: &str = "did:key:z6MkAuthor";
+
+    fn plan(yaml: &str) -> Plan {
+        parse(Path::new("plan.yaml"), yaml).unwrap()
+    }
+
+    #[test]
+    fn labels_resolve_to_ids_created_first() {
+        let units = build(
+            plan(
+                "
+                units:
+                  - label: i
+                    type: inference
+                    content: No invalidation is needed.
+                    references: [{ to: a, rel: derives-from }]
+                  - label: a
+                    type: assertion
+                    content: Cached entries embed the build hash.
+                    author: did:key:z6MkOther
+                ",
+            ),
+            Some(AUTHOR),
+        )
+        .unwrap();
+        let labels: Vec<&str> = units.iter().map(|(l, _)| l.as_str()).collect();
+        assert_eq!(labels, ["a", "i"]);
+        let (a, i) = (&units[0].1, &units[1].1);
+        assert!(a.id < i.id, "ids ascend along the argument");
+        assert_eq!(i.references.as_ref().unwrap()[0].id, a.id);
+        assert_eq!(a.author, "did:key:z6MkOther");
+        assert_eq!(i.author, AUTHOR);
+    }
+
+    #[test]
+    fn uuid_references_pass_through() {
+        let units = build(
+            plan(
+                "
+                units:
+                  - label: a
+                    type: assertion
+                    content: Supported elsewhere.
+                    references:
+                      - { to: 019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6c, rel: supports }
+                ",
+            ),
+            Some(AUTHOR),
+        )
+        .unwrap();
+        let refs = units[0].1.references.as_ref().unwrap();
+        assert_eq!(refs[0].id, "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6c");
+    }
+
+    #[test]
+    fn unknown_labels_and_missing_authors_are_rejected() {
+        let unknown = "
+            units:
+              - label: a
+                type: assertion
+                content: Dangling.
+                references: [{ to: nowhere, rel: supports }]
+            ";
+        assert_eq!(
+            build(plan(unknown), Some(AUTHOR)).unwrap_err(),
+            r#"unit "a" references unknown label "nowhere""#
+        );
+
+        let anonymous = "
+            units:
+              - { label: a, type: assertion, content: Whose? }
+            ";
+        assert!(build(plan(anonymous), None)
+            .unwrap_err()
+            .contains("has no author"));
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let cyclic = "
+            units:
+              - label: root
+                type: question
+                content: Why?
+              - label: a
+                type: assertion
+                content: Because b.
+                references: [{ to: b, rel: derives-from }]
+              - label: b
+                type: assertion
+                content: Because a.
+                references: [{ to: a, rel: derives-from }]
+            ";
+        let err = build(plan(cyclic), Some(AUTHOR)).unwrap_err();
+        assert!(
+            err.starts_with("reference cycle among labels a, b;"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn dependency_order_prefers_plan_order() {
+        let deps = [
+            BTreeSet::from([2]),
+            BTreeSet::new(),
+            BTreeSet::new(),
+            BTreeSet::from([0, 1]),
+        ];
+        assert_eq!(dependency_order(&deps), Ok(vec![1, 2, 0, 3]));
+        let cycle = [BTreeSet::new(), BTreeSet::from([2]), BTreeSet::from([1])];
+        assert_eq!(dependency_order(&cycle), Err(vec![1, 2]));
+    }
+}
@@ -0,0 +1,252 @@
+//! Named profiles in `~/.config/sweft/config.toml`.
+//!
+//! A profile bundles the settings that would otherwise be repeated on every
+//! command line — node URL, key file, default visibility for new units, and
+//! default output format:
+//!
+//! ```toml
+//! current = "work"
+//!
+//! [profiles.work]
+//! node = "https://node.example.com"
+//! key = "~/.config/sweft/work.key"
+//! visibility = "network"
+//! format = "text"
+//! ```
+//!
+//! The active profile is chosen by `--profile`, then `SWEFT_PROFILE`, then
+//! `current`. [`apply_profile`] runs before argument parsing and exports the
+//! profile's settings as the environment variables the subcommands already
+//! read (`SWEFT_NODE`, `SWEFT_KEY`, `SWEFT_VISIBILITY`, `SWEFT_FORMAT`), skipping
+//! any that are already set. Explicit flags therefore override the
+//! environment, which overrides the profile.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use semanticweft::Visibility;
+use serde::{Deserialize, Serialize};
+
+/// How commands that print units present them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable rendering.
+    Text,
+    /// JSON, one unit per line for streams.
+    Json,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// The contents of `config.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Config {
+    /// Profile used when neither `--profile` nor `SWEFT_PROFILE` is given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current: Option<String>,
+
+    /// Profiles by name.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// One named profile. Every field is optional.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    /// Base URL of the node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+
+    /// Path to the Ed25519 key file; a leading `~/` is expanded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+
+    /// Visibility given to units created with `sweft new`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+
+    /// Output format for commands that print units.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<OutputFormat>,
+}
+
+/// Location of the config file: `SWEFT_CONFIG`, or
+/// `~/.config/sweft/config.toml`.
+pub fn config_path() -> PathBuf {
+    match std::env::var_os("SWEFT_CONFIG") {
+        Some(p) => PathBuf::from(p),
+        None => crate::sweft_config_dir().join("config.toml"),
+    }
+}
+
+impl Config {
+    /// Load the config at `path`; a missing file is an empty config.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text)
+                .map_err(|e| format!("invalid config file {}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("failed to read {}: {e}", path.display())),
+        }
+    }
+
+    /// Write the config to `path`, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+        }
+        let text = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, text).map_err(|e| format!("failed to write {}: {e}", path.display()))
+    }
+}
+
+/// Select the active profile and export its settings to the environment.
+///
+/// `explicit` is the `--profile` value, if given on the command line. Naming
+/// a profile that does not exist — explicitly or via `SWEFT_PROFILE` — is an
+/// error; a dangling `current` is ignored, and an unreadable config file only
+/// warns, so a broken config never blocks commands that do not need it.
+pub fn apply_profile(explicit: Option<String>) -> Result<(), String> {
+    let config = Config::load(&config_path()).unwrap_or_else(|e| {
+        eprintln!("sweft: warning: {e}; ignoring profiles");
+        Config::default()
+    });
+    let requested = explicit.or_else(|| std::env::var("SWEFT_PROFILE").ok());
+    let Some(profile) = config.select(requested)? else {
+        return Ok(());
+    };
+    for (var, value) in profile.exports(|var| std::env::var_os(var).is_some()) {
+        std::env::set_var(var, value);
+    }
+    Ok(())
+}
+
+impl Config {
+    /// The active profile: `requested` (from `--profile` or `SWEFT_PROFILE`),
+    /// else `current`. A requested profile must exist; a missing `current`
+    /// selects nothing.
+    pub fn select(&self, requested: Option<String>) -> Result<Option<&Profile>, String> {
+        match requested {
+            Some(name) => self.profiles.get(&name).map(Some).ok_or_else(|| {
+                format!("no profile named {name:?} in {}", config_path().display())
+            }),
+            None => Ok(self
+                .current
+                .as_ref()
+                .and_then(|name| self.profiles.get(name))),
+        }
+    }
+}
+
+impl Profile {
+    /// The environment variables this profile sets, skipping those for
+    /// which `is_set` is true so the environment overrides the profile.
+    fn exports(&self, is_set: impl Fn(&str) -> bool) -> Vec<(&'static str, String)> {
+        [
+            ("SWEFT_NODE", self.node.clone()),
+            ("SWEFT_KEY", self.key.as_deref().map(expand_home)),
+            ("SWEFT_VISIBILITY", self.visibility.as_ref().map(|v| v.to_string())),
+            ("SWEFT_FORMAT", self.format.map(|f| f.to_string())),
+        ]
+        .into_iter()
+        .filter_map(|(var, value)| Some((var, value?)))
+        .filter(|(var, _)| !is_set(var))
+        .collect()
+    }
+}
+
+/// Find a `--profile NAME` or `--profile=NAME` argument ahead of parsing.
+pub fn profile_arg(args: &[String]) -> Option<String> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            break;
+        }
+        if arg == "--profile" {
+            return iter.next().cloned();
+        }
+        if let Some(name) = arg.strip_prefix("--profile=") {
+            return Some(name.to_string());
+        }
+    }
+    None
+}
+
+fn expand_home(path: &str) -> String {
+    match path.strip_prefix("~/") {
+        Some(rest) => {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            format!("{home}/{rest}")
+        }
+        None => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        toml::from_str(
+            r#"
+            current = "work"
+
+            [profiles.work]
+            node = "https://work.example.com"
+            format = "json"
+
+            [profiles.home]
+            node = "https://home.example.com"
+            key = "/keys/home.key"
+            visibility = "network"
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn requested_profile_wins_over_current() {
+        let config = config();
+        let home = config.select(Some("home".into())).unwrap().unwrap();
+        assert_eq!(home.node.as_deref(), Some("https://home.example.com"));
+        let current = config.select(None).unwrap().unwrap();
+        assert_eq!(current.node.as_deref(), Some("https://work.example.com"));
+    }
+
+    #[test]
+    fn unknown_requested_profile_is_an_error_but_dangling_current_is_not() {
+        let mut config = config();
+        assert!(config.select(Some("nope".into())).is_err());
+        config.current = Some("gone".into());
+        assert!(config.select(None).unwrap().is_none());
+        assert!(Config::default().select(None).unwrap().is_none());
+    }
+
+    #[test]
+    fn environment_overrides_profile() {
+        let config = config();
+        let home = config.select(Some("home".into())).unwrap().unwrap();
+        assert_eq!(
+            home.exports(|_| false),
+            vec![
+                ("SWEFT_NODE", "https://home.example.com".to_string()),
+                ("SWEFT_KEY", "/keys/home.key".to_string()),
+                ("SWEFT_VISIBILITY", "network".to_string()),
+            ]
+        );
+        let exported = home.exports(|var| var == "SWEFT_NODE");
+        assert!(exported.iter().all(|(var, _)| *var != "SWEFT_NODE"));
+        assert_eq!(exported.len(), 2);
+    }
+}
@@ -0,0 +1,394 @@
+//! `sweft doctor` — diagnose connectivity and authentication problems.
+//!
+//! Registration and signed requests fail for a handful of recurring reasons
+//! that all surface as a bare 401 or 404: a clock more than five minutes off
+//! (the node's replay window), a node whose advertised `api_base` differs
+//! from the URL clients use, a missing capability, or a reverse proxy that
+//! strips or rewrites the `Signature`, `Date`, or `Host` headers. Each check
+//! here isolates one of those causes and attaches a remediation hint.
+
+use std::time::{Duration, SystemTime};
+
+use ed25519_dalek::SigningKey;
+use semanticweft_agent_core::WebFingerError;
+use semanticweft_client::{Client, Error, ErrorCode};
+use semanticweft_node_api::{Capability, NodeInfo};
+
+use crate::{block_on, extract_host};
+
+/// The node's replay window for signed requests (spec §8, ADR-0002).
+const MAX_SKEW: Duration = Duration::from_secs(5 * 60);
+
+/// Skew above which the clock check warns even though requests still pass.
+const WARN_SKEW: Duration = Duration::from_secs(60);
+
+/// Result of one check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Warn,
+    Fail,
+    /// Not run because a prerequisite is missing.
+    Skip,
+}
+
+/// One line of the checklist.
+#[derive(Debug)]
+pub struct Check {
+    pub name: &'static str,
+    pub outcome: Outcome,
+    pub detail: String,
+    pub hint: Option<String>,
+}
+
+impl Check {
+    fn new(name: &'static str, outcome: Outcome, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            outcome,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+/// The outcome of every check, in the order they ran.
+#[derive(Debug)]
+pub struct Report {
+    pub node: String,
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    /// `true` if no check failed. Warnings and skipped checks do not count.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|c| c.outcome != Outcome::Fail)
+    }
+
+    /// Print the checklist to stdout.
+    pub fn print(&self) {
+        println!("Diagnosing {}", self.node);
+        println!();
+        for check in &self.checks {
+            let mark = match check.outcome {
+                Outcome::Pass => "pass",
+                Outcome::Warn => "warn",
+                Outcome::Fail => "FAIL",
+                Outcome::Skip => "skip",
+            };
+            println!("  [{mark}] {:<16} {}", check.name, check.detail);
+            if let Some(ref hint) = check.hint {
+                println!("         {:<16} hint: {hint}", "");
+            }
+        }
+        let failed = self
+            .checks
+            .iter()
+            .filter(|c| c.outcome == Outcome::Fail)
+            .count();
+        println!();
+        if failed == 0 {
+            println!("All checks passed.");
+        } else {
+            println!("{failed} check(s) failed.");
+        }
+    }
+}
+
+/// Run every check against `node`. `key` is the caller's identity key;
+/// without it the signed round-trip and WebFinger checks are skipped.
+pub fn run(node: &str, key: Option<&SigningKey>) -> Report {
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_else(|e| crate::fatal(&format!("cannot build HTTP client: {e}")));
+    let client = crate::node_client(node, key).with_http_client(http);
+    let node = client.node_url();
+
+    let (discovery, clock, info) = check_discovery(&client);
+    let reachable = clock.is_some();
+    let mut checks = vec![discovery];
+    checks.push(clock.unwrap_or_else(|| Check::new("clock", Outcome::Skip, "node unreachable")));
+    if let Some(ref info) = info {
+        checks.push(check_api_base(node, info));
+        checks.push(check_capabilities(info));
+    }
+
+    match client.did() {
+        _ if !reachable => {
+            checks.push(Check::new(
+                "signed request",
+                Outcome::Skip,
+                "node unreachable",
+            ));
+            checks.push(Check::new("webfinger", Outcome::Skip, "node unreachable"));
+        }
+        Some(did) => {
+            checks.push(check_signed_request(&client, did));
+            checks.push(check_webfinger(&client, did));
+        }
+        None => {
+            let hint = "run `sweft keygen`, or pass --key";
+            checks.push(Check::new("signed request", Outcome::Skip, "no identity key").hint(hint));
+            checks.push(Check::new("webfinger", Outcome::Skip, "no identity key").hint(hint));
+        }
+    }
+
+    Report {
+        node: node.to_string(),
+        checks,
+    }
+}
+
+/// Fetch and validate the discovery document. Returns the discovery check,
+/// the clock check when the node answered at all, and the parsed document
+/// when it is usable.
+fn check_discovery(client: &Client) -> (Check, Option<Check>, Option<NodeInfo>) {
+    const NAME: &str = "discovery";
+    let resp = match block_on(client.node_info_response()) {
+        Ok(resp) => resp,
+        Err(e) => {
+            let check = Check::new(NAME, Outcome::Fail, e.to_string())
+                .hint("check the URL and that the node is running and reachable");
+            return (check, None, None);
+        }
+    };
+
+    let clock = Some(check_clock(resp.headers().get("date")));
+
+    let status = resp.status();
+    if !status.is_success() {
+        let check = Check::new(
+            NAME,
+            Outcome::Fail,
+            format!("/.well-known/semanticweft returned {status}"),
+        )
+        .hint("pass the node's root URL; the discovery document is served outside /v1");
+        return (check, clock, None);
+    }
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    let info: NodeInfo = match block_on(resp.json()) {
+        Ok(info) => info,
+        Err(e) => {
+            let check = Check::new(
+                NAME,
+                Outcome::Fail,
+                format!("not a valid NodeInfo document: {e}"),
+            )
+            .hint("the URL may point at something other than a SemanticWeft node");
+            return (check, clock, None);
+        }
+    };
+
+    if !info.is_compatible() {
+        let check = Check::new(
+            NAME,
+            Outcome::Fail,
+            format!("unsupported protocol_version {:?}", info.protocol_version),
+        )
+        .hint(format!(
+            "this sweft speaks protocol {}",
+            NodeInfo::PROTOCOL_VERSION
+        ));
+        return (check, clock, None);
+    }
+    let check = if content_type.starts_with("application/json") {
+        let label = info.name.as_deref().unwrap_or(&info.node_id);
+        Check::new(
+            NAME,
+            Outcome::Pass,
+            format!("{label}, protocol {}", info.protocol_version),
+        )
+    } else {
+        Check::new(
+            NAME,
+            Outcome::Warn,
+            format!("served as {content_type:?}, not application/json"),
+        )
+        .hint("the spec requires Content-Type: application/json; check proxy rewriting")
+    };
+    (check, clock, Some(info))
+}
+
+/// Compare the node's `Date` header with the local clock.
+fn check_clock(date: Option<&reqwest::header::HeaderValue>) -> Check {
+    const NAME: &str = "clock";
+    let Some(server) = date
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| httpdate::parse_http_date(v).ok())
+    else {
+        return Check::new(
+            NAME,
+            Outcome::Warn,
+            "node sent no usable Date header; skew unknown",
+        );
+    };
+    let local = SystemTime::now();
+    let (skew, direction) = match server.duration_since(local) {
+        Ok(server_ahead) => (server_ahead, "behind"),
+        Err(e) => (e.duration(), "ahead of"),
+    };
+    let detail = match skew.as_secs() {
+        0 => "local clock is within 1s of the node".to_string(),
+        secs => format!("local clock is {secs}s {direction} the node"),
+    };
+    let hint =
+        "synchronise the clock (e.g. enable NTP); signed requests are rejected beyond ±5 minutes";
+    if skew > MAX_SKEW {
+        Check::new(NAME, Outcome::Fail, detail).hint(hint)
+    } else if skew > WARN_SKEW {
+        Check::new(NAME, Outcome::Warn, detail).hint(hint)
+    } else {
+        Check::new(NAME, Outcome::Pass, detail)
+    }
+}
+
+/// The advertised `api_base` should be the origin clients actually use.
+fn check_api_base(node: &str, info: &NodeInfo) -> Check {
+    const NAME: &str = "api_base";
+    let advertised = info.api_base.trim_end_matches('/');
+    if advertised.trim_end_matches("/v1") == node {
+        return Check::new(NAME, Outcome::Pass, advertised);
+    }
+    let outcome = if extract_host(advertised) == extract_host(node) {
+        Outcome::Warn
+    } else {
+        Outcome::Fail
+    };
+    Check::new(
+        NAME,
+        outcome,
+        format!("node advertises {advertised} but was reached at {node}"),
+    )
+    .hint("set SWEFT_API_BASE on the node to its public URL; peers and WebFinger links use it")
+}
+
+/// Report the advertised capabilities, warning when agent features are absent.
+fn check_capabilities(info: &NodeInfo) -> Check {
+    const NAME: &str = "capabilities";
+    let names: Vec<String> = info
+        .capabilities
+        .iter()
+        .map(Capability::to_string)
+        .collect();
+    let detail = names.join(", ");
+    if !info.supports(Capability::Sync) {
+        return Check::new(NAME, Outcome::Fail, detail)
+            .hint("every conformant node must advertise `sync`");
+    }
+    let missing: Vec<&str> = [
+        (Capability::Agents, "agents"),
+        (Capability::Follows, "follows"),
+    ]
+    .iter()
+    .filter(|(c, _)| !info.supports(*c))
+    .map(|(_, n)| *n)
+    .collect();
+    if missing.is_empty() {
+        Check::new(NAME, Outcome::Pass, detail)
+    } else {
+        Check::new(
+            NAME,
+            Outcome::Warn,
+            format!("{detail} (missing {})", missing.join(", ")),
+        )
+        .hint("registration, follows and inbox commands will not work on this node")
+    }
+}
+
+/// Sign a read-only request for the caller's inbox and classify any 401.
+fn check_signed_request(client: &Client, did: &str) -> Check {
+    const NAME: &str = "signed request";
+    match block_on(client.inbox_page(None, None)) {
+        Ok(_) => Check::new(
+            NAME,
+            Outcome::Pass,
+            format!("GET {} accepted", client.session().inbox_url()),
+        ),
+        Err(Error::Api {
+            status,
+            code,
+            message,
+        }) => Check::new(NAME, Outcome::Fail, format!("{status}: {message}"))
+            .hint(signed_request_hint(status, &code, did)),
+        Err(e) => Check::new(NAME, Outcome::Fail, e.to_string()),
+    }
+}
+
+/// The remediation for a rejected signed request, keyed off the status and
+/// the node's error code.
+fn signed_request_hint(status: u16, code: &ErrorCode, did: &str) -> String {
+    match (status, code) {
+        (404, _) | (401, ErrorCode::UnknownAgent) => format!(
+            "{did} is not registered here; run `sweft apply` or ask the operator to register it"
+        ),
+        (401, ErrorCode::SignatureMissing) => {
+            "a proxy in front of the node is stripping the Signature or Date header".to_string()
+        }
+        (401, ErrorCode::DateOutOfRange) => {
+            "the node rejected the request time; see the clock check".to_string()
+        }
+        (401, ErrorCode::SignatureInvalid) => {
+            "the signature did not match; a proxy may be rewriting the Host header or request path"
+                .to_string()
+        }
+        _ => "unexpected response; check the node's logs".to_string(),
+    }
+}
+
+/// Resolve `did@host` over WebFinger and check the link points at the DID.
+fn check_webfinger(client: &Client, did: &str) -> Check {
+    const NAME: &str = "webfinger";
+    let address = client.session().address(did);
+    match block_on(client.webfinger(did)) {
+        Ok(agent) => Check::new(
+            NAME,
+            Outcome::Pass,
+            format!("{address} → {} (inbox {})", agent.profile_url, agent.inbox_url),
+        ),
+        Err(e) if e.is_not_found() => {
+            Check::new(NAME, Outcome::Fail, format!("{address} not found"))
+                .hint("the agent is not registered on this node; run `sweft apply`")
+        }
+        Err(Error::Api { status, .. }) => {
+            Check::new(NAME, Outcome::Fail, format!("returned {status}"))
+                .hint("the node or a proxy does not serve /.well-known/webfinger")
+        }
+        Err(Error::WebFinger(e @ WebFingerError::SubjectMismatch { .. })) => {
+            Check::new(NAME, Outcome::Warn, e.to_string())
+        }
+        Err(Error::WebFinger(WebFingerError::MissingProfileLink)) => {
+            Check::new(NAME, Outcome::Fail, "JRD has no self link")
+        }
+        Err(Error::WebFinger(e)) => Check::new(NAME, Outcome::Fail, format!("malformed JRD: {e}")),
+        Err(e) => Check::new(NAME, Outcome::Fail, e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints_follow_the_error_code() {
+        let did = "did:key:z6MkAgent";
+        let hint = |status, code| signed_request_hint(status, &code, did);
+        assert!(hint(404, ErrorCode::NotFound).contains("not registered"));
+        assert!(hint(401, ErrorCode::UnknownAgent).contains("not registered"));
+        assert!(hint(401, ErrorCode::SignatureMissing).contains("stripping"));
+        assert!(hint(401, ErrorCode::DateOutOfRange).contains("clock"));
+        assert!(hint(401, ErrorCode::SignatureInvalid).contains("rewriting"));
+        assert!(hint(401, ErrorCode::Unauthorized).contains("logs"));
+        assert!(hint(500, ErrorCode::SignatureInvalid).contains("logs"));
+    }
+}
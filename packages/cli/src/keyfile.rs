@@ -0,0 +1,287 @@
+//! Identity key files, plaintext or passphrase-encrypted.
+//!
+//! A plaintext key file holds the 32-byte Ed25519 seed as 64 hex characters.
+//! An encrypted key file is a JSON document:
+//!
+//! ```json
+//! {
+//!   "format": "sweft-key/1",
+//!   "did": "did:key:z6Mk...",
+//!   "kdf": { "algorithm": "argon2id", "m_cost": 65536, "t_cost": 3, "p_cost": 1, "salt": "…" },
+//!   "cipher": { "algorithm": "xchacha20poly1305", "nonce": "…" },
+//!   "ciphertext": "…"
+//! }
+//! ```
+//!
+//! The seed is sealed with XChaCha20-Poly1305 under a key derived from the
+//! passphrase with Argon2id. The DID is stored in the clear so the file can
+//! be identified without the passphrase, and is bound to the ciphertext as
+//! associated data so it cannot be swapped. Binary fields are hex-encoded.
+//!
+//! Passphrases come from `SWEFT_PASSPHRASE` when set (for scripts and CI),
+//! otherwise from an interactive prompt.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use ed25519_dalek::SigningKey;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::{derive_did_and_pubkey, hex_decode, hex_encode};
+
+/// Format identifier of encrypted key files.
+pub const FORMAT: &str = "sweft-key/1";
+
+/// Environment variable consulted before prompting for a passphrase.
+pub const PASSPHRASE_ENV: &str = "SWEFT_PASSPHRASE";
+
+/// Environment variable for the replacement passphrase in `sweft key rekey`.
+pub const NEW_PASSPHRASE_ENV: &str = "SWEFT_NEW_PASSPHRASE";
+
+// Argon2id cost parameters for new files: 64 MiB, 3 passes, 1 lane
+// (RFC 9106 §4, second recommended option).
+const M_COST: u32 = 64 * 1024;
+const T_COST: u32 = 3;
+const P_COST: u32 = 1;
+
+// Upper bounds accepted when opening a file, so a crafted file cannot make
+// decryption use unbounded memory or time: 1 GiB, 10 passes, 16 lanes.
+const MAX_M_COST: u32 = 1024 * 1024;
+const MAX_T_COST: u32 = 10;
+const MAX_P_COST: u32 = 16;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedKey {
+    format: String,
+    did: String,
+    kdf: KdfParams,
+    cipher: CipherParams,
+    ciphertext: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KdfParams {
+    algorithm: String,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    salt: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CipherParams {
+    algorithm: String,
+    nonce: String,
+}
+
+/// `true` if `contents` is an encrypted key file rather than a hex seed.
+pub fn is_encrypted(contents: &str) -> bool {
+    contents.trim_start().starts_with('{')
+}
+
+/// Parse a plaintext key file.
+pub fn parse_plain(contents: &str) -> Result<SigningKey, String> {
+    let seed = hex_decode(contents.trim())?;
+    let arr: [u8; 32] = seed
+        .try_into()
+        .map_err(|_| "key file must contain a 32-byte hex seed (64 hex chars)".to_string())?;
+    Ok(SigningKey::from_bytes(&arr))
+}
+
+/// Seal `key` under `passphrase`, returning the key file contents.
+pub fn encrypt(key: &SigningKey, passphrase: &str) -> Result<String, String> {
+    encrypt_with(key, passphrase, M_COST, T_COST, P_COST)
+}
+
+fn encrypt_with(
+    key: &SigningKey,
+    passphrase: &str,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+) -> Result<String, String> {
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 24];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let (did, _) = derive_did_and_pubkey(key);
+    let cipher = cipher(passphrase, &salt, m_cost, t_cost, p_cost)?;
+    let ciphertext = cipher
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &key.to_bytes(),
+                aad: did.as_bytes(),
+            },
+        )
+        .map_err(|_| "encryption failed".to_string())?;
+
+    let file = EncryptedKey {
+        format: FORMAT.into(),
+        did,
+        kdf: KdfParams {
+            algorithm: "argon2id".into(),
+            m_cost,
+            t_cost,
+            p_cost,
+            salt: hex_encode(&salt),
+        },
+        cipher: CipherParams {
+            algorithm: "xchacha20poly1305".into(),
+            nonce: hex_encode(&nonce),
+        },
+        ciphertext: hex_encode(&ciphertext),
+    };
+    serde_json::to_string_pretty(&file).map_err(|e| e.to_string())
+}
+
+/// Open an encrypted key file with `passphrase`.
+pub fn decrypt(contents: &str, passphrase: &str) -> Result<SigningKey, String> {
+    let file: EncryptedKey =
+        serde_json::from_str(contents).map_err(|e| format!("malformed key file: {e}"))?;
+    if file.format != FORMAT {
+        return Err(format!("unsupported key file format {:?}", file.format));
+    }
+    if file.kdf.algorithm != "argon2id" || file.cipher.algorithm != "xchacha20poly1305" {
+        return Err(format!(
+            "unsupported key file algorithms {} / {}",
+            file.kdf.algorithm, file.cipher.algorithm
+        ));
+    }
+
+    if file.kdf.m_cost > MAX_M_COST || file.kdf.t_cost > MAX_T_COST || file.kdf.p_cost > MAX_P_COST
+    {
+        return Err(format!(
+            "key file KDF parameters exceed the supported maximum \
+             (m_cost {MAX_M_COST}, t_cost {MAX_T_COST}, p_cost {MAX_P_COST})"
+        ));
+    }
+
+    let salt = hex_decode(&file.kdf.salt)?;
+    let nonce = hex_decode(&file.cipher.nonce)?;
+    if nonce.len() != 24 {
+        return Err("malformed key file: nonce must be 24 bytes".into());
+    }
+    let ciphertext = hex_decode(&file.ciphertext)?;
+
+    let cipher = cipher(passphrase, &salt, file.kdf.m_cost, file.kdf.t_cost, file.kdf.p_cost)?;
+    let seed = cipher
+        .decrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: file.did.as_bytes(),
+            },
+        )
+        .map_err(|_| "wrong passphrase or corrupted key file".to_string())?;
+    let arr: [u8; 32] = seed
+        .try_into()
+        .map_err(|_| "malformed key file: seed must be 32 bytes".to_string())?;
+    Ok(SigningKey::from_bytes(&arr))
+}
+
+fn cipher(
+    passphrase: &str,
+    salt: &[u8],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+) -> Result<XChaCha20Poly1305, String> {
+    let params = Params::new(m_cost, t_cost, p_cost, Some(32))
+        .map_err(|e| format!("invalid KDF parameters: {e}"))?;
+    let mut derived = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut derived)
+        .map_err(|e| format!("key derivation failed: {e}"))?;
+    Ok(XChaCha20Poly1305::new(&derived.into()))
+}
+
+/// Read the passphrase for an existing key file from `SWEFT_PASSPHRASE` or
+/// a prompt.
+pub fn read_passphrase(prompt: &str) -> Result<String, String> {
+    if let Ok(p) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(p);
+    }
+    rpassword::prompt_password(prompt).map_err(|e| format!("cannot read passphrase: {e}"))
+}
+
+/// Read a passphrase for a file about to be encrypted: from `env_var` if
+/// set, otherwise prompted for twice. Empty passphrases are refused.
+pub fn read_new_passphrase(env_var: &str) -> Result<String, String> {
+    let passphrase = match std::env::var(env_var) {
+        Ok(p) => p,
+        Err(_) => {
+            let first = rpassword::prompt_password("New passphrase: ")
+                .map_err(|e| format!("cannot read passphrase: {e}"))?;
+            let second = rpassword::prompt_password("Repeat passphrase: ")
+                .map_err(|e| format!("cannot read passphrase: {e}"))?;
+            if first != second {
+                return Err("passphrases do not match".into());
+            }
+            first
+        }
+    };
+    if passphrase.is_empty() {
+        return Err("passphrase must not be empty".into());
+    }
+    Ok(passphrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Minimal Argon2 costs keep the tests fast.
+    fn sealed(key: &SigningKey, passphrase: &str) -> String {
+        encrypt_with(key, passphrase, 8, 1, 1).unwrap()
+    }
+
+    fn edit(contents: &str, f: impl FnOnce(&mut serde_json::Value)) -> String {
+        let mut value: serde_json::Value = serde_json::from_str(contents).unwrap();
+        f(&mut value);
+        value.to_string()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let contents = sealed(&key, "hunter2");
+        assert!(is_encrypted(&contents));
+        assert_eq!(decrypt(&contents, "hunter2").unwrap().to_bytes(), key.to_bytes());
+    }
+
+    #[test]
+    fn wrong_passphrase_fails() {
+        let contents = sealed(&SigningKey::from_bytes(&[7; 32]), "hunter2");
+        assert_eq!(
+            decrypt(&contents, "hunter3").unwrap_err(),
+            "wrong passphrase or corrupted key file"
+        );
+    }
+
+    #[test]
+    fn swapped_did_fails_authentication() {
+        let contents = sealed(&SigningKey::from_bytes(&[7; 32]), "hunter2");
+        let (other_did, _) = derive_did_and_pubkey(&SigningKey::from_bytes(&[8; 32]));
+        let tampered = edit(&contents, |v| v["did"] = other_did.into());
+        assert_eq!(
+            decrypt(&tampered, "hunter2").unwrap_err(),
+            "wrong passphrase or corrupted key file"
+        );
+    }
+
+    #[test]
+    fn excessive_kdf_costs_are_refused() {
+        let contents = sealed(&SigningKey::from_bytes(&[7; 32]), "hunter2");
+        for (field, value) in [("m_cost", MAX_M_COST + 1), ("t_cost", 1000), ("p_cost", 255)] {
+            let crafted = edit(&contents, |v| v["kdf"][field] = value.into());
+            assert!(
+                decrypt(&crafted, "hunter2").unwrap_err().contains("exceed"),
+                "{field}"
+            );
+        }
+    }
+}
@@ -7,14 +7,38 @@
 //! - **`validate`** — check a unit or array of units against the spec.
 //! - **`render`** — print a human-readable summary of a unit or graph.
 //! - **`new`** — create a new unit with an auto-generated id and timestamp.
+//! - **`compose`** — build a whole graph from a YAML or TOML outline whose
+//!   references use local labels (`--submit` sends it to a node).
 //! - **`keygen`** — generate an Ed25519 identity key pair.
+//! - **`key`** — export, import, or re-encrypt an identity key file, or
+//!   rotate to a new key with a signed succession statement.
+//! - **`sign`** — attach an Ed25519 proof to a unit or array of units.
+//! - **`verify`** — check the proofs on a unit or array of units.
 //!
 //! **Network (requires `--node` or `SWEFT_NODE`):**
 //! - **`register`** — register an agent profile on a node.
-//! - **`submit`** — submit a unit to a node.
+//! - **`apply`** — apply for probationary membership of a node.
+//! - **`status`** — show an agent's membership status and progress.
+//! - **`doctor`** — diagnose discovery, clock, signature, and WebFinger problems.
+//! - **`submit`** — submit a unit, or a whole graph with `--batch`, to a node.
+//! - **`outbox`** — queue units locally and deliver them when the node is
+//!   reachable, retrying with back-off.
 //! - **`fetch`** — retrieve a unit or list of units from a node.
+//! - **`follow`** / **`unfollow`** — manage who you follow.
+//! - **`following`** / **`followers`** — list an agent's follow graph.
+//! - **`inbox`** — read (and optionally keep polling) your inbox.
+//! - **`mirror`** — copy a node's public graph into a local SQLite file.
+//! - **`watch`** — stream new public units from a node as they arrive.
+//! - **`subgraph`** — fetch the connected subgraph around a unit.
+//! - **`browse`** — explore a graph in a full-screen terminal browser (also
+//!   works on a local file or mirror with `--from`).
+//! - **`profile`** — manage named node/key profiles in the config file.
+//! - **`bundle create`** — export a unit's justification as a signed evidence
+//!   bundle (`bundle verify` checks one offline).
 //!
-//! All local subcommands read JSON from a file path or from stdin (`-`).
+//! All local subcommands read JSON from a file path or from stdin (`-`);
+//! `validate`, `render`, and `verify` also accept a mirror database, and
+//! `fetch --from` filters a JSON file or mirror instead of querying a node.
 //! Network subcommands authenticate with Ed25519 HTTP Signatures; use
 //! `sweft keygen` to create your identity key before first use.
 
@@ -22,11 +46,32 @@ use std::fs;
 use std::io::{self, Read};
 use std::path::PathBuf;
 use std::process;
-
-use clap::{Parser, Subcommand};
-use ed25519_dalek::{Signer, SigningKey};
+use std::sync::OnceLock;
+
+mod batch;
+mod browse;
+mod bundle;
+mod compose;
+mod config;
+mod doctor;
+mod keyfile;
+mod mirror;
+mod outbox;
+mod watch;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use config::OutputFormat;
+use ed25519_dalek::SigningKey;
 use rand::rngs::OsRng;
-use semanticweft::{validate_unit, Graph, Reference, RelType, SemanticUnit, Source, UnitType};
+use semanticweft::{
+    proof_author, proof_signer, sign_unit, sign_unit_delegated, validate_unit, verify_proof,
+    Delegation, DelegationScope, Graph, ProofError, Reference, RelType, SemanticUnit, Source,
+    UnitType, Visibility,
+};
+use futures_util::{StreamExt, TryStreamExt};
+use semanticweft_agent_core::{sign_succession, AgentIdentity};
+use semanticweft_client::{Application, Client};
+use semanticweft_node_api::{AgentProfile, AgentStatus, ListQuery};
 
 /// sweft — SemanticWeft protocol CLI
 ///
@@ -34,6 +79,12 @@ use semanticweft::{validate_unit, Graph, Reference, RelType, SemanticUnit, Sourc
 #[derive(Parser)]
 #[command(name = "sweft", version, about, long_about = None)]
 struct Cli {
+    /// Named profile from ~/.config/sweft/config.toml supplying defaults for
+    /// --node, --key, visibility, and output format.
+    /// Can also be set via the SWEFT_PROFILE environment variable.
+    #[arg(long, global = true, env = "SWEFT_PROFILE", value_name = "NAME")]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -56,10 +107,17 @@ enum Command {
     /// Reads a JSON file containing either a single unit object or an array of
     /// unit objects and prints a structured text summary.
     ///
+    /// With --threads, replies are nested under the units they reference so
+    /// that challenges, refinements, and answers read as a conversation.
+    ///
     /// Pass `-` as FILE to read from stdin.
     Render {
         /// Path to a JSON file, or `-` for stdin.
         file: PathBuf,
+
+        /// Render reply chains as nested threads instead of grouping by type.
+        #[arg(long)]
+        threads: bool,
     },
 
     /// Create a new Semantic Unit and print it as JSON.
@@ -103,6 +161,70 @@ enum Command {
         /// Repeat for multiple references: --ref <uuid>:<rel> --ref <uuid>:<rel>
         #[arg(long = "ref", value_name = "UUID:REL")]
         references: Vec<String>,
+
+        /// Visibility: public | network | limited. Omitted means public.
+        /// Can also be set via the SWEFT_VISIBILITY environment variable.
+        #[arg(long, env = "SWEFT_VISIBILITY", value_name = "VISIBILITY")]
+        visibility: Option<Visibility>,
+
+        /// A DID allowed to see a `limited` unit.
+        /// Repeat for multiple recipients: --audience <did> --audience <did>
+        #[arg(long = "audience", value_name = "DID")]
+        audience: Vec<String>,
+    },
+
+    /// Build a graph from a YAML or TOML outline.
+    ///
+    /// Units are written with local labels and reference each other by
+    /// label. Each unit gets a UUIDv7 id in dependency order, so referenced
+    /// units are always older than the units citing them; every unit is
+    /// validated before anything is printed. A `.toml` extension selects
+    /// TOML, anything else (including `-` for stdin) is read as YAML.
+    ///
+    /// With --sign, every unit is signed with --key. Units must be authored
+    /// by the key's DID or, with --delegation, by the issuer of a token that
+    /// covers them. With --submit, the graph is written to --out and
+    /// submitted like `submit --batch`; if the run is interrupted, resume it
+    /// with `sweft submit --batch <OUT>` rather than composing again, which
+    /// would assign new ids.
+    ///
+    /// Examples:
+    ///   sweft compose plan.yaml > graph.json
+    ///   sweft compose plan.toml --sign --out graph.json
+    ///   sweft compose plan.yaml --sign --submit --out graph.json --node https://node.example.com
+    ///   sweft compose plan.yaml --sign --key worker.key --delegation worker.token
+    Compose {
+        /// Path to the plan file, or - to read YAML from stdin.
+        plan: PathBuf,
+
+        /// Write the graph (a JSON array) here instead of stdout.
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+
+        /// Sign every unit with --key.
+        #[arg(long)]
+        sign: bool,
+
+        /// Path to the Ed25519 key file. When signing or submitting, its DID
+        /// is the author of units the plan does not attribute.
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+
+        /// Sign as a delegate: --key is the delegate key and this is the
+        /// token the author issued to it (see `sweft key delegate`). The
+        /// issuer becomes the author of units the plan does not attribute.
+        #[arg(long, requires = "sign", value_name = "PATH")]
+        delegation: Option<PathBuf>,
+
+        /// Submit the graph to --node after writing it to --out.
+        #[arg(long, requires_all = ["out", "node"])]
+        submit: bool,
+
+        /// Base URL of the node to submit to.
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: Option<String>,
     },
 
     /// Generate an Ed25519 identity key pair.
@@ -117,11 +239,84 @@ enum Command {
     /// Examples:
     ///   sweft keygen
     ///   sweft keygen --out ~/.config/sweft/work.key
+    ///   sweft keygen --encrypt
+    ///
+    /// With --encrypt, the seed is sealed under a passphrase (Argon2id +
+    /// XChaCha20-Poly1305). The passphrase is read from SWEFT_PASSPHRASE or
+    /// prompted for, both here and whenever a command needs the key.
     Keygen {
         /// Where to write the key file (default: ~/.config/sweft/identity.key).
         /// Can also be set via the SWEFT_KEY environment variable.
         #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
         out: Option<PathBuf>,
+
+        /// Encrypt the key file with a passphrase.
+        #[arg(long)]
+        encrypt: bool,
+    },
+
+    /// Manage identity key files.
+    ///
+    /// Examples:
+    ///   sweft key rekey                       # change passphrase, or encrypt a plaintext key
+    ///   sweft key export > backup.hex         # print the raw seed
+    ///   sweft key import backup.hex --encrypt # restore it as an encrypted key file
+    ///   sweft key rotate --new next.key --node https://node.example.com
+    Key {
+        #[command(subcommand)]
+        action: KeyCommand,
+    },
+
+    /// Sign one or more Semantic Units with your identity key.
+    ///
+    /// Reads a single unit or an array of units, validates them, attaches a
+    /// proof made with the key file, and prints the signed JSON in the same
+    /// shape it was read. Units that already carry a proof are rejected
+    /// unless --replace is given.
+    ///
+    /// Pass `-` as FILE to read from stdin.
+    ///
+    /// Examples:
+    ///   sweft sign unit.json > signed.json
+    ///   sweft new -t assertion -c "..." -a did:key:z6Mk... | sweft sign - | \
+    ///     sweft submit --node https://node.example.com -
+    Sign {
+        /// Path to a JSON file, or `-` for stdin.
+        file: PathBuf,
+
+        /// Path to the Ed25519 key file.
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+
+        /// Discard any existing proof and sign again.
+        #[arg(long)]
+        replace: bool,
+
+        /// Sign as a delegate: --key is the delegate key and this is the
+        /// token the author issued to it (see `sweft key delegate`).
+        #[arg(long, value_name = "PATH")]
+        delegation: Option<PathBuf>,
+    },
+
+    /// Verify the proofs on one or more Semantic Units.
+    ///
+    /// Prints one line per unit: its id, proof status (`valid`, `invalid`,
+    /// or `unsigned`), the signer DID, and whether the signer is the unit's
+    /// author (`author`), a key the author delegated to (`delegated`), or
+    /// neither (`not-author`). Fields are separated by tabs.
+    ///
+    /// Exit status:
+    ///   0  every unit carries a valid proof made by or for its author
+    ///   1  at least one proof is invalid
+    ///   2  the input could not be read or parsed
+    ///   3  no proof is invalid, but some units are unsigned or signed by
+    ///      someone other than their author
+    ///
+    /// Pass `-` as FILE to read from stdin.
+    Verify {
+        /// Path to a JSON file, or `-` for stdin.
+        file: PathBuf,
     },
 
     /// Register an agent profile on a SemanticWeft node.
@@ -155,6 +350,95 @@ enum Command {
         key: Option<PathBuf>,
     },
 
+    /// Apply for membership of a node without operator involvement.
+    ///
+    /// Sends a self-service application (ADR-0013) signed with your identity
+    /// key. Applicants are admitted as probationary and graduate to full
+    /// membership after enough contributions; see `sweft status`.
+    ///
+    /// Examples:
+    ///   sweft apply --node https://node.example.com
+    ///   sweft apply --node https://node.example.com --sponsor did:key:z6Mk...
+    Apply {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
+
+        /// DID of a full member vouching for you.
+        #[arg(long, value_name = "DID")]
+        sponsor: Option<String>,
+
+        /// The inbox URL for this agent (default: the node's inbox for your DID).
+        #[arg(long, value_name = "URL")]
+        inbox_url: Option<String>,
+
+        /// Optional display name.
+        #[arg(long, value_name = "NAME")]
+        display_name: Option<String>,
+
+        /// Path to the Ed25519 key file (default: ~/.config/sweft/identity.key).
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+
+        /// Print the admission as text or as the node's JSON profile.
+        /// Can also be set via the SWEFT_FORMAT environment variable.
+        #[arg(long, value_enum, env = "SWEFT_FORMAT", default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+
+    /// Show an agent's membership status on a node.
+    ///
+    /// Reports whether the agent is a full or probationary member, its
+    /// contribution count, and how many more contributions it needs to
+    /// reach the node's probation threshold.
+    ///
+    /// Examples:
+    ///   sweft status --node https://node.example.com
+    ///   sweft status --node https://node.example.com did:key:z6Mk...
+    Status {
+        /// DID to look up (default: the DID of your key).
+        did: Option<String>,
+
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
+
+        /// Path to the Ed25519 key file (default: ~/.config/sweft/identity.key).
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+
+        /// Print the status as text or JSON.
+        /// Can also be set via the SWEFT_FORMAT environment variable.
+        #[arg(long, value_enum, env = "SWEFT_FORMAT", default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+
+    /// Diagnose problems talking to a node.
+    ///
+    /// Checks the discovery document, the advertised api_base and
+    /// capabilities, clock skew against the node, a signed request with your
+    /// key, and WebFinger resolution of your agent address. Prints a
+    /// checklist with hints and exits 1 if any check fails.
+    ///
+    /// Examples:
+    ///   sweft doctor --node https://node.example.com
+    Doctor {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
+
+        /// Path to the Ed25519 key file (default: ~/.config/sweft/identity.key).
+        /// Without a key file the signed-request and WebFinger checks are skipped.
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+    },
+
     /// Submit a Semantic Unit to a node.
     ///
     /// Reads a unit from a JSON file (or stdin with `-`) and sends it to the
@@ -166,6 +450,13 @@ enum Command {
     ///   sweft submit --node https://node.example.com unit.json
     ///   sweft new -t assertion -c "test" -a did:key:z6Mk... | \
     ///     sweft submit --node https://node.example.com -
+    ///   sweft submit --node https://node.example.com --batch graph.ndjson --key me.key
+    ///
+    /// With --batch, every unit in an NDJSON file is submitted in dependency
+    /// order (referenced units first), concurrently, backing off on 429.
+    /// With --key, unsigned units are signed before submission. Accepted
+    /// units are recorded in a journal so that a re-run resumes where the
+    /// previous one stopped. Exits 1 if any unit fails or is skipped.
     Submit {
         /// Base URL of the node (e.g. https://node.example.com).
         /// Can also be set via the SWEFT_NODE environment variable.
@@ -173,12 +464,58 @@ enum Command {
         node: String,
 
         /// Path to a JSON file containing the unit, or `-` for stdin.
-        file: PathBuf,
+        #[arg(required_unless_present = "batch", conflicts_with = "batch")]
+        file: Option<PathBuf>,
+
+        /// Submit every unit in this NDJSON file (or JSON array) as a graph.
+        #[arg(long, value_name = "FILE")]
+        batch: Option<PathBuf>,
 
         /// Path to the Ed25519 key file (required for non-public units).
         /// Can also be set via the SWEFT_KEY environment variable.
         #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
         key: Option<PathBuf>,
+
+        /// Authenticate as a delegate: --key is the delegate key and this is
+        /// the token the author issued to it.
+        #[arg(long, requires = "key", conflicts_with = "batch", value_name = "PATH")]
+        delegation: Option<PathBuf>,
+
+        /// Number of units submitted in parallel.
+        #[arg(long, default_value_t = 4, requires = "batch", value_name = "N")]
+        concurrency: usize,
+
+        /// Maximum requests per minute, to stay under the node's rate limit.
+        #[arg(long, requires = "batch", value_name = "N")]
+        rate: Option<u32>,
+
+        /// Resume journal (default: the batch file's path plus `.journal`).
+        #[arg(long, requires = "batch", value_name = "PATH")]
+        journal: Option<PathBuf>,
+    },
+
+    /// Queue units locally and deliver them to a node when it is reachable.
+    ///
+    /// The outbox survives restarts and dropped connections: units wait in
+    /// a JSON file until a node accepts them. A unit is submitted only after
+    /// the earlier-queued units it references have been delivered; transient
+    /// failures (unreachable node, 408, 429, 5xx) are retried with growing
+    /// back-off, and a unit the node already holds with identical content
+    /// counts as delivered. Other 4xx responses fail the unit and block the
+    /// units that depend on it until `sweft outbox retry`.
+    ///
+    /// Examples:
+    ///   sweft outbox add --key me.key reply.json
+    ///   sweft outbox flush --node https://node.example.com --wait
+    ///   sweft outbox list
+    Outbox {
+        /// Outbox file (default: ~/.config/sweft/outbox.json).
+        /// Can also be set via the SWEFT_OUTBOX environment variable.
+        #[arg(long, env = "SWEFT_OUTBOX", value_name = "PATH", global = true)]
+        outbox: Option<PathBuf>,
+
+        #[command(subcommand)]
+        action: OutboxCommand,
     },
 
     /// Fetch a unit or a list of units from a node.
@@ -187,15 +524,23 @@ enum Command {
     /// Without an ID, lists units with optional filters (GET /v1/units).
     /// Provide --key to authenticate and receive network-visibility units.
     ///
+    /// With --from, the same lookup and filters are applied to a local JSON
+    /// file or mirror database instead of a node.
+    ///
     /// Examples:
     ///   sweft fetch --node https://node.example.com <uuid>
     ///   sweft fetch --node https://node.example.com --author did:key:z6Mk...
     ///   sweft fetch --node https://node.example.com --type assertion --limit 10
+    ///   sweft fetch --from graph.db --type question --since 2026-01-01T00:00:00Z
     Fetch {
         /// Base URL of the node (e.g. https://node.example.com).
         /// Can also be set via the SWEFT_NODE environment variable.
-        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
-        node: String,
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL", required_unless_present = "from")]
+        node: Option<String>,
+
+        /// Read from a local JSON file or mirror database instead of a node.
+        #[arg(long, value_name = "PATH")]
+        from: Option<PathBuf>,
 
         /// Unit ID to fetch. If omitted, lists units with optional filters.
         id: Option<String>,
@@ -221,138 +566,910 @@ enum Command {
         #[arg(long, value_name = "UUID")]
         after: Option<String>,
 
-        /// Maximum number of units to return (1–500, default 50).
+        /// Maximum number of units to return (1–500, default 50; no upper
+        /// bound with --from).
         #[arg(long, value_name = "N")]
         limit: Option<u32>,
+
+        /// Print the response JSON as returned, or render the units as text.
+        /// Can also be set via the SWEFT_FORMAT environment variable.
+        #[arg(long, value_enum, env = "SWEFT_FORMAT", default_value_t = OutputFormat::Json)]
+        format: OutputFormat,
     },
-}
 
-fn main() {
-    let cli = Cli::parse();
+    /// Copy a node's public graph into a local SQLite mirror.
+    ///
+    /// Pulls every public unit from the node's sync endpoint. The sync
+    /// cursor is stored in the mirror, so re-running the command fetches
+    /// only units published since the last run, and an interrupted mirror
+    /// resumes where it stopped.
+    ///
+    /// The mirror can then be read by `validate`, `render`, `verify`, and
+    /// `fetch --from`.
+    ///
+    /// Example:
+    ///   sweft mirror --node https://node.example.com --into graph.db
+    Mirror {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
 
-    match cli.command {
-        Command::Validate { file } => {
-            let json = read_input(&file);
-            let units = parse_units(&json);
-            let mut all_valid = true;
-            for (i, unit) in units.iter().enumerate() {
-                if let Err(e) = validate_unit(unit) {
-                    if units.len() == 1 {
-                        eprintln!("error: {}", e);
-                    } else {
-                        eprintln!("error in unit {}: {}", i, e);
-                    }
-                    all_valid = false;
-                }
-            }
-            if all_valid {
-                if units.len() == 1 {
-                    println!("valid");
-                } else {
-                    println!("all {} units valid", units.len());
-                }
-            } else {
-                process::exit(1);
-            }
-        }
+        /// Path of the mirror database (created if missing).
+        #[arg(long, value_name = "PATH")]
+        into: PathBuf,
+    },
 
-        Command::Render { file } => {
-            let json = read_input(&file);
-            let units = parse_units(&json);
-            // A single unit is rendered in full detail; multiple units use the
-            // grouped graph summary view.
-            if units.len() == 1 {
-                print!("{}", semanticweft::render::render_unit(&units[0]));
-            } else {
-                let graph = Graph::from_units(units);
-                print!("{}", semanticweft::render::render_graph(&graph));
-            }
-        }
+    /// Follow another agent.
+    ///
+    /// Units the target publishes with `network` visibility are then
+    /// delivered to your inbox. You must be registered on the node.
+    ///
+    /// Example:
+    ///   sweft follow --node https://node.example.com did:key:z6Mk...
+    Follow {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
 
-        Command::New {
-            unit_type,
-            content,
-            author,
-            confidence,
-            assumptions,
-            source,
-            references,
-        } => {
-            // Parse each --ref argument from "<uuid>:<rel>" into a Reference.
-            let parsed_refs = if references.is_empty() {
-                None
-            } else {
-                let mut out = Vec::with_capacity(references.len());
-                for raw in &references {
-                    let (id_str, rel_str) = raw.split_once(':').unwrap_or_else(|| {
-                        fatal(&format!(
-                            "invalid --ref {:?}: expected format <uuid>:<rel>",
-                            raw
-                        ))
-                    });
-                    let rel = rel_str.parse::<RelType>().unwrap_or_else(|e| fatal(&e));
-                    out.push(Reference {
-                        id: id_str.to_string(),
-                        rel,
-                    });
-                }
-                Some(out)
-            };
+        /// DID of the agent to follow.
+        target: String,
 
-            let mut unit = SemanticUnit::new(unit_type, content, author);
-            unit.confidence = confidence;
-            // An empty --assumption list means the field is absent, not present-but-empty.
-            unit.assumptions = if assumptions.is_empty() {
-                None
-            } else {
-                Some(assumptions)
-            };
-            unit.source = source.map(Source::Uri);
-            unit.references = parsed_refs;
+        /// Path to the Ed25519 key file.
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+    },
 
-            // Validate before printing so the user gets a clear error rather than
-            // silently producing an invalid unit.
-            if let Err(e) = validate_unit(&unit) {
-                fatal(&format!("unit is invalid: {}", e));
-            }
+    /// Stop following an agent.
+    ///
+    /// Succeeds even if you were not following the target.
+    Unfollow {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
 
-            println!("{}", serde_json::to_string_pretty(&unit).unwrap());
-        }
+        /// DID of the agent to unfollow.
+        target: String,
 
-        Command::Keygen { out } => {
-            let path = out.unwrap_or_else(default_key_path);
+        /// Path to the Ed25519 key file.
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+    },
 
-            // Create parent directories if needed.
-            if let Some(parent) = path.parent() {
-                fs::create_dir_all(parent).unwrap_or_else(|e| {
-                    fatal(&format!("failed to create {}: {e}", parent.display()))
-                });
-            }
+    /// List the agents an agent follows, one DID per line.
+    ///
+    /// Defaults to your own DID (derived from the key file).
+    Following {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
 
-            let signing_key = SigningKey::generate(&mut OsRng);
-            let hex = hex_encode(&signing_key.to_bytes());
+        /// Agent whose follows to list. Defaults to your own DID.
+        did: Option<String>,
 
-            fs::write(&path, &hex).unwrap_or_else(|e| {
-                fatal(&format!("failed to write key file {}: {e}", path.display()))
-            });
+        /// Path to the Ed25519 key file.
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+    },
 
-            // Set restrictive permissions on Unix so the private key is not
-            // world-readable.
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                fs::set_permissions(&path, fs::Permissions::from_mode(0o600))
-                    .unwrap_or_else(|e| {
-                        eprintln!("sweft: warning: could not set key file permissions: {e}")
-                    });
-            }
+    /// List the agents that follow an agent, one DID per line.
+    ///
+    /// Defaults to your own DID (derived from the key file).
+    Followers {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
 
-            let (did, pubkey_multibase) = derive_did_and_pubkey(&signing_key);
-            println!("Key file : {}", path.display());
-            println!("DID      : {did}");
+        /// Agent whose followers to list. Defaults to your own DID.
+        did: Option<String>,
+
+        /// Path to the Ed25519 key file.
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+    },
+
+    /// Read your inbox.
+    ///
+    /// Fetches every page of units delivered to you (following
+    /// `next_cursor`) and renders them. With --follow, keeps polling for
+    /// new items until interrupted.
+    ///
+    /// Examples:
+    ///   sweft inbox --node https://node.example.com
+    ///   sweft inbox --node https://node.example.com --follow --format json
+    Inbox {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
+
+        /// Path to the Ed25519 key file.
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+
+        /// Start after this unit id instead of at the beginning.
+        #[arg(long, value_name = "UUID")]
+        after: Option<String>,
+
+        /// Page size requested from the node (1–100, default 20).
+        #[arg(long, value_name = "N")]
+        limit: Option<u32>,
+
+        /// Keep polling for new items after the inbox is drained.
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Seconds between polls with --follow.
+        #[arg(long, value_name = "SECS", default_value_t = 10)]
+        interval: u64,
+
+        /// Render units as text, or print each as a line of JSON.
+        /// Can also be set via the SWEFT_FORMAT environment variable.
+        #[arg(long, value_enum, env = "SWEFT_FORMAT", default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+
+    /// Stream public units from a node as they are published.
+    ///
+    /// Subscribes to the node's live sync stream and prints each unit as it
+    /// arrives. The id of the last unit seen is saved to a cursor file, so
+    /// stopping and restarting `watch` resumes without gaps. With no saved
+    /// cursor, the node's full public history is replayed first.
+    ///
+    /// Examples:
+    ///   sweft watch --node https://node.example.com
+    ///   sweft watch --node https://node.example.com --type question --format json
+    Watch {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
+
+        /// Comma-separated unit types to print (e.g. assertion,question).
+        #[arg(long = "type", value_name = "TYPES")]
+        unit_type: Option<String>,
+
+        /// Print only units by this author DID.
+        #[arg(long, value_name = "DID")]
+        author: Option<String>,
+
+        /// Start after this unit id, ignoring any saved cursor.
+        #[arg(long, value_name = "UUID")]
+        after: Option<String>,
+
+        /// Where to persist the cursor.
+        /// Defaults to `~/.config/sweft/watch/<host>.cursor`.
+        #[arg(long, value_name = "PATH")]
+        cursor_file: Option<PathBuf>,
+
+        /// Render units as text, or print each as a line of JSON.
+        /// Can also be set via the SWEFT_FORMAT environment variable.
+        #[arg(long, value_enum, env = "SWEFT_FORMAT", default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+
+    /// Fetch the connected subgraph around a unit.
+    ///
+    /// Retrieves every unit reachable from the given unit through references
+    /// in either direction, up to --depth hops (GET /v1/units/{id}/subgraph).
+    /// Provide --key to authenticate, so that network and limited units you
+    /// are entitled to see are included.
+    ///
+    /// Examples:
+    ///   sweft subgraph --node https://node.example.com <uuid> --depth 3
+    ///   sweft subgraph --node https://node.example.com <uuid> --format dot | dot -Tsvg > g.svg
+    Subgraph {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
+
+        /// ID of the unit at the centre of the subgraph.
+        id: String,
+
+        /// Maximum traversal depth in each direction (node default: 10).
+        #[arg(long, value_name = "N")]
+        depth: Option<u32>,
+
+        /// Path to the Ed25519 key file (enables network- and limited-unit access).
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+
+        /// Output format: raw units as a JSON array, threaded text, or
+        /// Graphviz DOT.
+        /// Can also be set via the SWEFT_GRAPH_FORMAT environment variable.
+        #[arg(long, value_enum, env = "SWEFT_GRAPH_FORMAT", default_value_t = GraphFormat::Json)]
+        format: GraphFormat,
+    },
+
+    /// Explore a graph in a full-screen terminal browser.
+    ///
+    /// Shows the unit list alongside the selected unit, its outgoing
+    /// references, and the units that reference it. Follow references with
+    /// Enter and retrace with Backspace; `c` selects the next challenge, `a`
+    /// and `s` expand assumptions and source, `t` cycles the type filter, and
+    /// `/` filters by author. Each unit shows whether its proof verifies.
+    ///
+    /// Browsing a node loads the subgraph around <ID> (or, without an id,
+    /// the first --limit units the node lists) and fetches further units as
+    /// references to them are followed.
+    ///
+    /// Examples:
+    ///   sweft browse --from graph.db
+    ///   sweft browse --node https://node.example.com <uuid> --depth 3
+    Browse {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL", required_unless_present = "from")]
+        node: Option<String>,
+
+        /// Browse a local JSON file or mirror database instead of a node.
+        #[arg(long, value_name = "PATH")]
+        from: Option<PathBuf>,
+
+        /// Unit to start at.
+        id: Option<String>,
+
+        /// Traversal depth of the initial subgraph around <ID> (node default: 10).
+        #[arg(long, value_name = "N", requires = "id", conflicts_with = "from")]
+        depth: Option<u32>,
+
+        /// Maximum number of units to load when no <ID> is given.
+        #[arg(long, value_name = "N", default_value_t = 500, conflicts_with = "from")]
+        limit: u32,
+
+        /// Path to the Ed25519 key file (enables network- and limited-unit access).
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+
+        /// Initial type filter (e.g. assertion).
+        #[arg(long = "type", value_name = "TYPE")]
+        unit_type: Option<String>,
+
+        /// Initial author filter (substring of the author DID).
+        #[arg(long, value_name = "DID")]
+        author: Option<String>,
+    },
+
+    /// Manage named profiles in ~/.config/sweft/config.toml.
+    ///
+    /// A profile supplies defaults for --node, --key, the visibility of new
+    /// units, and the output format. Select one per command with --profile
+    /// (or SWEFT_PROFILE), or make it the default with `sweft profile use`.
+    /// Explicit flags and environment variables always take precedence.
+    ///
+    /// Examples:
+    ///   sweft profile add work --node https://node.example.com \
+    ///     --key ~/.config/sweft/work.key --visibility network
+    ///   sweft profile use work
+    ///   sweft --profile lab fetch --type question
+    Profile {
+        #[command(subcommand)]
+        action: ProfileCommand,
+    },
+
+    /// Create or verify a signed evidence bundle.
+    ///
+    /// A bundle packages a unit together with everything it rests on — the
+    /// units it transitively references, their proofs, and the authors'
+    /// profiles — under a manifest signed by the exporting key, so a third
+    /// party can audit the justification without contacting any node.
+    ///
+    /// Examples:
+    ///   sweft bundle create --node https://node.example.com <uuid>
+    ///   sweft bundle verify <uuid>.bundle.tar
+    Bundle {
+        #[command(subcommand)]
+        action: BundleCommand,
+    },
+}
+
+/// Output formats for commands that print a graph.
+#[derive(Clone, Copy, ValueEnum)]
+enum GraphFormat {
+    /// The units as a JSON array, readable by `render`, `validate`, etc.
+    Json,
+    /// Threaded plain text, as `sweft render --threads`.
+    Text,
+    /// Graphviz DOT.
+    Dot,
+}
+
+#[derive(Subcommand)]
+enum KeyCommand {
+    /// Print the raw hex seed of a key file, decrypting it if necessary.
+    ///
+    /// The output is an unencrypted private key; handle it accordingly.
+    Export {
+        /// Key file to export (default: ~/.config/sweft/identity.key).
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+
+        /// Write the seed to this file (mode 0600) instead of stdout.
+        #[arg(short, long, value_name = "PATH")]
+        out: Option<PathBuf>,
+    },
+
+    /// Install a key from a hex seed or another key file.
+    Import {
+        /// Hex seed or key file to import, or `-` for stdin.
+        file: PathBuf,
+
+        /// Where to write the key file (default: ~/.config/sweft/identity.key).
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        out: Option<PathBuf>,
+
+        /// Encrypt the installed key file with a passphrase.
+        #[arg(long)]
+        encrypt: bool,
+
+        /// Overwrite an existing key file.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Re-encrypt a key file under a new passphrase.
+    ///
+    /// A plaintext key file is encrypted in place, which is how existing
+    /// keys are migrated. The current passphrase is read from
+    /// SWEFT_PASSPHRASE or prompted for; the new one from
+    /// SWEFT_NEW_PASSPHRASE or prompted for.
+    Rekey {
+        /// Key file to re-encrypt (default: ~/.config/sweft/identity.key).
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+    },
+
+    /// Retire a key in favour of a new one.
+    ///
+    /// Writes a succession statement naming the new key's DID, signed by the
+    /// old key and countersigned by the new one. A node that accepts it moves
+    /// your profile, reputation and follows to the new DID and stops
+    /// accepting the old key; units signed by the old key stay attributed to
+    /// you. The --new key file is generated if it does not exist.
+    ///
+    /// The statement is printed (or written to --out) so it can be posted to
+    /// every node you are registered on; --node posts it to one directly.
+    ///
+    /// Examples:
+    ///   sweft key rotate --new next.key --node https://node.example.com
+    ///   sweft key rotate --new next.key --reason "laptop stolen" --out rotation.json
+    Rotate {
+        /// Key file being retired (default: ~/.config/sweft/identity.key).
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+
+        /// Key file to rotate to; generated if missing.
+        #[arg(long, value_name = "PATH")]
+        new: PathBuf,
+
+        /// Encrypt the generated key file with a passphrase.
+        #[arg(long)]
+        encrypt: bool,
+
+        /// Why the key is being retired, recorded in the statement.
+        #[arg(long, value_name = "TEXT")]
+        reason: Option<String>,
+
+        /// Write the statement to this file instead of stdout.
+        #[arg(short, long, value_name = "PATH")]
+        out: Option<PathBuf>,
+
+        /// Post the statement to this node.
+        #[arg(long, value_name = "URL")]
+        node: Option<String>,
+    },
+
+    /// Let another key act for this one, e.g. a short-lived worker.
+    ///
+    /// Writes a delegation token, signed by --key, that lets the --to key
+    /// sign units and requests on your behalf until it expires. Nodes
+    /// attribute that work to you. The --to key file is generated if it does
+    /// not exist; hand it and the token to the worker, which passes them to
+    /// `sweft sign`/`sweft submit` as --key and --delegation.
+    ///
+    /// Each --type, --visibility and --endpoint narrows what the token
+    /// allows; without any of one kind, that dimension is unrestricted.
+    /// Endpoints are "METHOD PATH", where {did} stands for your DID, `*` as
+    /// the method matches any method, and a trailing `*` matches any suffix.
+    ///
+    /// Examples:
+    ///   sweft key delegate --to worker.key --type assertion \
+    ///     --endpoint "POST /v1/units" --ttl 30 --out worker-token.json
+    Delegate {
+        /// Key file to delegate from (default: ~/.config/sweft/identity.key).
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+
+        /// Delegate key file; generated if missing.
+        #[arg(long, value_name = "PATH")]
+        to: PathBuf,
+
+        /// Encrypt the generated key file with a passphrase.
+        #[arg(long)]
+        encrypt: bool,
+
+        /// A unit type the delegate may sign. Repeatable.
+        #[arg(long = "type", value_name = "TYPE")]
+        unit_types: Vec<UnitType>,
+
+        /// A visibility the delegate may use. Repeatable.
+        #[arg(long = "visibility", value_name = "VISIBILITY")]
+        visibilities: Vec<Visibility>,
+
+        /// A node endpoint the delegate may call, e.g. "POST /v1/units".
+        /// Repeatable; without any, the delegate can sign units but not
+        /// call the node.
+        #[arg(long = "endpoint", value_name = "METHOD PATH")]
+        endpoints: Vec<String>,
+
+        /// How long the token is valid, in minutes.
+        #[arg(long, default_value_t = 60, value_name = "MINUTES")]
+        ttl: u64,
+
+        /// Write the token to this file instead of stdout.
+        #[arg(short, long, value_name = "PATH")]
+        out: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileCommand {
+    /// Create a profile, or update the given fields of an existing one.
+    ///
+    /// The first profile added becomes the default.
+    Add {
+        /// Profile name.
+        name: String,
+
+        /// Base URL of the node.
+        #[arg(long, value_name = "URL")]
+        node: Option<String>,
+
+        /// Path to the Ed25519 key file.
+        #[arg(long, value_name = "PATH")]
+        key: Option<String>,
+
+        /// Default visibility for `sweft new`: public | network | limited.
+        #[arg(long, value_name = "VISIBILITY")]
+        visibility: Option<Visibility>,
+
+        /// Default output format.
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+
+        /// Also make this the default profile.
+        #[arg(long = "use")]
+        make_current: bool,
+    },
+
+    /// List profiles; the default is marked with `*`.
+    List,
+
+    /// Make a profile the default.
+    Use {
+        /// Profile name.
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum OutboxCommand {
+    /// Validate units and add them to the outbox.
+    ///
+    /// With --key, unsigned units are signed first, so what is queued is
+    /// exactly what will be sent.
+    Add {
+        /// JSON file with a unit or an array of units, or `-` for stdin.
+        file: PathBuf,
+
+        /// Path to the Ed25519 key file used to sign unsigned units.
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+    },
+
+    /// Submit every unit that is due.
+    ///
+    /// Exits 1 if any unit has failed permanently or is blocked.
+    Flush {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
+
+        /// Key file used to sign the requests (required for non-public units).
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+
+        /// Keep running, sleeping between retries, until no unit is queued.
+        #[arg(long)]
+        wait: bool,
+    },
+
+    /// List queued, delivered, failed, and blocked units.
+    List,
+
+    /// Requeue failed and blocked units.
+    Retry,
+
+    /// Remove delivered units from the outbox.
+    Prune,
+}
+
+#[derive(Subcommand)]
+enum BundleCommand {
+    /// Export a unit's justification subgraph as a signed bundle.
+    ///
+    /// Fetches the unit's subgraph from the node, keeps the unit and every
+    /// unit it transitively references, and writes them to a tar archive
+    /// with a manifest of SHA-256 digests signed by your key.
+    Create {
+        /// Base URL of the node (e.g. https://node.example.com).
+        /// Can also be set via the SWEFT_NODE environment variable.
+        #[arg(long, env = "SWEFT_NODE", value_name = "URL")]
+        node: String,
+
+        /// ID of the unit whose justification to export.
+        id: String,
+
+        /// Path to the Ed25519 key file that signs the manifest.
+        /// Can also be set via the SWEFT_KEY environment variable.
+        #[arg(long, env = "SWEFT_KEY", value_name = "PATH")]
+        key: Option<PathBuf>,
+
+        /// Output path. Defaults to `<id>.bundle.tar`.
+        #[arg(short, long, value_name = "PATH")]
+        out: Option<PathBuf>,
+
+        /// Maximum subgraph traversal depth requested from the node.
+        #[arg(long, value_name = "N")]
+        depth: Option<u32>,
+    },
+
+    /// Verify a bundle offline.
+    ///
+    /// Checks the manifest signature, every file digest, and each unit's
+    /// validity and proof. Exits 0 if the bundle verifies, 1 if it does not.
+    Verify {
+        /// Path to the bundle archive.
+        file: PathBuf,
+
+        /// Treat units without a proof as a verification failure.
+        #[arg(long)]
+        require_proofs: bool,
+
+        /// Accept units whose proof verifies but was made by a key other
+        /// than the author's. By default such units fail the audit.
+        #[arg(long)]
+        allow_foreign_signers: bool,
+    },
+}
+
+fn main() {
+    // Profiles feed the SWEFT_* variables that clap reads as defaults, so the
+    // active one must be applied before parsing.
+    let args: Vec<String> = std::env::args().collect();
+    if let Err(e) = config::apply_profile(config::profile_arg(&args)) {
+        fatal(&e);
+    }
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Validate { file } => {
+            let units = load_units(&file);
+            let mut all_valid = true;
+            for (i, unit) in units.iter().enumerate() {
+                if let Err(e) = validate_unit(unit) {
+                    if units.len() == 1 {
+                        eprintln!("error: {}", e);
+                    } else {
+                        eprintln!("error in unit {}: {}", i, e);
+                    }
+                    all_valid = false;
+                }
+            }
+            if all_valid {
+                if units.len() == 1 {
+                    println!("valid");
+                } else {
+                    println!("all {} units valid", units.len());
+                }
+            } else {
+                process::exit(1);
+            }
+        }
+
+        Command::Render { file, threads } => {
+            let units = load_units(&file);
+            // A single unit is rendered in full detail; multiple units use the
+            // grouped graph summary view, or the threaded view on request.
+            if threads {
+                let graph = Graph::from_units(units);
+                print!("{}", semanticweft::render::render_threads(&graph));
+            } else if units.len() == 1 {
+                print!("{}", semanticweft::render::render_unit(&units[0]));
+            } else {
+                let graph = Graph::from_units(units);
+                print!("{}", semanticweft::render::render_graph(&graph));
+            }
+        }
+
+        Command::New {
+            unit_type,
+            content,
+            author,
+            confidence,
+            assumptions,
+            source,
+            references,
+            visibility,
+            audience,
+        } => {
+            // Parse each --ref argument from "<uuid>:<rel>" into a Reference.
+            let parsed_refs = if references.is_empty() {
+                None
+            } else {
+                let mut out = Vec::with_capacity(references.len());
+                for raw in &references {
+                    let (id_str, rel_str) = raw.split_once(':').unwrap_or_else(|| {
+                        fatal(&format!(
+                            "invalid --ref {:?}: expected format <uuid>:<rel>",
+                            raw
+                        ))
+                    });
+                    let rel = rel_str.parse::<RelType>().unwrap_or_else(|e| fatal(&e));
+                    out.push(Reference {
+                        id: id_str.to_string(),
+                        rel,
+                    });
+                }
+                Some(out)
+            };
+
+            let mut unit = SemanticUnit::new(unit_type, content, author);
+            unit.confidence = confidence;
+            // An empty --assumption list means the field is absent, not present-but-empty.
+            unit.assumptions = if assumptions.is_empty() {
+                None
+            } else {
+                Some(assumptions)
+            };
+            unit.source = source.map(Source::Uri);
+            unit.references = parsed_refs;
+            // Public is the default; leave the field absent rather than explicit.
+            unit.visibility = visibility.filter(|v| *v != Visibility::Public);
+            unit.audience = if audience.is_empty() {
+                None
+            } else {
+                Some(audience)
+            };
+
+            // Validate before printing so the user gets a clear error rather than
+            // silently producing an invalid unit.
+            if let Err(e) = validate_unit(&unit) {
+                fatal(&format!("unit is invalid: {}", e));
+            }
+
+            println!("{}", serde_json::to_string_pretty(&unit).unwrap());
+        }
+
+        Command::Compose {
+            plan,
+            out,
+            sign,
+            key,
+            delegation,
+            submit,
+            node,
+        } => {
+            let parsed = compose::parse(&plan, &read_input(&plan)).unwrap_or_else(|e| fatal(&e));
+            // Only load the key when it is used, so a profile's key does not
+            // prompt for a passphrase on a plain compose.
+            let signing_key = match key {
+                Some(p) if sign || submit => Some(load_key(Some(p))),
+                None if sign => Some(load_key(None)),
+                _ => None,
+            };
+            let did = signing_key.as_ref().map(|k| derive_did_and_pubkey(k).0);
+            let delegation = delegation.map(|p| load_delegation(&p));
+            if let (Some(token), Some(did)) = (&delegation, &did) {
+                if token.audience != *did {
+                    fatal(&format!(
+                        "the delegation was issued to {}, not the signing key {did}",
+                        token.audience
+                    ));
+                }
+            }
+            let default_author = delegation.as_ref().map(|t| &t.issuer).or(did.as_ref());
+            let mut units = compose::build(parsed, default_author.map(String::as_str))
+                .unwrap_or_else(|e| fatal(&e));
+
+            if sign {
+                let (key, did) = (signing_key.as_ref().unwrap(), did.as_deref().unwrap());
+                for (label, unit) in &mut units {
+                    // A proof for anyone else would be rejected on
+                    // verification, or worse, misattribute the unit.
+                    let signed = match &delegation {
+                        _ if unit.author == did => sign_unit(unit, key, did),
+                        Some(token) if unit.author == token.issuer => {
+                            if let Err(e) =
+                                token.verify_for_unit(did, unit, std::time::SystemTime::now())
+                            {
+                                fatal(&format!("the delegation does not cover unit {label:?}: {e}"));
+                            }
+                            sign_unit_delegated(unit, key, token.clone())
+                        }
+                        _ => fatal(&format!(
+                            "unit {label:?} is authored by {}, which the signing key {did} \
+                             cannot sign for",
+                            unit.author
+                        )),
+                    };
+                    if let Err(e) = signed {
+                        fatal(&format!("cannot sign unit {label:?}: {e}"));
+                    }
+                }
+            }
+
+            let graph: Vec<&SemanticUnit> = units.iter().map(|(_, u)| u).collect();
+            let json = serde_json::to_string_pretty(&graph).expect("serializable");
+            match out {
+                Some(ref out) => {
+                    fs::write(out, format!("{json}\n")).unwrap_or_else(|e| {
+                        fatal(&format!("failed to write {}: {e}", out.display()))
+                    });
+                    for (label, unit) in &units {
+                        eprintln!("{label}\t{}", unit.id);
+                    }
+                }
+                None => println!("{json}"),
+            }
+
+            if submit {
+                let passed = batch::run(batch::BatchOptions {
+                    node: node.expect("clap requires --node with --submit"),
+                    file: out.expect("clap requires --out with --submit"),
+                    key: signing_key,
+                    did,
+                    delegation,
+                    concurrency: 4,
+                    rate_per_minute: None,
+                    journal: None,
+                });
+                if !passed {
+                    process::exit(1);
+                }
+            }
+        }
+
+        Command::Keygen { out, encrypt } => {
+            let path = out.unwrap_or_else(default_key_path);
+            let passphrase = encrypt.then(|| {
+                keyfile::read_new_passphrase(keyfile::PASSPHRASE_ENV)
+                    .unwrap_or_else(|e| fatal(&e))
+            });
+
+            let signing_key = SigningKey::generate(&mut OsRng);
+            write_key_file(&path, &signing_key, passphrase.as_deref());
+
+            let (did, pubkey_multibase) = derive_did_and_pubkey(&signing_key);
+            println!("Key file : {}", path.display());
+            println!("DID      : {did}");
             println!("Public key: {pubkey_multibase}");
         }
 
+        Command::Sign {
+            file,
+            key,
+            replace,
+            delegation,
+        } => {
+            let json = read_input(&file);
+            let is_array = json.trim_start().starts_with('[');
+            let mut units = parse_units(&json);
+            let signing_key = load_key(key);
+            let delegation = delegation.map(|p| load_delegation(&p));
+            let (did, _) = derive_did_and_pubkey(&signing_key);
+            if let Some(token) = &delegation {
+                if token.audience != did {
+                    fatal(&format!(
+                        "the delegation was issued to {}, not the signing key {did}",
+                        token.audience
+                    ));
+                }
+            }
+            // Units should be authored by whoever the signature speaks for.
+            let author = delegation.as_ref().map_or(did.as_str(), |t| t.issuer.as_str());
+
+            for unit in &mut units {
+                if let Err(e) = validate_unit(unit) {
+                    fatal(&format!("unit {} is invalid: {e}", unit.id));
+                }
+                if replace {
+                    unit.proof = None;
+                } else if unit.proof.is_some() {
+                    fatal(&format!(
+                        "unit {} is already signed; pass --replace to sign it again",
+                        unit.id
+                    ));
+                }
+                if unit.author != author {
+                    eprintln!(
+                        "warning: unit {} is authored by {}, not {author}",
+                        unit.id, unit.author
+                    );
+                }
+                let signed = match &delegation {
+                    Some(token) => sign_unit_delegated(unit, &signing_key, token.clone()),
+                    None => sign_unit(unit, &signing_key, &did),
+                };
+                if let Err(e) = signed {
+                    fatal(&format!("cannot sign unit {}: {e}", unit.id));
+                }
+            }
+
+            let out = if is_array {
+                serde_json::to_string_pretty(&units)
+            } else {
+                serde_json::to_string_pretty(&units[0])
+            };
+            println!("{}", out.expect("serialization is infallible"));
+        }
+
+        Command::Verify { file } => {
+            let units = load_units(&file);
+            let mut any_invalid = false;
+            let mut any_unattributed = false;
+
+            for unit in &units {
+                let signer = proof_signer(unit).unwrap_or("-");
+                let (status, matches) = match verify_proof(unit) {
+                    Ok(()) if signer == unit.author => ("valid", "author"),
+                    Ok(()) if proof_author(unit) == Some(unit.author.as_str()) => {
+                        ("valid", "delegated")
+                    }
+                    Ok(()) => {
+                        any_unattributed = true;
+                        ("valid", "not-author")
+                    }
+                    Err(ProofError::ProofMissing) => {
+                        any_unattributed = true;
+                        ("unsigned", "-")
+                    }
+                    Err(e) => {
+                        any_invalid = true;
+                        eprintln!("error: unit {}: {e}", unit.id);
+                        ("invalid", "-")
+                    }
+                };
+                println!("{}\t{status}\t{signer}\t{matches}", unit.id);
+            }
+
+            if any_invalid {
+                process::exit(1);
+            } else if any_unattributed {
+                process::exit(3);
+            }
+        }
+
         Command::Register {
             node,
             inbox_url,
@@ -360,43 +1477,87 @@ fn main() {
             key,
         } => {
             let signing_key = load_key(key);
-            let (did, pubkey_multibase) = derive_did_and_pubkey(&signing_key);
+            let client = node_client(&node, Some(&signing_key));
+            let profile = or_exit(block_on(client.register(&inbox_url, display_name.as_deref())));
+            println!("{}", serde_json::to_string(&profile).expect("serializable"));
+        }
 
-            let node = node.trim_end_matches('/');
-            let path = format!("/v1/agents/{}", urlencoded(&did));
-            let host = extract_host(node);
-            let (date, sig) = http_sign(&signing_key, &did, "post", &path, &host);
+        Command::Apply {
+            node,
+            sponsor,
+            inbox_url,
+            display_name,
+            key,
+            format,
+        } => {
+            let signing_key = load_key(key);
+            let client = node_client(&node, Some(&signing_key));
+            let profile = or_exit(block_on(client.apply(Application {
+                inbox_url,
+                display_name,
+                sponsor_did: sponsor,
+            })));
+            let threshold = fetch_probation_threshold(&client);
+            print_agent_status(&profile, threshold, format);
+        }
 
-            let body = serde_json::json!({
-                "did": did,
-                "inbox_url": inbox_url,
-                "display_name": display_name,
-                "public_key": pubkey_multibase,
-            });
+        Command::Status {
+            did,
+            node,
+            key,
+            format,
+        } => {
+            let did = did.unwrap_or_else(|| derive_did_and_pubkey(&load_key(key)).0);
+            let client = node_client(&node, None);
+            let profile = or_exit(block_on(client.agent(&did)));
+            let threshold = fetch_probation_threshold(&client);
+            print_agent_status(&profile, threshold, format);
+        }
 
-            let client = reqwest::blocking::Client::new();
-            let resp = client
-                .post(format!("{node}{path}"))
-                .header("host", &host)
-                .header("date", &date)
-                .header("signature", &sig)
-                .json(&body)
-                .send()
-                .unwrap_or_else(|e| fatal(&format!("request failed: {e}")));
-
-            let status = resp.status();
-            let text = resp.text().unwrap_or_default();
-
-            if status.is_success() {
-                println!("{text}");
-            } else {
-                eprintln!("sweft: server returned {status}");
-                eprintln!("{text}");
+        Command::Doctor { node, key } => {
+            let path = key.unwrap_or_else(default_key_path);
+            let signing_key = path.exists().then(|| load_key(Some(path)));
+            let report = doctor::run(&node, signing_key.as_ref());
+            report.print();
+            if !report.passed() {
                 process::exit(1);
             }
         }
 
-        Command::Submit { node, file, key } => {
+        Command::Submit {
+            node,
+            batch: Some(batch),
+            key,
+            concurrency,
+            rate,
+            journal,
+            ..
+        } => {
+            let signing_key = key.map(|p| load_key(Some(p)));
+            let did = signing_key.as_ref().map(|k| derive_did_and_pubkey(k).0);
+            let passed = batch::run(batch::BatchOptions {
+                node,
+                file: batch,
+                key: signing_key,
+                did,
+                delegation: None,
+                concurrency,
+                rate_per_minute: rate,
+                journal,
+            });
+            if !passed {
+                process::exit(1);
+            }
+        }
+
+        Command::Submit {
+            node,
+            file,
+            key,
+            delegation,
+            ..
+        } => {
+            let file = file.expect("clap requires FILE without --batch");
             let json = read_input(&file);
             // Validate locally before sending.
             let unit = match serde_json::from_str::<SemanticUnit>(&json) {
@@ -407,36 +1568,129 @@ fn main() {
                 fatal(&format!("unit is invalid: {e}"));
             }
 
-            let node = node.trim_end_matches('/');
-            let path = "/v1/units";
-            let host = extract_host(node);
-
-            let client = reqwest::blocking::Client::new();
-            let mut builder = client
-                .post(format!("{node}{path}"))
-                .header("host", &host)
-                .json(&unit);
-
-            if let Some(signing_key) = key.map(|p| load_key(Some(p))) {
-                let (did, _) = derive_did_and_pubkey(&signing_key);
-                let (date, sig) = http_sign(&signing_key, &did, "post", path, &host);
-                builder = builder.header("date", &date).header("signature", &sig);
+            let signing_key = key.map(|p| load_key(Some(p)));
+            let mut client = node_client(&node, signing_key.as_ref());
+            if let Some(path) = delegation {
+                client = client.with_delegation(load_delegation(&path));
             }
+            let submitted = or_exit(block_on(client.submit(&unit)));
+            println!("{}", serde_json::to_string(&submitted.unit).expect("serializable"));
+        }
 
-            let resp = builder
-                .send()
-                .unwrap_or_else(|e| fatal(&format!("request failed: {e}")));
+        Command::Outbox { outbox, action } => {
+            let path = outbox.unwrap_or_else(|| sweft_config_dir().join("outbox.json"));
+            let mut file = outbox::OutboxFile::open(&path).unwrap_or_else(|e| fatal(&e));
+            match action {
+                OutboxCommand::Add { file: input, key } => {
+                    let signing_key = key.map(|p| load_key(Some(p)));
+                    let did = signing_key.as_ref().map(|k| derive_did_and_pubkey(k).0);
+                    let (mut added, mut present) = (0, 0);
+                    for mut unit in load_units(&input) {
+                        if let Err(e) = validate_unit(&unit) {
+                            fatal(&format!("unit {} is invalid: {e}", unit.id));
+                        }
+                        if let (Some(key), Some(did), None) = (&signing_key, &did, &unit.proof) {
+                            if let Err(e) = sign_unit(&mut unit, key, did) {
+                                fatal(&format!("cannot sign unit {}: {e}", unit.id));
+                            }
+                        }
+                        match file.outbox.enqueue(unit) {
+                            Ok(true) => added += 1,
+                            Ok(false) => present += 1,
+                            Err(e) => fatal(&e.to_string()),
+                        }
+                    }
+                    file.save().unwrap_or_else(|e| fatal(&e));
+                    println!("Queued {added} units in {} ({present} already queued)", path.display());
+                }
+                OutboxCommand::Flush { node, key, wait } => {
+                    let signing_key = key.map(|p| load_key(Some(p)));
+                    // The outbox schedules its own retries, durably; the
+                    // client only needs to report a 429.
+                    let client = node_client(&node, signing_key.as_ref()).with_retry(
+                        semanticweft_client::RetryPolicy {
+                            max_retries: 0,
+                            ..Default::default()
+                        },
+                    );
+                    outbox::flush(&mut file, &client, wait).unwrap_or_else(|e| fatal(&e));
+                    let summary = file.outbox.summary();
+                    if summary.queued > 0 {
+                        eprintln!("sweft: {} units still queued", summary.queued);
+                    }
+                    if summary.failed + summary.blocked > 0 {
+                        eprintln!(
+                            "sweft: {} failed, {} blocked; see `sweft outbox list`",
+                            summary.failed, summary.blocked
+                        );
+                        process::exit(1);
+                    }
+                }
+                OutboxCommand::List => outbox::list(&file.outbox),
+                OutboxCommand::Retry => {
+                    let n = file.outbox.retry_failed();
+                    file.save().unwrap_or_else(|e| fatal(&e));
+                    println!("Requeued {n} units");
+                }
+                OutboxCommand::Prune => {
+                    let n = file.outbox.prune_delivered();
+                    file.save().unwrap_or_else(|e| fatal(&e));
+                    println!("Removed {n} delivered units");
+                }
+            }
+        }
 
-            let status = resp.status();
-            let text = resp.text().unwrap_or_default();
+        Command::Fetch {
+            from: Some(from),
+            id,
+            unit_type,
+            author,
+            since,
+            after,
+            limit,
+            format,
+            ..
+        } => {
+            if let Some(id) = id {
+                let unit = if mirror::is_mirror(&from) {
+                    open_mirror(&from).get(&id).unwrap_or_else(|e| fatal(&e))
+                } else {
+                    load_units(&from).into_iter().find(|u| u.id == id)
+                };
+                match unit {
+                    Some(u) => print_fetched(
+                        &serde_json::to_string(&u).expect("serializable"),
+                        format,
+                    ),
+                    None => {
+                        eprintln!("sweft: unit {id} not found in {}", from.display());
+                        process::exit(1);
+                    }
+                }
+                return;
+            }
 
-            if status.is_success() {
-                println!("{text}");
+            let filter = mirror::LocalFilter {
+                unit_types: parse_unit_types(unit_type.as_deref()),
+                author,
+                since: since.map(|s| {
+                    chrono::DateTime::parse_from_rfc3339(&s)
+                        .unwrap_or_else(|e| fatal(&format!("--since: {e}")))
+                }),
+                after,
+                limit: Some(limit.unwrap_or(50) as usize),
+            };
+            let (units, has_more) = if mirror::is_mirror(&from) {
+                open_mirror(&from).query(&filter).unwrap_or_else(|e| fatal(&e))
             } else {
-                eprintln!("sweft: server returned {status}");
-                eprintln!("{text}");
-                process::exit(1);
-            }
+                filter.apply(load_units(&from))
+            };
+            let body = serde_json::json!({
+                "units": units,
+                "cursor": units.last().map(|u| u.id.clone()),
+                "has_more": has_more,
+            });
+            print_fetched(&body.to_string(), format);
         }
 
         Command::Fetch {
@@ -448,67 +1702,485 @@ fn main() {
             since,
             after,
             limit,
+            format,
+            ..
         } => {
-            let node = node.trim_end_matches('/');
-            let host = extract_host(node);
+            let node = node.expect("required unless --from is present");
+            let signing_key = key.map(|p| load_key(Some(p)));
+            let client = node_client(&node, signing_key.as_ref());
+
+            let body = match id {
+                Some(ref uid) => serde_json::to_string(&or_exit(block_on(client.get(uid)))),
+                None => {
+                    let query = ListQuery {
+                        unit_types: unit_type
+                            .iter()
+                            .flat_map(|t| t.split(','))
+                            .map(|t| t.trim().to_string())
+                            .collect(),
+                        author,
+                        since,
+                        after,
+                        limit,
+                        ..ListQuery::default()
+                    };
+                    serde_json::to_string(&or_exit(block_on(client.list_page(&query))))
+                }
+            };
+            print_fetched(&body.expect("serializable"), format);
+        }
 
-            let path = if let Some(ref uid) = id {
-                format!("/v1/units/{}", urlencoded(uid))
-            } else {
-                let mut params: Vec<(&str, String)> = Vec::new();
-                if let Some(ref t) = unit_type {
-                    params.push(("type", t.clone()));
+        Command::Follow { node, target, key } => {
+            let client = node_client(&node, Some(&load_key(key)));
+            or_exit(block_on(client.follow(&target)));
+            println!("Following {target}");
+        }
+
+        Command::Unfollow { node, target, key } => {
+            let client = node_client(&node, Some(&load_key(key)));
+            or_exit(block_on(client.unfollow(&target)));
+            println!("Unfollowed {target}");
+        }
+
+        Command::Following { node, did, key } => list_follows(&node, did, key, "following"),
+
+        Command::Followers { node, did, key } => list_follows(&node, did, key, "followers"),
+
+        Command::Inbox {
+            node,
+            key,
+            after,
+            limit,
+            follow,
+            interval,
+            format,
+        } => {
+            let client = node_client(&node, Some(&load_key(key)));
+            let mut cursor = after;
+
+            loop {
+                // Drain every available page, then either stop or wait and poll
+                // again from the last item seen.
+                loop {
+                    let page = or_exit(block_on(client.inbox_page(cursor.as_deref(), limit)));
+                    for unit in &page.items {
+                        if format == OutputFormat::Json {
+                            println!("{}", serde_json::to_string(unit).expect("serializable"));
+                        } else {
+                            println!("{}", semanticweft::render::render_unit(unit));
+                        }
+                    }
+                    if let Some(last) = page.items.last() {
+                        cursor = Some(last.id.clone());
+                    }
+                    match page.next_cursor {
+                        Some(next) => cursor = Some(next),
+                        None => break,
+                    }
+                }
+
+                if !follow {
+                    break;
                 }
-                if let Some(ref a) = author {
-                    params.push(("author", a.clone()));
+                std::thread::sleep(std::time::Duration::from_secs(interval));
+            }
+        }
+
+        Command::Mirror { node, into } => {
+            let mut mirror = open_mirror(&into);
+            let fetched = mirror.pull(&node_client(&node, None)).unwrap_or_else(|e| fatal(&e));
+            let total = mirror.len().unwrap_or_else(|e| fatal(&e));
+            println!("Mirrored {fetched} new units into {} ({total} total)", into.display());
+        }
+
+        Command::Watch {
+            node,
+            unit_type,
+            author,
+            after,
+            cursor_file,
+            format,
+        } => {
+            let unit_types = parse_unit_types(unit_type.as_deref());
+            let cursor_file = cursor_file.unwrap_or_else(|| {
+                let host = extract_host(&node).replace(':', "_");
+                sweft_config_dir().join("watch").join(format!("{host}.cursor"))
+            });
+            watch::run(watch::WatchOptions {
+                node,
+                unit_types,
+                author,
+                after,
+                cursor_file,
+                json: format == OutputFormat::Json,
+            });
+        }
+
+        Command::Subgraph {
+            node,
+            id,
+            depth,
+            key,
+            format,
+        } => {
+            let signing_key = key.map(|p| load_key(Some(p)));
+            let client = node_client(&node, signing_key.as_ref());
+            let units = or_exit(block_on(client.subgraph(&id, depth)));
+            match format {
+                GraphFormat::Json => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&units).expect("serializable")
+                ),
+                GraphFormat::Text => {
+                    print!("{}", semanticweft::render::render_threads(&Graph::from_units(units)))
                 }
-                if let Some(ref s) = since {
-                    params.push(("since", s.clone()));
+                GraphFormat::Dot => {
+                    print!("{}", semanticweft::render::render_dot(&Graph::from_units(units)))
                 }
-                if let Some(ref c) = after {
-                    params.push(("after", c.clone()));
+            }
+        }
+
+        Command::Browse {
+            node,
+            from,
+            id,
+            depth,
+            limit,
+            key,
+            unit_type,
+            author,
+        } => {
+            let unit_type = unit_type.map(|t| {
+                t.parse::<UnitType>()
+                    .unwrap_or_else(|e| fatal(&format!("--type: {e}")))
+            });
+            let (units, backing) = match from {
+                Some(from) => (load_units(&from), None),
+                None => {
+                    let node = node.expect("required unless --from is present");
+                    let signing_key = key.map(|p| load_key(Some(p)));
+                    let client = node_client(&node, signing_key.as_ref());
+                    let units = match id {
+                        Some(ref id) => or_exit(block_on(client.subgraph(id, depth))),
+                        None => fetch_unit_list(&client, limit),
+                    };
+                    (units, Some(browse::NodeBacking { client }))
                 }
-                if let Some(l) = limit {
-                    params.push(("limit", l.to_string()));
+            };
+            if units.is_empty() {
+                fatal("no units to browse");
+            }
+            browse::run(browse::BrowseOptions {
+                units,
+                backing,
+                start: id,
+                unit_type,
+                author,
+            })
+            .unwrap_or_else(|e| fatal(&format!("terminal error: {e}")));
+        }
+
+        Command::Key {
+            action: KeyCommand::Export { key, out },
+        } => {
+            let signing_key = load_key(key);
+            let hex = hex_encode(&signing_key.to_bytes());
+            match out {
+                Some(path) => {
+                    write_private_file(&path, &hex);
+                    eprintln!("sweft: wrote unencrypted seed to {}", path.display());
                 }
+                None => println!("{hex}"),
+            }
+        }
+
+        Command::Key {
+            action:
+                KeyCommand::Import {
+                    file,
+                    out,
+                    encrypt,
+                    force,
+                },
+        } => {
+            let contents = read_input(&file);
+            let signing_key = if keyfile::is_encrypted(&contents) {
+                let passphrase =
+                    keyfile::read_passphrase(&format!("Passphrase for {}: ", file.display()))
+                        .unwrap_or_else(|e| fatal(&e));
+                keyfile::decrypt(&contents, &passphrase).unwrap_or_else(|e| fatal(&e))
+            } else {
+                keyfile::parse_plain(&contents).unwrap_or_else(|e| fatal(&e))
+            };
+
+            let path = out.unwrap_or_else(default_key_path);
+            if path.exists() && !force {
+                fatal(&format!(
+                    "{} already exists; pass --force to overwrite it",
+                    path.display()
+                ));
+            }
+            let passphrase = encrypt.then(|| {
+                keyfile::read_new_passphrase(keyfile::NEW_PASSPHRASE_ENV)
+                    .unwrap_or_else(|e| fatal(&e))
+            });
+            write_key_file(&path, &signing_key, passphrase.as_deref());
+
+            let (did, _) = derive_did_and_pubkey(&signing_key);
+            println!("Key file : {}", path.display());
+            println!("DID      : {did}");
+        }
+
+        Command::Key {
+            action: KeyCommand::Rekey { key },
+        } => {
+            let path = key.unwrap_or_else(default_key_path);
+            let signing_key = load_key(Some(path.clone()));
+            let passphrase = keyfile::read_new_passphrase(keyfile::NEW_PASSPHRASE_ENV)
+                .unwrap_or_else(|e| fatal(&e));
+            write_key_file(&path, &signing_key, Some(&passphrase));
+            println!("Re-encrypted {}", path.display());
+        }
+
+        Command::Key {
+            action:
+                KeyCommand::Rotate {
+                    key,
+                    new,
+                    encrypt,
+                    reason,
+                    out,
+                    node,
+                },
+        } => {
+            let old_key = load_key(key);
+            let new_key = if new.exists() {
+                load_key(Some(new.clone()))
+            } else {
+                let generated = SigningKey::generate(&mut OsRng);
+                let passphrase = encrypt.then(|| {
+                    keyfile::read_new_passphrase(keyfile::NEW_PASSPHRASE_ENV)
+                        .unwrap_or_else(|e| fatal(&e))
+                });
+                write_key_file(&new, &generated, passphrase.as_deref());
+                eprintln!("sweft: generated {}", new.display());
+                generated
+            };
+            if new_key == old_key {
+                fatal("the new key is the same as the old one");
+            }
 
-                let qs: String = params
-                    .iter()
-                    .enumerate()
-                    .map(|(i, (k, v))| {
-                        format!(
-                            "{}{}={}",
-                            if i == 0 { "?" } else { "&" },
-                            k,
-                            urlencoded(v)
-                        )
-                    })
-                    .collect();
-
-                format!("/v1/units{qs}")
+            let statement = sign_succession(
+                &AgentIdentity::from_seed(&old_key.to_bytes()),
+                &AgentIdentity::from_seed(&new_key.to_bytes()),
+                chrono::Utc::now().to_rfc3339(),
+                reason,
+            );
+            let json = serde_json::to_string_pretty(&statement).expect("serializable");
+            match &out {
+                Some(path) => fs::write(path, format!("{json}\n")).unwrap_or_else(|e| {
+                    fatal(&format!("failed to write {}: {e}", path.display()))
+                }),
+                None if node.is_none() => println!("{json}"),
+                None => {}
+            }
+
+            if let Some(node) = node {
+                let profile = or_exit(block_on(node_client(&node, None).rotate(&statement)));
+                eprintln!(
+                    "sweft: {} is now {} (reputation {:.2})",
+                    statement.predecessor, profile.did, profile.reputation
+                );
+            }
+            eprintln!(
+                "sweft: sign future requests with {} (e.g. SWEFT_KEY={})",
+                new.display(),
+                new.display()
+            );
+        }
+
+        Command::Key {
+            action:
+                KeyCommand::Delegate {
+                    key,
+                    to,
+                    encrypt,
+                    unit_types,
+                    visibilities,
+                    endpoints,
+                    ttl,
+                    out,
+                },
+        } => {
+            let root_key = load_key(key);
+            let delegate_key = if to.exists() {
+                load_key(Some(to.clone()))
+            } else {
+                let generated = SigningKey::generate(&mut OsRng);
+                let passphrase = encrypt.then(|| {
+                    keyfile::read_new_passphrase(keyfile::NEW_PASSPHRASE_ENV)
+                        .unwrap_or_else(|e| fatal(&e))
+                });
+                write_key_file(&to, &generated, passphrase.as_deref());
+                eprintln!("sweft: generated {}", to.display());
+                generated
             };
+            if delegate_key == root_key {
+                fatal("a key cannot delegate to itself");
+            }
+            if let Some(bad) = endpoints.iter().find(|e| !e.contains(' ')) {
+                fatal(&format!("endpoint {bad:?} is not \"METHOD PATH\""));
+            }
 
-            let client = reqwest::blocking::Client::new();
-            let mut builder = client.get(format!("{node}{path}")).header("host", &host);
+            let (audience, _) = derive_did_and_pubkey(&delegate_key);
+            let now = std::time::SystemTime::now();
+            let token = AgentIdentity::from_seed(&root_key.to_bytes()).delegate(
+                audience,
+                DelegationScope {
+                    unit_types,
+                    visibilities,
+                    endpoints,
+                },
+                now,
+                now + std::time::Duration::from_secs(ttl * 60),
+            );
+            let json = serde_json::to_string_pretty(&token).expect("serializable");
+            match &out {
+                Some(path) => fs::write(path, format!("{json}\n")).unwrap_or_else(|e| {
+                    fatal(&format!("failed to write {}: {e}", path.display()))
+                }),
+                None => println!("{json}"),
+            }
+            eprintln!(
+                "sweft: {} may act for {} until {}",
+                token.audience, token.issuer, token.expires
+            );
+        }
 
-            if let Some(signing_key) = key.map(|p| load_key(Some(p))) {
-                let (did, _) = derive_did_and_pubkey(&signing_key);
-                let (date, sig) = http_sign(&signing_key, &did, "get", &path, &host);
-                builder = builder.header("date", &date).header("signature", &sig);
+        Command::Profile { action } => {
+            let path = config::config_path();
+            let mut config = config::Config::load(&path).unwrap_or_else(|e| fatal(&e));
+            match action {
+                ProfileCommand::Add {
+                    name,
+                    node,
+                    key,
+                    visibility,
+                    format,
+                    make_current,
+                } => {
+                    let profile = config.profiles.entry(name.clone()).or_default();
+                    if node.is_some() {
+                        profile.node = node;
+                    }
+                    if key.is_some() {
+                        profile.key = key;
+                    }
+                    if visibility.is_some() {
+                        profile.visibility = visibility;
+                    }
+                    if format.is_some() {
+                        profile.format = format;
+                    }
+                    if make_current || config.current.is_none() {
+                        config.current = Some(name.clone());
+                    }
+                    config.save(&path).unwrap_or_else(|e| fatal(&e));
+                    println!("Saved profile {name} to {}", path.display());
+                }
+                ProfileCommand::List => {
+                    for (name, p) in &config.profiles {
+                        let marker = if config.current.as_ref() == Some(name) { '*' } else { ' ' };
+                        println!(
+                            "{marker} {name:<12} node={} key={} visibility={} format={}",
+                            p.node.as_deref().unwrap_or("-"),
+                            p.key.as_deref().unwrap_or("-"),
+                            p.visibility.as_ref().map_or("-".to_string(), |v| v.to_string()),
+                            p.format.map_or("-".to_string(), |f| f.to_string()),
+                        );
+                    }
+                }
+                ProfileCommand::Use { name } => {
+                    if !config.profiles.contains_key(&name) {
+                        fatal(&format!("no profile named {name:?}"));
+                    }
+                    config.current = Some(name.clone());
+                    config.save(&path).unwrap_or_else(|e| fatal(&e));
+                    println!("Default profile is now {name}");
+                }
             }
+        }
+
+        Command::Bundle {
+            action:
+                BundleCommand::Create {
+                    node,
+                    id,
+                    key,
+                    out,
+                    depth,
+                },
+        } => {
+            let signing_key = load_key(key);
+            let (did, _) = derive_did_and_pubkey(&signing_key);
+            let client = node_client(&node, Some(&signing_key));
 
-            let resp = builder
-                .send()
-                .unwrap_or_else(|e| fatal(&format!("request failed: {e}")));
+            let graph = Graph::from_units(or_exit(block_on(client.subgraph(&id, depth))));
 
-            let status = resp.status();
-            let text = resp.text().unwrap_or_default();
+            // The justification is the unit plus everything it rests on.
+            let Some(root) = graph.get(&id) else {
+                fatal(&format!("node did not return unit {id}"));
+            };
+            let mut units: Vec<SemanticUnit> = std::iter::once(root)
+                .chain(graph.ancestors(&id))
+                .cloned()
+                .collect();
+            units.sort_by(|a, b| a.id.cmp(&b.id));
+
+            let mut dids: Vec<&str> = units.iter().map(|u| u.author.as_str()).collect();
+            dids.sort();
+            dids.dedup();
+            let authors = dids
+                .into_iter()
+                .map(|author| bundle::AuthorEntry {
+                    did: author.to_string(),
+                    profile: block_on(client.agent(author))
+                        .ok()
+                        .map(|p| serde_json::to_value(p).expect("serializable")),
+                })
+                .collect();
+
+            let archive = bundle::create(bundle::BundleInput {
+                root: &id,
+                source_node: client.node_url(),
+                units: &units,
+                authors,
+                key: &signing_key,
+                exporter: &did,
+            })
+            .unwrap_or_else(|e| fatal(&e));
+
+            let out = out.unwrap_or_else(|| PathBuf::from(format!("{id}.bundle.tar")));
+            fs::write(&out, archive)
+                .unwrap_or_else(|e| fatal(&format!("cannot write {}: {e}", out.display())));
+            println!("Wrote {} units to {}", units.len(), out.display());
+        }
 
-            if status.is_success() {
-                println!("{text}");
+        Command::Bundle {
+            action: BundleCommand::Verify {
+                file,
+                require_proofs,
+                allow_foreign_signers,
+            },
+        } => {
+            let archive = fs::read(&file)
+                .unwrap_or_else(|e| fatal(&format!("cannot read {}: {e}", file.display())));
+            let report = bundle::verify(&archive).unwrap_or_else(|e| fatal(&e));
+            report.print();
+            if report.passed(require_proofs, allow_foreign_signers) {
+                println!("\nbundle verified");
             } else {
-                eprintln!("sweft: server returned {status}");
-                eprintln!("{text}");
+                println!("\nbundle FAILED verification");
                 process::exit(1);
             }
         }
@@ -521,8 +2193,13 @@ fn main() {
 
 /// Return the default key file path: `~/.config/sweft/identity.key`.
 fn default_key_path() -> PathBuf {
+    sweft_config_dir().join("identity.key")
+}
+
+/// Return the CLI's configuration directory: `~/.config/sweft`.
+fn sweft_config_dir() -> PathBuf {
     let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    PathBuf::from(home).join(".config").join("sweft").join("identity.key")
+    PathBuf::from(home).join(".config").join("sweft")
 }
 
 /// Load an Ed25519 signing key from a hex-encoded seed file.
@@ -531,18 +2208,65 @@ fn default_key_path() -> PathBuf {
 /// Exits with a helpful error if the file is missing or malformed.
 fn load_key(path: Option<PathBuf>) -> SigningKey {
     let p = path.unwrap_or_else(default_key_path);
-    let hex = fs::read_to_string(&p).unwrap_or_else(|e| {
+    let contents = fs::read_to_string(&p).unwrap_or_else(|e| {
         fatal(&format!(
             "failed to read key file {}: {e}\nRun `sweft keygen` to create one.",
             p.display()
         ))
     });
-    let seed = hex_decode(hex.trim())
-        .unwrap_or_else(|e| fatal(&format!("invalid key file {}: {e}", p.display())));
-    let arr: [u8; 32] = seed
-        .try_into()
-        .unwrap_or_else(|_| fatal("key file must contain a 32-byte hex seed (64 hex chars)"));
-    SigningKey::from_bytes(&arr)
+    let key = if keyfile::is_encrypted(&contents) {
+        keyfile::read_passphrase(&format!("Passphrase for {}: ", p.display()))
+            .and_then(|passphrase| keyfile::decrypt(&contents, &passphrase))
+    } else {
+        keyfile::parse_plain(&contents)
+    };
+    key.unwrap_or_else(|e| fatal(&format!("invalid key file {}: {e}", p.display())))
+}
+
+/// Write `key` to `path`, encrypted under `passphrase` if one is given.
+fn write_key_file(path: &std::path::Path, key: &SigningKey, passphrase: Option<&str>) {
+    let contents = match passphrase {
+        Some(p) => keyfile::encrypt(key, p).unwrap_or_else(|e| fatal(&e)),
+        None => hex_encode(&key.to_bytes()),
+    };
+    write_private_file(path, &contents);
+}
+
+/// Write secret material to `path`, creating parent directories. The data
+/// goes to a temporary file that is private from the start (mode 0600 on
+/// Unix) and is then renamed into place, so an interrupted write never
+/// leaves a truncated or readable key behind.
+fn write_private_file(path: &std::path::Path, contents: &str) {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap_or_else(|e| {
+            fatal(&format!("failed to create {}: {e}", parent.display()))
+        });
+    }
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+    let _ = fs::remove_file(&tmp);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(&tmp)
+        .and_then(|mut file| {
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&tmp, path))
+        .unwrap_or_else(|e| {
+            let _ = fs::remove_file(&tmp);
+            fatal(&format!("failed to write key file {}: {e}", path.display()))
+        });
 }
 
 /// Derive the `did:key` DID and public key multibase from a signing key.
@@ -554,22 +2278,6 @@ fn derive_did_and_pubkey(key: &SigningKey) -> (String, String) {
     (format!("did:key:z{encoded}"), format!("z{encoded}"))
 }
 
-/// Build `Date` and `Signature` HTTP header values for an outbound request.
-///
-/// Mirrors `build_outbound_signature` in the node's auth middleware so that
-/// the CLI and node agree on the signing string format.
-fn http_sign(key: &SigningKey, did: &str, method: &str, path: &str, host: &str) -> (String, String) {
-    let date = httpdate::fmt_http_date(std::time::SystemTime::now());
-    let signing_string =
-        format!("(request-target): {method} {path}\nhost: {host}\ndate: {date}");
-    let sig_bytes = key.sign(signing_string.as_bytes()).to_bytes();
-    let sig_encoded = format!("z{}", bs58::encode(sig_bytes).into_string());
-    let sig_header = format!(
-        r#"keyId="{did}",algorithm="ed25519",headers="(request-target) host date",signature="{sig_encoded}""#
-    );
-    (date, sig_header)
-}
-
 /// Extract the `host` component (no scheme, no path) from a node base URL.
 fn extract_host(node_url: &str) -> String {
     let stripped = node_url
@@ -579,26 +2287,138 @@ fn extract_host(node_url: &str) -> String {
 }
 
 // ---------------------------------------------------------------------------
-// General helpers
+// Network helpers
 // ---------------------------------------------------------------------------
 
-/// Percent-encode a string for safe inclusion in a URL path or query string.
-fn urlencoded(s: &str) -> String {
-    // Encode characters outside the unreserved set (RFC 3986 §2.3).
-    let mut out = String::with_capacity(s.len());
-    for b in s.bytes() {
-        match b {
-            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
-                out.push(b as char);
+/// Run `future` to completion on the CLI's single-threaded runtime.
+///
+/// The CLI is synchronous; async [`Client`] calls are driven one at a time
+/// from here.
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap_or_else(|e| fatal(&format!("cannot start async runtime: {e}")))
+        })
+        .block_on(future)
+}
+
+/// A client for `node`, signing requests with `key` when one is given.
+fn node_client(node: &str, key: Option<&SigningKey>) -> Client {
+    Client::new(node, key.map(|k| AgentIdentity::from_seed(&k.to_bytes())))
+}
+
+/// Unwrap a client result, or report the node's error and exit 1. Failures
+/// to reach the node at all exit 2, like other fatal errors.
+fn or_exit<T>(result: Result<T, semanticweft_client::Error>) -> T {
+    use semanticweft_client::Error;
+    match result {
+        Ok(value) => value,
+        Err(Error::Api {
+            status, message, ..
+        }) => {
+            eprintln!("sweft: server returned {status}");
+            eprintln!("{message}");
+            process::exit(1);
+        }
+        Err(e @ Error::RateLimited { .. }) => {
+            eprintln!("sweft: {e}");
+            process::exit(1);
+        }
+        Err(e) => fatal(&e.to_string()),
+    }
+}
+
+/// Page through `GET /v1/units` until `limit` units have been collected or
+/// the node has no more; exits on any HTTP or parse error.
+fn fetch_unit_list(client: &Client, limit: u32) -> Vec<SemanticUnit> {
+    let query = ListQuery {
+        limit: Some(limit.min(500)),
+        ..ListQuery::default()
+    };
+    or_exit(block_on(
+        client.list(query).take(limit as usize).try_collect(),
+    ))
+}
+
+/// Print one side of an agent's follow graph (`relation` is `following` or
+/// `followers`), one DID per line.
+fn list_follows(node: &str, did: Option<String>, key: Option<PathBuf>, relation: &str) {
+    let client = node_client(node, Some(&load_key(key)));
+    let subject = did.unwrap_or_else(|| client.did().expect("signing client").to_string());
+    let entries = match relation {
+        "following" => client.following(&subject).boxed_local(),
+        _ => client.followers(&subject).boxed_local(),
+    };
+    for entry in or_exit(block_on(entries.try_collect::<Vec<_>>())) {
+        println!("{}", entry.did);
+    }
+}
+
+/// The node's advertised `probation_threshold`, if it publishes one.
+fn fetch_probation_threshold(client: &Client) -> Option<u32> {
+    block_on(client.node_info())
+        .ok()
+        .and_then(|info| info.probation_threshold)
+}
+
+/// Print an agent's membership status and, for probationary members, how
+/// far they are from graduating.
+fn print_agent_status(profile: &AgentProfile, threshold: Option<u32>, format: OutputFormat) {
+    let remaining = match (&profile.status, threshold) {
+        (AgentStatus::Probationary, Some(t)) => Some(t.saturating_sub(profile.contribution_count)),
+        _ => None,
+    };
+    match format {
+        OutputFormat::Json => {
+            let report = serde_json::json!({
+                "did": profile.did,
+                "status": profile.status,
+                "contribution_count": profile.contribution_count,
+                "probation_threshold": threshold,
+                "remaining": remaining,
+                "reputation": profile.reputation,
+                "predecessor": profile.predecessor,
+                "successor": profile.successor,
+            });
+            println!("{}", serde_json::to_string_pretty(&report).expect("serializable"));
+        }
+        OutputFormat::Text => {
+            let status = match profile.status {
+                AgentStatus::Full => "full",
+                AgentStatus::Probationary => "probationary",
+            };
+            println!("DID           : {}", profile.did);
+            println!("Status        : {status}");
+            match (remaining, threshold) {
+                (Some(r), Some(t)) => println!(
+                    "Contributions : {} / {t} ({r} more to full membership)",
+                    profile.contribution_count
+                ),
+                (None, None) if profile.status == AgentStatus::Probationary => println!(
+                    "Contributions : {} (node does not advertise a probation threshold)",
+                    profile.contribution_count
+                ),
+                _ => println!("Contributions : {}", profile.contribution_count),
             }
-            _ => {
-                out.push_str(&format!("%{b:02X}"));
+            println!("Reputation    : {:.2}", profile.reputation);
+            if let Some(predecessor) = &profile.predecessor {
+                println!("Rotated from  : {predecessor}");
+            }
+            if let Some(successor) = &profile.successor {
+                println!("Rotated to    : {successor} (this key is retired)");
             }
         }
     }
-    out
 }
 
+// ---------------------------------------------------------------------------
+// General helpers
+// ---------------------------------------------------------------------------
+
 /// Hex-encode a byte slice as a lowercase string.
 fn hex_encode(bytes: &[u8]) -> String {
     bytes.iter().map(|b| format!("{b:02x}")).collect()
@@ -606,7 +2426,7 @@ fn hex_encode(bytes: &[u8]) -> String {
 
 /// Decode a lowercase hex string into bytes.
 fn hex_decode(s: &str) -> Result<Vec<u8>, String> {
-    if s.len() % 2 != 0 {
+    if !s.len().is_multiple_of(2) {
         return Err("odd-length hex string".into());
     }
     (0..s.len())
@@ -630,6 +2450,12 @@ fn read_input(path: &PathBuf) -> String {
     }
 }
 
+/// Read a delegation token written by `sweft key delegate`, exiting on failure.
+fn load_delegation(path: &PathBuf) -> Delegation {
+    serde_json::from_str(&read_input(path))
+        .unwrap_or_else(|e| fatal(&format!("invalid delegation {}: {e}", path.display())))
+}
+
 /// Parse a JSON string as either an array of units or a single unit.
 ///
 /// Tries the array form first (covering the common "graph file" case), then
@@ -649,6 +2475,54 @@ fn parse_units(json: &str) -> Vec<SemanticUnit> {
     }
 }
 
+/// Print a unit or list response body, verbatim for JSON or rendered for text.
+fn print_fetched(body: &str, format: OutputFormat) {
+    if format == OutputFormat::Json {
+        println!("{body}");
+        return;
+    }
+    let value: serde_json::Value = serde_json::from_str(body)
+        .unwrap_or_else(|e| fatal(&format!("malformed response: {e}")));
+    let units = match value.get("units") {
+        Some(list) => parse_units(&list.to_string()),
+        None => parse_units(body),
+    };
+    if units.len() == 1 {
+        print!("{}", semanticweft::render::render_unit(&units[0]));
+    } else {
+        print!("{}", semanticweft::render::render_graph(&Graph::from_units(units)));
+    }
+}
+
+/// Load units from a JSON file, stdin (`-`), or a mirror database.
+fn load_units(path: &PathBuf) -> Vec<SemanticUnit> {
+    if mirror::is_mirror(path) {
+        let units = open_mirror(path).all().unwrap_or_else(|e| fatal(&e));
+        if units.is_empty() {
+            fatal(&format!("mirror {} is empty — nothing to process", path.display()));
+        }
+        return units;
+    }
+    parse_units(&read_input(path))
+}
+
+/// Open a mirror database, exiting on failure.
+fn open_mirror(path: &std::path::Path) -> mirror::Mirror {
+    mirror::Mirror::open(path).unwrap_or_else(|e| fatal(&e))
+}
+
+/// Parse a comma-separated `--type` value, exiting on an unknown type.
+fn parse_unit_types(arg: Option<&str>) -> Vec<UnitType> {
+    arg.into_iter()
+        .flat_map(|t| t.split(','))
+        .map(|t| {
+            t.trim()
+                .parse()
+                .unwrap_or_else(|e| fatal(&format!("--type: {e}")))
+        })
+        .collect()
+}
+
 /// Print an error message to stderr and exit with code 2.
 fn fatal(msg: &str) -> ! {
     eprintln!("sweft: {}", msg);
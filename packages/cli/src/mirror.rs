@@ -0,0 +1,264 @@
+//! Local mirrors of a node's public graph.
+//!
+//! `sweft mirror` copies every public unit from a node's `/v1/sync` endpoint
+//! into a SQLite file. The sync cursor is stored in the same file and
+//! advanced in the same transaction as each page of units, so an
+//! interrupted mirror resumes where it stopped and re-running it fetches
+//! only what is new.
+//!
+//! The commands that read units from a file (`render`, `validate`, and
+//! `fetch --from`) accept a mirror database wherever they accept JSON;
+//! [`is_mirror`] tells them apart by the SQLite file header.
+
+use std::io::Read;
+use std::path::Path;
+
+use chrono::{DateTime, FixedOffset};
+use rusqlite::{params, Connection};
+use semanticweft::{SemanticUnit, UnitType};
+use semanticweft_client::Client;
+
+use crate::block_on;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS units (
+    id         TEXT PRIMARY KEY,
+    unit_type  TEXT NOT NULL,
+    author     TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    json       TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS units_type   ON units(unit_type);
+CREATE INDEX IF NOT EXISTS units_author ON units(author);
+
+CREATE TABLE IF NOT EXISTS meta (
+    key   TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+";
+
+/// Page size requested from `/v1/sync`.
+const PAGE_LIMIT: usize = 500;
+
+/// `true` if the file at `path` is a SQLite database rather than JSON.
+pub fn is_mirror(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    std::fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .map(|()| &header == b"SQLite format 3\0")
+        .unwrap_or(false)
+}
+
+/// A local mirror database.
+pub struct Mirror {
+    conn: Connection,
+}
+
+impl Mirror {
+    /// Open (creating if necessary) the mirror at `path`.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let conn = Connection::open(path)
+            .map_err(|e| format!("cannot open mirror {}: {e}", path.display()))?;
+        conn.execute_batch(SCHEMA)
+            .map_err(|e| format!("cannot initialise mirror {}: {e}", path.display()))?;
+        Ok(Self { conn })
+    }
+
+    /// Pull every unit after the stored cursor from the client's node and
+    /// return how many were fetched.
+    ///
+    /// A mirror is bound to the node it was first filled from; pulling from a
+    /// different node is refused because the two cursors are unrelated.
+    pub fn pull(&mut self, client: &Client) -> Result<usize, String> {
+        let node = client.node_url();
+        match self.meta("source_node")? {
+            Some(source) if source != node => {
+                return Err(format!("mirror was created from {source}, not {node}"));
+            }
+            Some(_) => {}
+            None => self.set_meta("source_node", node)?,
+        }
+
+        let mut fetched = 0;
+        loop {
+            let cursor = self.meta("cursor")?;
+            let page = block_on(client.sync_page(cursor.as_deref(), PAGE_LIMIT))
+                .map_err(|e| e.to_string())?;
+
+            let Some(last) = page.units.last().map(|u| u.id.clone()) else {
+                break;
+            };
+            self.store_page(&page.units, &last)?;
+            fetched += page.units.len();
+
+            if !page.has_more {
+                break;
+            }
+        }
+        Ok(fetched)
+    }
+
+    /// Insert a page of units and advance the cursor atomically.
+    fn store_page(&mut self, units: &[SemanticUnit], cursor: &str) -> Result<(), String> {
+        let tx = self.conn.transaction().map_err(|e| e.to_string())?;
+        for unit in units {
+            let json = serde_json::to_string(unit).map_err(|e| e.to_string())?;
+            tx.execute(
+                "INSERT OR REPLACE INTO units (id, unit_type, author, created_at, json)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![unit.id, unit.unit_type.to_string(), unit.author, unit.created_at, json],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES ('cursor', ?1)",
+            params![cursor],
+        )
+        .map_err(|e| e.to_string())?;
+        tx.commit().map_err(|e| e.to_string())
+    }
+
+    /// Number of units held.
+    pub fn len(&self) -> Result<usize, String> {
+        self.conn
+            .query_row("SELECT COUNT(*) FROM units", [], |row| row.get::<_, i64>(0))
+            .map(|n| n as usize)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Every unit, in ascending id order.
+    pub fn all(&self) -> Result<Vec<SemanticUnit>, String> {
+        self.query(&LocalFilter::default()).map(|(units, _)| units)
+    }
+
+    /// Look up a single unit by id.
+    pub fn get(&self, id: &str) -> Result<Option<SemanticUnit>, String> {
+        match self.conn.query_row(
+            "SELECT json FROM units WHERE id = ?1",
+            params![id],
+            |row| row.get::<_, String>(0),
+        ) {
+            Ok(json) => serde_json::from_str(&json).map(Some).map_err(|e| e.to_string()),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Units matching `filter`, in ascending id order, plus whether more
+    /// matches exist beyond `filter.limit`.
+    pub fn query(&self, filter: &LocalFilter) -> Result<(Vec<SemanticUnit>, bool), String> {
+        // Narrow by the indexed columns in SQL; `LocalFilter::matches` then
+        // applies the full filter, including the timestamp comparison.
+        let mut sql = String::from("SELECT json FROM units WHERE id > ?1");
+        let mut args: Vec<String> = vec![filter.after.clone().unwrap_or_default()];
+        if let Some(ref author) = filter.author {
+            args.push(author.clone());
+            sql.push_str(&format!(" AND author = ?{}", args.len()));
+        }
+        if !filter.unit_types.is_empty() {
+            let placeholders: Vec<String> = filter
+                .unit_types
+                .iter()
+                .map(|t| {
+                    args.push(t.to_string());
+                    format!("?{}", args.len())
+                })
+                .collect();
+            sql.push_str(&format!(" AND unit_type IN ({})", placeholders.join(", ")));
+        }
+        sql.push_str(" ORDER BY id");
+
+        let mut stmt = self.conn.prepare(&sql).map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(args.iter()), |row| {
+                row.get::<_, String>(0)
+            })
+            .map_err(|e| e.to_string())?;
+
+        let mut units = Vec::new();
+        for json in rows {
+            let json = json.map_err(|e| e.to_string())?;
+            let unit: SemanticUnit = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+            if !filter.matches(&unit) {
+                continue;
+            }
+            if filter.limit.is_some_and(|l| units.len() == l) {
+                return Ok((units, true));
+            }
+            units.push(unit);
+        }
+        Ok((units, false))
+    }
+
+    fn meta(&self, key: &str) -> Result<Option<String>, String> {
+        match self.conn.query_row(
+            "SELECT value FROM meta WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        ) {
+            Ok(v) => Ok(Some(v)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    fn set_meta(&self, key: &str, value: &str) -> Result<(), String> {
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)",
+                params![key, value],
+            )
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// `GET /v1/units`-style filtering applied to local units.
+#[derive(Debug, Default)]
+pub struct LocalFilter {
+    /// Include only these types; empty means all.
+    pub unit_types: Vec<UnitType>,
+    /// Include only units by this author.
+    pub author: Option<String>,
+    /// Include only units created at or after this instant.
+    pub since: Option<DateTime<FixedOffset>>,
+    /// Include only units whose id sorts after this cursor.
+    pub after: Option<String>,
+    /// Maximum number of units to return; `None` means unlimited.
+    pub limit: Option<usize>,
+}
+
+impl LocalFilter {
+    /// `true` if `unit` passes every criterion except `limit`.
+    pub fn matches(&self, unit: &SemanticUnit) -> bool {
+        if !self.unit_types.is_empty() && !self.unit_types.contains(&unit.unit_type) {
+            return false;
+        }
+        if self.author.as_ref().is_some_and(|a| *a != unit.author) {
+            return false;
+        }
+        if self.after.as_ref().is_some_and(|a| unit.id.as_str() <= a.as_str()) {
+            return false;
+        }
+        if let Some(since) = self.since {
+            match DateTime::parse_from_rfc3339(&unit.created_at) {
+                Ok(created) if created >= since => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Apply the filter to in-memory units, returning matches in ascending id
+    /// order plus whether more exist beyond `limit`.
+    pub fn apply(&self, mut units: Vec<SemanticUnit>) -> (Vec<SemanticUnit>, bool) {
+        units.sort_by(|a, b| a.id.cmp(&b.id));
+        let mut matched: Vec<SemanticUnit> =
+            units.into_iter().filter(|u| self.matches(u)).collect();
+        let has_more = self.limit.is_some_and(|l| matched.len() > l);
+        if let Some(l) = self.limit {
+            matched.truncate(l);
+        }
+        (matched, has_more)
+    }
+}
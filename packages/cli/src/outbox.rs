@@ -0,0 +1,151 @@
+//! `sweft outbox` — a file-backed queue of units waiting for a node.
+//!
+//! The queue logic (reference ordering, back-off, what counts as delivered)
+//! is agent-core's [`Outbox`]; this module stores it as JSON and drives it
+//! against a node. The file is rewritten after every attempt, through a
+//! temporary file and a rename, so a crash or a dropped link never loses a
+//! unit or its retry schedule.
+//!
+//! `add` queues units, `flush` submits whatever is due (with `--wait`, it
+//! keeps going until nothing is left to retry), `list` shows the queue, and
+//! `retry` and `prune` requeue failures and drop delivered units.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use semanticweft::SemanticUnit;
+use semanticweft_agent_core::{Attempt, EntryState, Outbox};
+use semanticweft_client::{Client, Error};
+
+use crate::block_on;
+
+/// An [`Outbox`] loaded from, and saved back to, a JSON file.
+pub struct OutboxFile {
+    path: PathBuf,
+    pub outbox: Outbox,
+}
+
+impl OutboxFile {
+    /// Load the outbox at `path`; a missing file is an empty outbox.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let outbox = match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text)
+                .map_err(|e| format!("cannot parse outbox {}: {e}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Outbox::new(),
+            Err(e) => return Err(format!("cannot read outbox {}: {e}", path.display())),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            outbox,
+        })
+    }
+
+    /// Write the outbox back atomically.
+    pub fn save(&self) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("cannot create {}: {e}", parent.display()))?;
+        }
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let json = serde_json::to_string_pretty(&self.outbox).expect("serializable");
+        fs::write(&tmp, format!("{json}\n"))
+            .and_then(|()| fs::rename(&tmp, &self.path))
+            .map_err(|e| format!("cannot write outbox {}: {e}", self.path.display()))
+    }
+}
+
+/// Submit every due unit to `client`'s node, saving after each attempt.
+/// With `wait`, sleep until retries fall due and stop only when nothing
+/// queued is left; otherwise make one pass. Prints one line per attempt.
+pub fn flush(file: &mut OutboxFile, client: &Client, wait: bool) -> Result<(), String> {
+    loop {
+        let due: Vec<SemanticUnit> = file
+            .outbox
+            .due(SystemTime::now())
+            .into_iter()
+            .cloned()
+            .collect();
+        if due.is_empty() {
+            let Some(next) = file.outbox.next_due().filter(|_| wait) else {
+                return Ok(());
+            };
+            let pause = next
+                .duration_since(SystemTime::now())
+                .unwrap_or(Duration::ZERO);
+            eprintln!("sweft: waiting {}s for the next retry", pause.as_secs());
+            std::thread::sleep(pause);
+            continue;
+        }
+
+        for unit in due {
+            let attempt = submit(client, &unit);
+            let state = file
+                .outbox
+                .record(&unit.id, attempt, SystemTime::now())
+                .cloned();
+            file.save()?;
+            let entry = file.outbox.get(&unit.id).expect("recorded unit is queued");
+            match state {
+                Some(EntryState::Delivered) => println!("{}\tdelivered", unit.id),
+                Some(EntryState::Failed { status, reason }) => {
+                    println!("{}\tfailed\t{status} {reason}", unit.id)
+                }
+                _ => println!(
+                    "{}\tretry in {}s\t{}",
+                    unit.id,
+                    entry
+                        .due()
+                        .duration_since(SystemTime::now())
+                        .unwrap_or_default()
+                        .as_secs(),
+                    entry.last_error.as_deref().unwrap_or("")
+                ),
+            }
+        }
+    }
+}
+
+/// One submission, translated into the outbox's terms. The client does not
+/// retry on its own (see `main`), so a 429 reaches the outbox's schedule.
+fn submit(client: &Client, unit: &SemanticUnit) -> Attempt {
+    match block_on(client.submit(unit)) {
+        Ok(submitted) => Attempt::Response {
+            status: if submitted.created { 201 } else { 200 },
+            retry_after: None,
+            message: String::new(),
+        },
+        Err(Error::Api {
+            status, message, ..
+        }) => Attempt::Response {
+            status,
+            retry_after: None,
+            message,
+        },
+        Err(Error::RateLimited { retry_after }) => Attempt::Response {
+            status: 429,
+            retry_after,
+            message: "rate limited".into(),
+        },
+        Err(e) => Attempt::Unreachable(e.to_string()),
+    }
+}
+
+/// Print the queue, one unit per line: id, state, attempts, detail.
+pub fn list(outbox: &Outbox) {
+    for entry in outbox.entries() {
+        let (state, detail) = match &entry.state {
+            EntryState::Queued => ("queued", entry.last_error.clone().unwrap_or_default()),
+            EntryState::Delivered => ("delivered", String::new()),
+            EntryState::Failed { status, reason } => ("failed", format!("{status} {reason}")),
+            EntryState::Blocked { on } => ("blocked", format!("waiting on failed unit {on}")),
+        };
+        println!("{}\t{state}\t{}\t{detail}", entry.unit.id, entry.attempts);
+    }
+    let s = outbox.summary();
+    eprintln!(
+        "{} queued, {} delivered, {} failed, {} blocked",
+        s.queued, s.delivered, s.failed, s.blocked
+    );
+}
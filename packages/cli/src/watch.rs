@@ -0,0 +1,153 @@
+//! `sweft watch` — tail a node's public unit stream.
+//!
+//! Consumes `GET /v1/sync` in Server-Sent Events mode (spec §5.5.2). The id
+//! of the last unit seen is persisted to a cursor file after every event, so
+//! a restarted watch resumes exactly where the previous one stopped.
+//!
+//! The node's SSE replay covers only one page of history before switching to
+//! live delivery, and a consumer that falls behind the live channel receives
+//! an `event: lag` instead of the units it missed. In both cases the gap is
+//! closed the same way: poll `/v1/sync` in JSON mode from the saved cursor
+//! until `has_more` is false, then reconnect the stream with
+//! `Last-Event-ID` set to that cursor. That logic, and the SSE parsing, live
+//! in [`SyncCursor`]; the requests go through [`Client`], and this module
+//! only drives the two and does the printing.
+
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use semanticweft::{SemanticUnit, UnitType};
+use semanticweft_agent_core::{SyncCursor, SyncEvent, SyncRequest, DEFAULT_PAGE_LIMIT};
+use semanticweft_client::{Client, Error};
+
+use crate::block_on;
+
+/// Delay before reconnecting after the stream drops, unless the node asks
+/// for another with `retry:`.
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// Options for [`run`], mirroring the `watch` subcommand's flags.
+pub struct WatchOptions {
+    pub node: String,
+    pub unit_types: Vec<UnitType>,
+    pub author: Option<String>,
+    pub after: Option<String>,
+    pub cursor_file: PathBuf,
+    pub json: bool,
+}
+
+/// Watch the node until interrupted. Never returns normally.
+pub fn run(opts: WatchOptions) -> ! {
+    let client = crate::node_client(&opts.node, None);
+    let mut sync = SyncCursor::new(opts.after.clone().or_else(|| load_cursor(&opts.cursor_file)));
+
+    loop {
+        match sync.next_request(client.session()) {
+            SyncRequest::Poll { .. } => {
+                if let Err(e) = catch_up(&client, &mut sync, &opts) {
+                    eprintln!("sweft: catch-up failed: {e}; retrying");
+                    thread::sleep(RECONNECT_DELAY);
+                }
+            }
+            SyncRequest::Stream { last_event_id, .. } => {
+                match stream(&client, last_event_id, &mut sync, &opts) {
+                    Ok(StreamEnd::Lagged) => {
+                        eprintln!("sweft: fell behind the live stream; re-polling from cursor");
+                    }
+                    Ok(StreamEnd::Closed) => {
+                        eprintln!("sweft: stream closed by node; reconnecting");
+                        thread::sleep(sync.retry().unwrap_or(RECONNECT_DELAY));
+                    }
+                    Err(e) => {
+                        eprintln!("sweft: stream error: {e}; reconnecting");
+                        thread::sleep(sync.retry().unwrap_or(RECONNECT_DELAY));
+                    }
+                }
+                sync.on_disconnect();
+            }
+        }
+    }
+}
+
+/// Why a stream connection ended without an I/O error.
+enum StreamEnd {
+    /// The node sent `event: lag`.
+    Lagged,
+    /// The node closed the connection.
+    Closed,
+}
+
+/// Fetch one page of `/v1/sync` in JSON mode.
+fn catch_up(client: &Client, sync: &mut SyncCursor, opts: &WatchOptions) -> Result<(), Error> {
+    let page = block_on(client.sync_page(sync.cursor(), DEFAULT_PAGE_LIMIT))?;
+    handle(opts, sync.on_list(page));
+    Ok(())
+}
+
+/// Follow the SSE stream until it lags, closes, or fails.
+fn stream(
+    client: &Client,
+    last_event_id: Option<String>,
+    sync: &mut SyncCursor,
+    opts: &WatchOptions,
+) -> Result<StreamEnd, Error> {
+    let mut resp = block_on(client.sync_stream(last_event_id.as_deref()))?;
+    loop {
+        let Some(chunk) = block_on(resp.chunk())? else {
+            return Ok(StreamEnd::Closed);
+        };
+        let events = sync.on_chunk(&chunk);
+        let lagged = events.contains(&SyncEvent::Resync);
+        handle(opts, events);
+        if lagged {
+            return Ok(StreamEnd::Lagged);
+        }
+    }
+}
+
+/// Print the units that pass the filters and persist cursor moves.
+fn handle(opts: &WatchOptions, events: Vec<SyncEvent>) {
+    for event in events {
+        match event {
+            SyncEvent::Unit(unit) => print_unit(opts, &unit),
+            SyncEvent::Cursor(cursor) => save_cursor(&opts.cursor_file, &cursor),
+            SyncEvent::Malformed(e) => eprintln!("sweft: skipping malformed event: {e}"),
+            SyncEvent::Resync => {}
+        }
+    }
+}
+
+fn print_unit(opts: &WatchOptions, unit: &SemanticUnit) {
+    if !opts.unit_types.is_empty() && !opts.unit_types.contains(&unit.unit_type) {
+        return;
+    }
+    if opts.author.as_ref().is_some_and(|a| *a != unit.author) {
+        return;
+    }
+    if opts.json {
+        println!("{}", serde_json::to_string(unit).expect("serializable"));
+    } else {
+        println!("{}", semanticweft::render::render_unit(unit));
+    }
+}
+
+fn load_cursor(path: &PathBuf) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Persist the cursor, replacing the file atomically so a crash never
+/// leaves it truncated.
+fn save_cursor(path: &PathBuf, cursor: &str) {
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let tmp = path.with_extension("tmp");
+    if let Err(e) = fs::write(&tmp, cursor).and_then(|()| fs::rename(&tmp, path)) {
+        eprintln!("sweft: cannot save cursor to {}: {e}", path.display());
+    }
+}
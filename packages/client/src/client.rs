@@ -0,0 +1,629 @@
+//! The [`Client`] type.
+
+use std::future::Future;
+use std::time::{Duration, SystemTime};
+
+use futures_util::stream::{self, Stream, TryStreamExt};
+use reqwest::header::{HeaderMap, ACCEPT, CONTENT_TYPE, RETRY_AFTER};
+use reqwest::{Method, RequestBuilder, Response};
+use semanticweft::{Delegation, SemanticUnit};
+use semanticweft_agent_core::{
+    resolve_jrd, AgentAddress, AgentIdentity, NodeSession, ResolvedAgent, SignableRequest,
+};
+use semanticweft_node_api::{
+    AgentProfile, ApplyRequest, FollowEntry, FollowListResponse, FollowRequest, InboxResponse,
+    ListQuery, ListResponse, NodeInfo, RegisterRequest, SubgraphResponse, SuccessionStatement,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::Error;
+
+/// How the client reacts to `429 Too Many Requests`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries before giving up with [`Error::RateLimited`].
+    pub max_retries: u32,
+    /// Upper bound on a single wait, whatever `Retry-After` asks for.
+    pub max_wait: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            max_wait: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Whether [`Client::submit`] stored a new unit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submitted {
+    /// The unit as the node stored it.
+    pub unit: SemanticUnit,
+    /// `true` for 201 Created, `false` for the idempotent 200 on a unit the
+    /// node already held.
+    pub created: bool,
+}
+
+/// Optional fields of a membership application; see [`Client::apply`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Application {
+    /// Where the node delivers units; defaults to the node-hosted inbox.
+    pub inbox_url: Option<String>,
+    pub display_name: Option<String>,
+    /// A full member vouching for the applicant.
+    pub sponsor_did: Option<String>,
+}
+
+/// An async client for one node, acting as one agent.
+///
+/// With an identity, every request is signed with an HTTP Signature (the
+/// node ignores signatures on endpoints that do not need one); calls that
+/// require authentication fail with [`Error::NoIdentity`] without it.
+#[derive(Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    node_url: String,
+    session: NodeSession,
+    identity: Option<AgentIdentity>,
+    delegation: Option<String>,
+    retry: RetryPolicy,
+}
+
+impl Client {
+    /// A client for the node at `node_url` (its origin, e.g.
+    /// `https://node.example.com`), without fetching its discovery document.
+    pub fn new(node_url: &str, identity: Option<AgentIdentity>) -> Self {
+        let node_url = node_url.trim_end_matches('/').to_string();
+        let did = identity
+            .as_ref()
+            .map(AgentIdentity::did)
+            .unwrap_or_default();
+        Self {
+            http: reqwest::Client::new(),
+            session: NodeSession::new(format!("{node_url}/v1"), did),
+            node_url,
+            identity,
+            delegation: None,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Fetch the node's discovery document and build a client against the
+    /// `api_base` it advertises. Fails with [`Error::Session`] if the node
+    /// speaks an incompatible protocol version; check capabilities with
+    /// [`NodeSession::require`] on [`session`](Self::session).
+    pub async fn discover(node_url: &str, identity: Option<AgentIdentity>) -> Result<Self, Error> {
+        let probe = Self::new(node_url, identity);
+        let info = probe.node_info().await?;
+        let session = NodeSession::from_node_info(info, probe.session.own_did.clone())?;
+        let node_url = session.api_base.trim_end_matches("/v1").to_string();
+        Ok(Self {
+            session,
+            node_url,
+            ..probe
+        })
+    }
+
+    /// Use `http` for requests, e.g. to set timeouts or a proxy.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Act for `delegation.issuer` with the client's identity as the delegate
+    /// key (ADR-0014). Every request then carries the token in a signed
+    /// `Delegation` header, and the node treats the caller as the issuer
+    /// within the token's scope. [`did`](Self::did) and the session's URLs
+    /// refer to the issuer from then on.
+    pub fn with_delegation(mut self, delegation: Delegation) -> Self {
+        self.session.own_did = delegation.issuer.clone();
+        self.delegation = Some(serde_json::to_string(&delegation).expect("serializable"));
+        self
+    }
+
+    /// Replace the default [`RetryPolicy`].
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// The node's origin URL.
+    pub fn node_url(&self) -> &str {
+        &self.node_url
+    }
+
+    /// URL helpers for this node and identity.
+    pub fn session(&self) -> &NodeSession {
+        &self.session
+    }
+
+    /// The DID requests are signed as, if the client has an identity.
+    pub fn did(&self) -> Option<&str> {
+        self.identity
+            .as_ref()
+            .map(|_| self.session.own_did.as_str())
+    }
+
+    /// The discovery document, if the client was built with [`discover`](Self::discover).
+    pub fn discovered(&self) -> Option<&NodeInfo> {
+        self.session.node_info.as_ref()
+    }
+
+    // ── Discovery ────────────────────────────────────────────────────────────
+
+    /// `GET /.well-known/semanticweft`
+    pub async fn node_info(&self) -> Result<NodeInfo, Error> {
+        let url = format!("{}/.well-known/semanticweft", self.node_url);
+        self.call(Method::GET, url, None::<&()>).await
+    }
+
+    /// `GET /.well-known/semanticweft`, undecoded and whatever its status,
+    /// for diagnostics that look at the node's `Date` or `Content-Type`
+    /// headers or at why the document does not parse.
+    pub async fn node_info_response(&self) -> Result<Response, Error> {
+        let url = format!("{}/.well-known/semanticweft", self.node_url);
+        Ok(self.request(&Method::GET, &url, None)?.send().await?)
+    }
+
+    // ── Agents ───────────────────────────────────────────────────────────────
+
+    /// `POST /v1/agents/{did}` — register the client's identity.
+    pub async fn register(
+        &self,
+        inbox_url: &str,
+        display_name: Option<&str>,
+    ) -> Result<AgentProfile, Error> {
+        let identity = self.identity()?;
+        let body = RegisterRequest {
+            did: identity.did(),
+            inbox_url: inbox_url.to_string(),
+            display_name: display_name.map(str::to_string),
+            public_key: Some(identity.public_key_multibase()),
+        };
+        self.call(Method::POST, self.session.register_url(), Some(&body))
+            .await
+    }
+
+    /// `POST /v1/agents/{did}/apply` — apply for probationary membership.
+    pub async fn apply(&self, application: Application) -> Result<AgentProfile, Error> {
+        let identity = self.identity()?;
+        let body = ApplyRequest {
+            did: identity.did(),
+            inbox_url: application
+                .inbox_url
+                .unwrap_or_else(|| self.session.inbox_url()),
+            display_name: application.display_name,
+            public_key: Some(identity.public_key_multibase()),
+            sponsor_did: application.sponsor_did,
+        };
+        self.call(Method::POST, self.session.apply_url(), Some(&body))
+            .await
+    }
+
+    /// `GET /v1/agents/{did}`
+    pub async fn agent(&self, did: &str) -> Result<AgentProfile, Error> {
+        self.call(Method::GET, self.session.agent_url(did), None::<&()>)
+            .await
+    }
+
+    /// `GET /.well-known/webfinger` on this node — where it says `did`'s
+    /// profile and inbox are.
+    pub async fn webfinger(&self, did: &str) -> Result<ResolvedAgent, Error> {
+        self.resolve_at(self.session.webfinger_url(did), &self.session.address(did))
+            .await
+    }
+
+    /// Resolve `address` over WebFinger on the agent's own node, which need
+    /// not be this client's.
+    pub async fn resolve(&self, address: &AgentAddress) -> Result<ResolvedAgent, Error> {
+        self.resolve_at(address.webfinger_url(), address).await
+    }
+
+    async fn resolve_at(&self, url: String, address: &AgentAddress) -> Result<ResolvedAgent, Error> {
+        let (status, text) = self.send(Method::GET, url, None::<&()>).await?;
+        if !(200..300).contains(&status) {
+            return Err(Error::from_response(status, &text));
+        }
+        Ok(resolve_jrd(address, &text)?)
+    }
+
+    /// `POST /v1/agents/{did}/succession` — move the statement's predecessor
+    /// to its successor DID. The statement carries its own signatures (see
+    /// [`sign_succession`]); returns the successor's profile.
+    ///
+    /// [`sign_succession`]: semanticweft_agent_core::sign_succession
+    pub async fn rotate(&self, statement: &SuccessionStatement) -> Result<AgentProfile, Error> {
+        let url = self.session.succession_url(&statement.predecessor);
+        self.call(Method::POST, url, Some(statement)).await
+    }
+
+    /// `GET /v1/agents/{did}/succession` — the statement that retired `did`.
+    pub async fn succession(&self, did: &str) -> Result<SuccessionStatement, Error> {
+        self.call(Method::GET, self.session.succession_url(did), None::<&()>)
+            .await
+    }
+
+    // ── Units ────────────────────────────────────────────────────────────────
+
+    /// `POST /v1/units`
+    pub async fn submit(&self, unit: &SemanticUnit) -> Result<Submitted, Error> {
+        let (status, text) = self
+            .send(Method::POST, self.session.units_url(), Some(unit))
+            .await?;
+        let unit = decode(status, &text)?;
+        Ok(Submitted {
+            unit,
+            created: status == 201,
+        })
+    }
+
+    /// `GET /v1/units/{id}`
+    pub async fn get(&self, id: &str) -> Result<SemanticUnit, Error> {
+        self.call(Method::GET, self.session.unit_url(id), None::<&()>)
+            .await
+    }
+
+    /// `GET /v1/units` — one page of results.
+    pub async fn list_page(&self, query: &ListQuery) -> Result<ListResponse, Error> {
+        let url = format!("{}{}", self.session.units_url(), list_params(query));
+        self.call(Method::GET, url, None::<&()>).await
+    }
+
+    /// `GET /v1/units` — every matching unit, fetching pages as the stream
+    /// is polled. `query.limit` is the page size.
+    pub fn list(&self, query: ListQuery) -> impl Stream<Item = Result<SemanticUnit, Error>> + '_ {
+        let after = query.after.clone();
+        paginate(after, move |after| {
+            let query = ListQuery {
+                after,
+                ..query.clone()
+            };
+            async move {
+                let page = self.list_page(&query).await?;
+                Ok((page.units, page.cursor.filter(|_| page.has_more)))
+            }
+        })
+    }
+
+    /// `GET /v1/units/{id}/subgraph`
+    pub async fn subgraph(&self, id: &str, depth: Option<u32>) -> Result<Vec<SemanticUnit>, Error> {
+        let url = self.session.subgraph_url(id, depth);
+        let body: SubgraphResponse = self.call(Method::GET, url, None::<&()>).await?;
+        Ok(body.units)
+    }
+
+    /// `GET /v1/sync` — one page of up to `limit` public units after
+    /// `after`, oldest first.
+    pub async fn sync_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<ListResponse, Error> {
+        let url = self.session.sync_url(after, limit);
+        self.call(Method::GET, url, None::<&()>).await
+    }
+
+    /// `GET /v1/sync` — every public unit after `after`, oldest first.
+    pub fn sync(
+        &self,
+        after: Option<String>,
+    ) -> impl Stream<Item = Result<SemanticUnit, Error>> + '_ {
+        paginate(after, move |after| async move {
+            let page = self.sync_page(after.as_deref(), SYNC_PAGE).await?;
+            Ok((page.units, page.cursor.filter(|_| page.has_more)))
+        })
+    }
+
+    /// `GET /v1/sync` as a Server-Sent Events stream, resuming after
+    /// `last_event_id`. Read the body with [`Response::chunk`] and feed it
+    /// to a [`SyncCursor`](semanticweft_agent_core::SyncCursor).
+    pub async fn sync_stream(&self, last_event_id: Option<&str>) -> Result<Response, Error> {
+        let mut request = self
+            .request(&Method::GET, &self.session.sync_stream_url(), None)?
+            .header(ACCEPT, "text/event-stream");
+        if let Some(id) = last_event_id {
+            request = request.header("last-event-id", id);
+        }
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        if !(200..300).contains(&status) {
+            return Err(Error::from_response(status, &response.text().await?));
+        }
+        Ok(response)
+    }
+
+    // ── Follows ──────────────────────────────────────────────────────────────
+
+    /// `POST /v1/agents/{did}/following`
+    pub async fn follow(&self, target: &str) -> Result<(), Error> {
+        self.identity()?;
+        let body = FollowRequest {
+            target: target.to_string(),
+        };
+        let url = self.session.following_url(&self.session.own_did);
+        self.send_ok(Method::POST, url, Some(&body))
+            .await
+    }
+
+    /// `DELETE /v1/agents/{did}/following/{target}`
+    pub async fn unfollow(&self, target: &str) -> Result<(), Error> {
+        self.identity()?;
+        self.send_ok(
+            Method::DELETE,
+            self.session.unfollow_url(target),
+            None::<&()>,
+        )
+        .await
+    }
+
+    /// `GET /v1/agents/{did}/following`
+    pub fn following(&self, did: &str) -> impl Stream<Item = Result<FollowEntry, Error>> + '_ {
+        self.follow_list(self.session.following_url(did))
+    }
+
+    /// `GET /v1/agents/{did}/followers`
+    pub fn followers(&self, did: &str) -> impl Stream<Item = Result<FollowEntry, Error>> + '_ {
+        self.follow_list(self.session.followers_url(did))
+    }
+
+    fn follow_list(&self, url: String) -> impl Stream<Item = Result<FollowEntry, Error>> + '_ {
+        paginate(None, move |after| {
+            let url = with_after(&url, after.as_deref());
+            async move {
+                let page: FollowListResponse = self.call(Method::GET, url, None::<&()>).await?;
+                Ok((page.items, page.next_cursor))
+            }
+        })
+    }
+
+    // ── Inbox ────────────────────────────────────────────────────────────────
+
+    /// `GET /v1/agents/{did}/inbox` — one page.
+    pub async fn inbox_page(
+        &self,
+        after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Result<InboxResponse, Error> {
+        self.identity()?;
+        let mut url = with_after(&self.session.inbox_url(), after);
+        if let Some(l) = limit {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str(&format!("limit={l}"));
+        }
+        self.call(Method::GET, url, None::<&()>).await
+    }
+
+    /// `GET /v1/agents/{did}/inbox` — everything delivered after `after`.
+    pub fn inbox(
+        &self,
+        after: Option<String>,
+    ) -> impl Stream<Item = Result<SemanticUnit, Error>> + '_ {
+        paginate(after, move |after| async move {
+            let page = self.inbox_page(after.as_deref(), None).await?;
+            Ok((page.items, page.next_cursor))
+        })
+    }
+
+    // ── Transport ────────────────────────────────────────────────────────────
+
+    fn identity(&self) -> Result<&AgentIdentity, Error> {
+        self.identity.as_ref().ok_or(Error::NoIdentity)
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: String,
+        body: Option<&impl Serialize>,
+    ) -> Result<T, Error> {
+        let (status, text) = self.send(method, url, body).await?;
+        decode(status, &text)
+    }
+
+    async fn send_ok(
+        &self,
+        method: Method,
+        url: String,
+        body: Option<&impl Serialize>,
+    ) -> Result<(), Error> {
+        let (status, text) = self.send(method, url, body).await?;
+        if (200..300).contains(&status) {
+            Ok(())
+        } else {
+            Err(Error::from_response(status, &text))
+        }
+    }
+
+    /// Send a request, signing it when the client has an identity and
+    /// waiting out 429 responses. Returns the status and body of the first
+    /// response that is not a 429.
+    async fn send(
+        &self,
+        method: Method,
+        url: String,
+        body: Option<&impl Serialize>,
+    ) -> Result<(u16, String), Error> {
+        let body = body
+            .map(serde_json::to_vec)
+            .transpose()
+            .map_err(|e| Error::Decode(e.to_string()))?;
+        let mut attempt = 0;
+        loop {
+            // Re-signed on every attempt so the Date stays fresh.
+            let response = self.request(&method, &url, body.as_deref())?.send().await?;
+            let status = response.status().as_u16();
+            if status != 429 {
+                return Ok((status, response.text().await?));
+            }
+            let retry_after = retry_after(response.headers(), SystemTime::now());
+            if attempt >= self.retry.max_retries {
+                return Err(Error::RateLimited { retry_after });
+            }
+            let wait = retry_after.unwrap_or(Duration::from_secs(1 << attempt.min(6)));
+            tokio::time::sleep(wait.min(self.retry.max_wait)).await;
+            attempt += 1;
+        }
+    }
+
+    /// Build a request with a JSON `body`, signed when the client has an
+    /// identity.
+    fn request(
+        &self,
+        method: &Method,
+        url: &str,
+        body: Option<&[u8]>,
+    ) -> Result<RequestBuilder, Error> {
+        let mut request = self.http.request(method.clone(), url);
+        if let Some(identity) = &self.identity {
+            let mut signable = SignableRequest::from_url(method.as_str(), url)?;
+            if let Some(ref delegation) = self.delegation {
+                signable = signable.header("delegation", delegation.as_str());
+                request = request.header("delegation", delegation.as_str());
+            }
+            if let Some(b) = body {
+                signable = signable.body(b);
+            }
+            for (name, value) in signable.sign(identity, SystemTime::now()).pairs() {
+                request = request.header(name, value);
+            }
+        }
+        if let Some(b) = body {
+            request = request
+                .header(CONTENT_TYPE, "application/json")
+                .body(b.to_vec());
+        }
+        Ok(request)
+    }
+}
+
+/// Page size for [`Client::sync`].
+const SYNC_PAGE: usize = 500;
+
+fn decode<T: DeserializeOwned>(status: u16, text: &str) -> Result<T, Error> {
+    if !(200..300).contains(&status) {
+        return Err(Error::from_response(status, text));
+    }
+    serde_json::from_str(text).map_err(|e| Error::Decode(e.to_string()))
+}
+
+/// Turn a page fetcher into a stream of items. `fetch` gets the cursor of
+/// the page to load and returns its items and the next cursor, `None` on
+/// the last page.
+fn paginate<'a, T, F, Fut>(
+    first: Option<String>,
+    mut fetch: F,
+) -> impl Stream<Item = Result<T, Error>> + 'a
+where
+    T: 'a,
+    F: FnMut(Option<String>) -> Fut + 'a,
+    Fut: Future<Output = Result<(Vec<T>, Option<String>), Error>> + 'a,
+{
+    stream::try_unfold(Some(first), move |cursor| {
+        let page = cursor.map(&mut fetch);
+        async move {
+            match page {
+                None => Ok::<_, Error>(None),
+                Some(page) => {
+                    let (items, next) = page.await?;
+                    let items = stream::iter(items.into_iter().map(Ok::<T, Error>));
+                    Ok(Some((items, next.map(Some))))
+                }
+            }
+        }
+    })
+    .try_flatten()
+}
+
+/// The query string for a [`ListQuery`], including the leading `?`.
+fn list_params(query: &ListQuery) -> String {
+    let mut params: Vec<(&str, String)> = Vec::new();
+    if !query.unit_types.is_empty() {
+        params.push(("type", query.unit_types.join(",")));
+    }
+    let optional = [
+        ("author", &query.author),
+        ("since", &query.since),
+        ("subject", &query.subject),
+        ("predicate", &query.predicate),
+        ("after", &query.after),
+    ];
+    for (name, value) in optional {
+        if let Some(v) = value {
+            params.push((name, v.clone()));
+        }
+    }
+    if let Some(l) = query.limit {
+        params.push(("limit", l.to_string()));
+    }
+    params
+        .iter()
+        .enumerate()
+        .map(|(i, (k, v))| {
+            let sep = if i == 0 { '?' } else { '&' };
+            format!("{sep}{k}={}", urlencoding::encode(v))
+        })
+        .collect()
+}
+
+fn with_after(url: &str, after: Option<&str>) -> String {
+    match after {
+        Some(a) => format!("{url}?after={}", urlencoding::encode(a)),
+        None => url.to_string(),
+    }
+}
+
+/// The wait a `Retry-After` header asks for: delta-seconds or an HTTP date.
+fn retry_after(headers: &HeaderMap, now: SystemTime) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(at.duration_since(now).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn list_params_encodes_every_filter() {
+        let query = ListQuery {
+            unit_types: vec!["assertion".into(), "inference".into()],
+            author: Some("did:key:z6MkA".into()),
+            limit: Some(10),
+            ..ListQuery::default()
+        };
+        assert_eq!(
+            list_params(&query),
+            "?type=assertion%2Cinference&author=did%3Akey%3Az6MkA&limit=10"
+        );
+        assert_eq!(list_params(&ListQuery::default()), "");
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_dates() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_771_329_600);
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers, now), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(7)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Tue, 17 Feb 2026 12:00:30 GMT"),
+        );
+        assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(30)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Tue, 17 Feb 2026 11:00:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers, now), Some(Duration::ZERO));
+    }
+}
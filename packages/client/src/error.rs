@@ -0,0 +1,170 @@
+//! Client errors, with node error responses mapped to typed codes.
+
+use std::time::Duration;
+
+use semanticweft_agent_core::{SessionError, SignatureError, WebFingerError};
+use semanticweft_node_api::ErrorResponse;
+use thiserror::Error;
+
+/// The `code` of a node [`ErrorResponse`] (spec §4.2), plus the codes this
+/// node implementation uses for authentication and authorisation failures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorCode {
+    InvalidJson,
+    InvalidParameter,
+    SigningRequired,
+    Unauthorized,
+    /// The request carried no `Signature` or `Date` header.
+    SignatureMissing,
+    /// The `Date` header fell outside the node's replay window.
+    DateOutOfRange,
+    /// The signing key is not registered on the node.
+    UnknownAgent,
+    /// The signature did not verify.
+    SignatureInvalid,
+    Forbidden,
+    NotFound,
+    IdConflict,
+    ValidationFailed,
+    PowRequired,
+    RateLimitExceeded,
+    InternalError,
+    /// A code this client does not know, kept verbatim.
+    Other(String),
+}
+
+impl ErrorCode {
+    /// Map a wire code to its variant.
+    pub fn parse(code: &str) -> Self {
+        use semanticweft_node_api::error::codes;
+        match code {
+            codes::INVALID_JSON => Self::InvalidJson,
+            codes::INVALID_PARAMETER => Self::InvalidParameter,
+            codes::SIGNING_REQUIRED => Self::SigningRequired,
+            codes::UNAUTHORIZED => Self::Unauthorized,
+            codes::SIGNATURE_MISSING => Self::SignatureMissing,
+            codes::DATE_OUT_OF_RANGE => Self::DateOutOfRange,
+            codes::UNKNOWN_AGENT => Self::UnknownAgent,
+            codes::SIGNATURE_INVALID => Self::SignatureInvalid,
+            "forbidden" => Self::Forbidden,
+            codes::NOT_FOUND => Self::NotFound,
+            codes::ID_CONFLICT => Self::IdConflict,
+            codes::VALIDATION_FAILED => Self::ValidationFailed,
+            codes::POW_REQUIRED => Self::PowRequired,
+            codes::RATE_LIMIT_EXCEEDED => Self::RateLimitExceeded,
+            codes::INTERNAL_ERROR => Self::InternalError,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// The code a response without a parseable body most likely carried.
+    fn from_status(status: u16) -> Self {
+        match status {
+            400 => Self::InvalidParameter,
+            401 => Self::Unauthorized,
+            403 => Self::Forbidden,
+            404 => Self::NotFound,
+            409 => Self::IdConflict,
+            422 => Self::ValidationFailed,
+            428 => Self::PowRequired,
+            429 => Self::RateLimitExceeded,
+            500..=599 => Self::InternalError,
+            _ => Self::Other(status.to_string()),
+        }
+    }
+}
+
+/// Everything a [`Client`](crate::Client) call can fail with.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The node answered with an error status.
+    #[error("node returned {status} ({code:?}): {message}")]
+    Api {
+        status: u16,
+        code: ErrorCode,
+        message: String,
+    },
+
+    /// Still rate limited after the configured number of retries.
+    #[error("rate limited by the node (retry after {retry_after:?})")]
+    RateLimited { retry_after: Option<Duration> },
+
+    /// The request needs an identity but the client has none.
+    #[error("this request must be signed; create the client with an identity")]
+    NoIdentity,
+
+    #[error("request failed: {0}")]
+    Transport(#[from] reqwest::Error),
+
+    #[error(transparent)]
+    Url(#[from] SignatureError),
+
+    /// A discovery document this client cannot work with.
+    #[error(transparent)]
+    Session(#[from] SessionError),
+
+    /// A WebFinger response that does not describe the requested agent.
+    #[error(transparent)]
+    WebFinger(#[from] WebFingerError),
+
+    /// A response body that does not match the node API types.
+    #[error("malformed response from node: {0}")]
+    Decode(String),
+}
+
+impl Error {
+    /// Build an [`Error::Api`] from a status and the raw response body.
+    pub(crate) fn from_response(status: u16, body: &str) -> Self {
+        match serde_json::from_str::<ErrorResponse>(body) {
+            Ok(e) => Error::Api {
+                status,
+                code: ErrorCode::parse(&e.code),
+                message: e.error,
+            },
+            Err(_) => Error::Api {
+                status,
+                code: ErrorCode::from_status(status),
+                message: body.trim().to_string(),
+            },
+        }
+    }
+
+    /// The node's error code, for [`Error::Api`].
+    pub fn code(&self) -> Option<&ErrorCode> {
+        match self {
+            Error::Api { code, .. } => Some(code),
+            _ => None,
+        }
+    }
+
+    /// `true` for a 404 from the node.
+    pub fn is_not_found(&self) -> bool {
+        self.code() == Some(&ErrorCode::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_error_response_codes() {
+        let e = Error::from_response(409, r#"{"error":"different content","code":"id_conflict"}"#);
+        assert_eq!(e.code(), Some(&ErrorCode::IdConflict));
+        assert_eq!(
+            e.to_string(),
+            "node returned 409 (IdConflict): different content"
+        );
+
+        let e = Error::from_response(418, r#"{"error":"x","code":"teapot"}"#);
+        assert_eq!(e.code(), Some(&ErrorCode::Other("teapot".into())));
+    }
+
+    #[test]
+    fn falls_back_to_the_status_without_a_body() {
+        let e = Error::from_response(404, "");
+        assert!(e.is_not_found());
+        let e = Error::from_response(502, "<html>bad gateway</html>");
+        assert_eq!(e.code(), Some(&ErrorCode::InternalError));
+    }
+}
@@ -0,0 +1,39 @@
+//! Typed async client for the SemanticWeft node API.
+//!
+//! [`Client`] wraps `reqwest` around the pure logic in
+//! `semanticweft-agent-core`: URLs come from a [`NodeSession`], and every
+//! request is signed with the client's [`AgentIdentity`] through
+//! [`SignableRequest`]. On top of that it
+//!
+//! - decodes responses into the `semanticweft-node-api` types,
+//! - turns error responses into [`Error::Api`] with a typed [`ErrorCode`],
+//! - waits out `429 Too Many Requests`, honouring `Retry-After` (see
+//!   [`RetryPolicy`]), and
+//! - exposes paginated endpoints as streams that follow the cursor as they
+//!   are polled.
+//!
+//! ```no_run
+//! use futures_util::TryStreamExt;
+//! use semanticweft_agent_core::AgentIdentity;
+//! use semanticweft_client::Client;
+//!
+//! # async fn run() -> Result<(), semanticweft_client::Error> {
+//! let identity = AgentIdentity::generate();
+//! let client = Client::discover("https://node.example.com", Some(identity)).await?;
+//! client.apply(Default::default()).await?;
+//!
+//! let units: Vec<_> = client.sync(None).try_collect().await?;
+//! println!("{} public units", units.len());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`NodeSession`]: semanticweft_agent_core::NodeSession
+//! [`AgentIdentity`]: semanticweft_agent_core::AgentIdentity
+//! [`SignableRequest`]: semanticweft_agent_core::SignableRequest
+
+mod client;
+mod error;
+
+pub use client::{Application, Client, RetryPolicy, Submitted};
+pub use error::{Error, ErrorCode};
@@ -0,0 +1,301 @@
+//! End-to-end tests of [`Client`] against an in-process node.
+
+use futures_util::TryStreamExt;
+use semanticweft::{SemanticUnit, UnitType, Visibility};
+use semanticweft_agent_core::AgentIdentity;
+use semanticweft_client::{Application, Client, Error, ErrorCode};
+use semanticweft_conformance::spawn_node;
+use semanticweft_node_api::{Capability, ListQuery};
+
+fn unit(author: &str, content: &str) -> SemanticUnit {
+    SemanticUnit::new(UnitType::Assertion, content, author)
+}
+
+async fn member(base: &str) -> Client {
+    let client = Client::discover(base, Some(AgentIdentity::generate()))
+        .await
+        .unwrap();
+    client.apply(Application::default()).await.unwrap();
+    client
+}
+
+#[tokio::test]
+async fn discovers_applies_and_submits() {
+    let (base, _storage) = spawn_node().await;
+    let client = member(&base).await;
+    let did = client.did().unwrap().to_string();
+    assert_eq!(client.discovered().unwrap().api_base, base);
+    assert_eq!(client.session().api_base, format!("{base}/v1"));
+    assert_eq!(
+        client.session().require(&[Capability::Sync, Capability::Agents]),
+        Ok(())
+    );
+
+    let profile = client.agent(&did).await.unwrap();
+    assert_eq!(profile.did, did);
+
+    let u = unit(&did, "The cache embeds the build hash.");
+    let first = client.submit(&u).await.unwrap();
+    assert!(first.created);
+    assert_eq!(first.unit.id, u.id);
+    let again = client.submit(&u).await.unwrap();
+    assert!(
+        !again.created,
+        "resubmitting identical content is idempotent"
+    );
+
+    assert_eq!(client.get(&u.id).await.unwrap(), u);
+}
+
+#[tokio::test]
+async fn maps_error_responses() {
+    let (base, _storage) = spawn_node().await;
+    let client = member(&base).await;
+
+    let err = client
+        .get("019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6c")
+        .await
+        .unwrap_err();
+    assert!(err.is_not_found(), "{err}");
+
+    let conflict = client.apply(Application::default()).await.unwrap_err();
+    assert!(
+        matches!(conflict, Error::Api { status: 409, .. }),
+        "{conflict}"
+    );
+
+    let anonymous = Client::new(&base, None);
+    assert!(matches!(
+        anonymous.follow("did:key:z6MkAnyone").await,
+        Err(Error::NoIdentity)
+    ));
+
+    let bad = unit(client.did().unwrap(), "");
+    let err = client.submit(&bad).await.unwrap_err();
+    assert_eq!(err.code(), Some(&ErrorCode::ValidationFailed), "{err}");
+}
+
+#[tokio::test]
+async fn list_and_sync_follow_the_cursor() {
+    let (base, _storage) = spawn_node().await;
+    let client = member(&base).await;
+    let did = client.did().unwrap().to_string();
+
+    let mut ids = Vec::new();
+    for i in 0..5 {
+        let u = unit(&did, &format!("claim {i}"));
+        client.submit(&u).await.unwrap();
+        ids.push(u.id);
+    }
+
+    let query = ListQuery {
+        author: Some(did.clone()),
+        limit: Some(2),
+        ..ListQuery::default()
+    };
+    let page = client.list_page(&query).await.unwrap();
+    assert_eq!(page.units.len(), 2);
+    assert!(page.has_more);
+
+    let listed: Vec<String> = client
+        .list(query)
+        .map_ok(|u| u.id)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(listed, ids);
+
+    let synced: Vec<String> = client
+        .sync(Some(ids[1].clone()))
+        .map_ok(|u| u.id)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(synced, ids[2..]);
+
+    let page = client.sync_page(Some(&ids[1]), 2).await.unwrap();
+    let paged: Vec<&str> = page.units.iter().map(|u| u.id.as_str()).collect();
+    assert_eq!(paged, [ids[2].as_str(), ids[3].as_str()]);
+    assert!(page.has_more);
+
+    let mut stream = client.sync_stream(Some(&ids[3])).await.unwrap();
+    let mut received = String::new();
+    while !received.contains(&format!("id: {}", ids[4])) {
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), stream.chunk())
+            .await
+            .expect("stream replays history")
+            .unwrap()
+            .expect("stream stays open");
+        received.push_str(&String::from_utf8_lossy(&chunk));
+    }
+    assert!(!received.contains(&format!("id: {}", ids[3])));
+}
+
+#[tokio::test]
+async fn subgraph_returns_the_neighbourhood() {
+    let (base, _storage) = spawn_node().await;
+    let client = member(&base).await;
+    let did = client.did().unwrap().to_string();
+
+    let premise = unit(&did, "Premise.");
+    let mut conclusion = SemanticUnit::new(UnitType::Inference, "Conclusion.", did.as_str());
+    conclusion.references = Some(vec![semanticweft::Reference {
+        id: premise.id.clone(),
+        rel: semanticweft::RelType::DerivesFrom,
+    }]);
+    client.submit(&premise).await.unwrap();
+    client.submit(&conclusion).await.unwrap();
+
+    let units = client.subgraph(&conclusion.id, Some(1)).await.unwrap();
+    let mut ids: Vec<&str> = units.iter().map(|u| u.id.as_str()).collect();
+    ids.sort();
+    let mut expected = [premise.id.as_str(), conclusion.id.as_str()];
+    expected.sort();
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+async fn follows_and_reads_the_inbox() {
+    let (base, _storage) = spawn_node().await;
+    let alice = member(&base).await;
+    let bob = member(&base).await;
+    let alice_did = alice.did().unwrap().to_string();
+    let bob_did = bob.did().unwrap().to_string();
+
+    alice.follow(&bob_did).await.unwrap();
+    let following: Vec<String> = alice
+        .following(&alice_did)
+        .map_ok(|e| e.did)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(following, [bob_did.as_str()]);
+    let followers: Vec<String> = bob
+        .followers(&bob_did)
+        .map_ok(|e| e.did)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(followers, [alice_did.as_str()]);
+
+    let mut note = unit(&bob_did, "For followers.");
+    note.visibility = Some(Visibility::Network);
+    bob.submit(&note).await.unwrap();
+    let inbox: Vec<String> = alice
+        .inbox(None)
+        .map_ok(|u| u.id)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(inbox, [note.id]);
+
+    alice.unfollow(&bob_did).await.unwrap();
+    let following: Vec<_> = alice
+        .following(&alice_did)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert!(following.is_empty());
+}
+
+#[tokio::test]
+async fn delegate_submits_for_the_root() {
+    use semanticweft::DelegationScope;
+    use std::time::{Duration, SystemTime};
+
+    let (base, _storage) = spawn_node().await;
+    let root = AgentIdentity::generate();
+    let root_client = Client::discover(&base, Some(root.clone())).await.unwrap();
+    root_client.apply(Application::default()).await.unwrap();
+
+    let worker = AgentIdentity::generate();
+    let now = SystemTime::now();
+    let token = root.delegate(
+        worker.did(),
+        DelegationScope {
+            endpoints: vec!["POST /v1/units".into()],
+            ..Default::default()
+        },
+        now,
+        now + Duration::from_secs(600),
+    );
+    let client = Client::discover(&base, Some(worker))
+        .await
+        .unwrap()
+        .with_delegation(token);
+    assert_eq!(client.did(), Some(root.did().as_str()));
+
+    let mut note = unit(&root.did(), "Posted by a worker.");
+    note.visibility = Some(Visibility::Network);
+    assert!(client.submit(&note).await.unwrap().created);
+
+    let err = client.inbox(None).try_collect::<Vec<_>>().await.unwrap_err();
+    assert!(matches!(err, Error::Api { status: 401, .. }), "{err}");
+}
+
+#[tokio::test]
+async fn delegate_follows_as_the_root() {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+    // The node refuses delegates on follow endpoints whatever the scope, so
+    // a bare listener records the request line instead.
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let request_line = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut first = String::new();
+        reader.read_line(&mut first).await.unwrap();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).await.unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).await.unwrap();
+        reader
+            .into_inner()
+            .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        first.trim_end().to_string()
+    });
+
+    let root = AgentIdentity::generate();
+    let worker = AgentIdentity::generate();
+    let now = std::time::SystemTime::now();
+    let token = root.delegate(
+        worker.did(),
+        Default::default(),
+        now,
+        now + std::time::Duration::from_secs(600),
+    );
+    let client = Client::new(&base, Some(worker)).with_delegation(token);
+    client.follow("did:key:z6MkTarget").await.unwrap();
+
+    let url = client.session().following_url(&root.did());
+    let path = url.strip_prefix(&base).unwrap();
+    assert_eq!(request_line.await.unwrap(), format!("POST {path} HTTP/1.1"));
+}
+
+#[tokio::test]
+async fn webfinger_resolves_advertised_inbox() {
+    let (base, _storage) = spawn_node().await;
+    let client = member(&base).await;
+    let did = client.did().unwrap().to_string();
+
+    let agent = client.webfinger(&did).await.unwrap();
+    assert_eq!(agent.profile_url, client.session().agent_url(&did));
+    assert_eq!(agent.inbox_url, client.session().inbox_url());
+
+    let missing = client.webfinger("did:key:z6MkNobody").await.unwrap_err();
+    assert!(missing.is_not_found(), "{missing}");
+}
@@ -46,6 +46,9 @@
 //! | `delete_agent_returns_204` | §8.3 DELETE agent |
 //! | `delete_unknown_agent_returns_404` | §8.3 DELETE agent |
 //! | `register_agent_wrong_did_returns_403` | §8.1 agents auth |
+//! | `apply_admits_unregistered_agent_as_probationary` | §8.1 self-service apply |
+//! | `succession_rotates_agent_to_new_did` | §8.7 key rotation |
+//! | `delegated_key_submits_for_root_within_scope` | §8.8 delegated keys |
 //! | `delete_agent_unauthenticated_returns_401` | §8.3 DELETE agent auth |
 //! | `delete_agent_wrong_did_returns_403` | §8.3 DELETE agent auth |
 //! | `follow_and_list` | §8.5 follows |
@@ -62,6 +65,12 @@
 //! | `update_reputation_below_threshold_returns_403` | §7 community gate |
 //! | `new_community_all_same_rep_allows_vote` | §7 community gate |
 //! | `reputation_update_is_weighted_by_caller_rep` | §7 weighted update |
+//! | `questions_filter_by_status` | §5.6 questions |
+//! | `questions_filter_by_referenced_type_and_reject_bad_status` | §5.6 questions |
+//! | `similar_returns_near_duplicates_only` | §5.7 similar |
+//! | `list_units_filter_by_triple_pattern` | §4.4 triple filters |
+//! | `submit_malformed_triples_returns_422` | §5.1 validation |
+//! | `subgraph_includes_network_units_for_followers_only` | §5.4/9.5 subgraph visibility |
 
 use semanticweft::{RelType, Reference, SemanticUnit, UnitType, Visibility};
 use semanticweft_conformance::spawn_node;
@@ -125,6 +134,8 @@ async fn seed_agent(
             status: semanticweft_node_api::AgentStatus::Full,
             contribution_count: 0,
             reputation: 0.5,
+            predecessor: None,
+            successor: None,
         })
         .await
         .expect("seed agent");
@@ -406,6 +417,80 @@ async fn subgraph_traversal() {
     assert!(ids.contains(&child_id.as_str()), "subgraph must include referencing child");
 }
 
+/// Spec §5.4/§9.5: the subgraph applies the same visibility rules as a
+/// single-unit fetch — a `network` unit appears only for an authenticated
+/// follower of its author.
+#[tokio::test]
+async fn subgraph_includes_network_units_for_followers_only() {
+    let (base, storage) = spawn_node().await;
+    let client = make_client();
+    let addr = base.strip_prefix("http://").unwrap_or(&base);
+
+    let (author_key, author_did, author_pubkey) = make_agent_key();
+    seed_agent(&storage, &author_did, &format!("{base}/v1/agents/{author_did}/inbox"), &author_pubkey).await;
+    let (follower_key, follower_did, follower_pubkey) = make_agent_key();
+    seed_agent(&storage, &follower_did, &format!("{base}/v1/agents/{follower_did}/inbox"), &follower_pubkey).await;
+    storage.add_follow(&follower_did, &author_did).await.unwrap();
+
+    let root = SemanticUnit::new(UnitType::Assertion, "public root", &author_did);
+    let root_id = root.id.clone();
+    client.post(format!("{base}/v1/units")).json(&root).send().await.unwrap();
+
+    let mut reply = SemanticUnit::new(UnitType::Inference, "network-only reply", &author_did);
+    reply.visibility = Some(Visibility::Network);
+    reply.references = Some(vec![semanticweft::Reference {
+        id: root_id.clone(),
+        rel: semanticweft::RelType::DerivesFrom,
+    }]);
+    let reply_id = reply.id.clone();
+    let (date, sig) = http_sig(&author_key, &author_did, "post", "/v1/units", addr);
+    let resp = client
+        .post(format!("{base}/v1/units"))
+        .header("host", addr)
+        .header("date", &date)
+        .header("signature", &sig)
+        .json(&reply)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+
+    let path = format!("/v1/units/{root_id}/subgraph");
+    let ids = |body: Value| -> Vec<String> {
+        body["units"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|u| u["id"].as_str().map(String::from))
+            .collect()
+    };
+
+    let resp = client.get(format!("{base}{path}")).send().await.unwrap();
+    assert_eq!(resp.status(), 200);
+    let anonymous = ids(resp.json().await.unwrap());
+    assert!(anonymous.contains(&root_id));
+    assert!(
+        !anonymous.contains(&reply_id),
+        "network unit must not appear in an unauthenticated subgraph"
+    );
+
+    let (date, sig) = http_sig(&follower_key, &follower_did, "get", &path, addr);
+    let resp = client
+        .get(format!("{base}{path}"))
+        .header("host", addr)
+        .header("date", &date)
+        .header("signature", &sig)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let followed = ids(resp.json().await.unwrap());
+    assert!(
+        followed.contains(&reply_id),
+        "network unit must appear in a follower's subgraph"
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Sync — §6
 // ---------------------------------------------------------------------------
@@ -592,8 +677,7 @@ async fn delete_unknown_agent_returns_404() {
     let client = make_client();
     let addr = base.strip_prefix("http://").unwrap_or(&base);
 
-    let (key, did, pubkey) = make_agent_key();
-    let path = format!("/v1/agents/{did}");
+    let (_key, did, pubkey) = make_agent_key();
 
     // Seed just for auth — but the agent path uses a different DID; actually
     // we need the signer to exist in storage (RequireAuth looks up by keyId).
@@ -722,6 +806,219 @@ async fn register_agent_wrong_did_returns_403() {
     assert_eq!(resp.status(), 403, "registering as a different DID should return 403");
 }
 
+/// Spec §8.1: an unregistered agent can apply with a signature over its own
+/// `did:key`, is admitted as probationary, and can then authenticate normally.
+#[tokio::test]
+async fn apply_admits_unregistered_agent_as_probationary() {
+    let (base, _storage) = spawn_node().await;
+    let client = make_client();
+    let addr = base.strip_prefix("http://").unwrap_or(&base);
+
+    let (key, did, pubkey) = make_agent_key();
+    let path = format!("/v1/agents/{did}/apply");
+    let (date, sig) = http_sig(&key, &did, "post", &path, addr);
+    let body = serde_json::json!({
+        "did": did,
+        "inbox_url": format!("{base}/v1/agents/{did}/inbox"),
+    });
+
+    let resp = client
+        .post(format!("{base}{path}"))
+        .header("host", addr)
+        .header("date", &date)
+        .header("signature", &sig)
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201, "apply should return 201");
+    let profile: Value = resp.json().await.unwrap();
+    assert_eq!(profile["status"], "probationary");
+    assert_eq!(profile["contribution_count"], 0);
+    assert_eq!(profile["public_key"].as_str().unwrap(), pubkey);
+
+    // A second application conflicts.
+    let (date, sig) = http_sig(&key, &did, "post", &path, addr);
+    let resp = client
+        .post(format!("{base}{path}"))
+        .header("host", addr)
+        .header("date", &date)
+        .header("signature", &sig)
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 409);
+
+    // The admitted agent now passes registered-agent auth.
+    let inbox_path = format!("/v1/agents/{did}/inbox");
+    let (date, sig) = http_sig(&key, &did, "get", &inbox_path, addr);
+    let resp = client
+        .get(format!("{base}{inbox_path}"))
+        .header("host", addr)
+        .header("date", &date)
+        .header("signature", &sig)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let info: Value = client
+        .get(format!("{base}/.well-known/semanticweft"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(
+        info["probation_threshold"].as_u64().is_some(),
+        "well-known must advertise probation_threshold"
+    );
+}
+
+#[tokio::test]
+async fn succession_rotates_agent_to_new_did() {
+    use semanticweft_agent_core::{sign_succession, AgentIdentity};
+
+    let (base, storage) = spawn_node().await;
+    let client = make_client();
+
+    let old = AgentIdentity::generate();
+    let new = AgentIdentity::generate();
+    let old_did = old.did();
+    seed_agent(
+        &storage,
+        &old_did,
+        &format!("{base}/v1/agents/{old_did}/inbox"),
+        &old.public_key_multibase(),
+    )
+    .await;
+    let (_, fan_did, fan_pubkey) = make_agent_key();
+    seed_agent(&storage, &fan_did, &format!("{base}/v1/agents/{fan_did}/inbox"), &fan_pubkey).await;
+    storage.add_follow(&fan_did, &old_did).await.unwrap();
+
+    let statement = sign_succession(&old, &new, "2026-03-01T12:00:00Z", Some("rotation".into()));
+    let resp = client
+        .post(format!("{base}/v1/agents/{old_did}/succession"))
+        .json(&statement)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201, "succession should return 201");
+    let profile: Value = resp.json().await.unwrap();
+    assert_eq!(profile["did"].as_str(), Some(new.did().as_str()));
+    assert_eq!(profile["predecessor"].as_str(), Some(old_did.as_str()));
+
+    // The old profile points forward, and the statement is published.
+    let retired: Value = client
+        .get(format!("{base}/v1/agents/{old_did}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(retired["successor"].as_str(), Some(new.did().as_str()));
+    let published: Value = client
+        .get(format!("{base}/v1/agents/{old_did}/succession"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(published, serde_json::to_value(&statement).unwrap());
+
+    // Followers moved with the agent.
+    let body: Value = client
+        .get(format!("{base}/v1/agents/{}/followers", new.did()))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["items"][0]["did"].as_str(), Some(fan_did.as_str()));
+
+    // A statement whose countersignature does not match is rejected.
+    let mut forged = sign_succession(&new, &AgentIdentity::generate(), "2026-03-01T12:00:00Z", None);
+    forged.successor_signature = statement.successor_signature.clone();
+    let resp = client
+        .post(format!("{base}/v1/agents/{}/succession", new.did()))
+        .json(&forged)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+}
+
+#[tokio::test]
+async fn delegated_key_submits_for_root_within_scope() {
+    use semanticweft::DelegationScope;
+    use semanticweft_agent_core::{AgentIdentity, SignableRequest};
+    use std::time::{Duration, SystemTime};
+
+    let (base, storage) = spawn_node().await;
+    let client = make_client();
+
+    let root = AgentIdentity::generate();
+    let root_did = root.did();
+    seed_agent(
+        &storage,
+        &root_did,
+        &format!("{base}/v1/agents/{root_did}/inbox"),
+        &root.public_key_multibase(),
+    )
+    .await;
+    let worker = AgentIdentity::generate();
+    let now = SystemTime::now();
+    let token = root.delegate(
+        worker.did(),
+        DelegationScope {
+            unit_types: vec![UnitType::Assertion],
+            endpoints: vec!["POST /v1/units".into()],
+            ..Default::default()
+        },
+        now - Duration::from_secs(60),
+        now + Duration::from_secs(3600),
+    );
+    let token_json = serde_json::to_string(&token).unwrap();
+
+    let submit = |unit: &SemanticUnit| {
+        let body = serde_json::to_vec(unit).unwrap();
+        let url = format!("{base}/v1/units");
+        let signed = SignableRequest::from_url("POST", &url)
+            .unwrap()
+            .header("delegation", token_json.as_str())
+            .body(&body)
+            .sign(&worker, SystemTime::now());
+        let mut req = client
+            .post(url)
+            .header("content-type", "application/json")
+            .header("delegation", token_json.as_str());
+        for (name, value) in signed.pairs() {
+            req = req.header(name, value);
+        }
+        req.body(body).send()
+    };
+
+    // The worker signs both the request and the unit; both count as the root's.
+    let mut unit = SemanticUnit::new(UnitType::Assertion, "Posted by a worker.", &root_did);
+    unit.visibility = Some(Visibility::Network);
+    worker.sign_unit_delegated(&mut unit, token.clone()).unwrap();
+    let resp = submit(&unit).await.unwrap();
+    assert_eq!(resp.status(), 201, "delegated submission should return 201");
+    let stored: Value = resp.json().await.unwrap();
+    assert_eq!(stored["proof"]["delegation"]["issuer"].as_str(), Some(root_did.as_str()));
+
+    // Unit types outside the token's scope are refused.
+    let mut question = SemanticUnit::new(UnitType::Question, "Out of scope?", &root_did);
+    question.visibility = Some(Visibility::Network);
+    let resp = submit(&question).await.unwrap();
+    assert_eq!(resp.status(), 403);
+}
+
 // ---------------------------------------------------------------------------
 // Follows — §9
 // ---------------------------------------------------------------------------
@@ -1221,6 +1518,8 @@ async fn well_known_includes_capabilities() {
     assert!(cap_strs.contains(&"agents"), "capabilities must include 'agents'");
     assert!(cap_strs.contains(&"follows"), "capabilities must include 'follows'");
     assert!(cap_strs.contains(&"peers"), "capabilities must include 'peers'");
+    assert!(cap_strs.contains(&"questions"), "capabilities must include 'questions'");
+    assert!(cap_strs.contains(&"similar"), "capabilities must include 'similar'");
 
     // protocol_version is required by the spec.
     assert!(
@@ -1588,3 +1887,224 @@ async fn subgraph_respects_depth_limit() {
     assert!(depth2_ids.contains(&b_id.as_str()), "B must be in depth=2 subgraph");
     assert!(depth2_ids.contains(&c_id.as_str()), "grandchild C must be in depth=2 subgraph");
 }
+
+// ---------------------------------------------------------------------------
+// Questions — §5.6
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn questions_filter_by_status() {
+    let (base, _storage) = spawn_node().await;
+    let client = make_client();
+
+    let open = SemanticUnit::new(UnitType::Question, "Still open?", "did:key:z6MkAsker");
+    let answered = SemanticUnit::new(UnitType::Question, "Answered?", "did:key:z6MkAsker");
+    let disputed = SemanticUnit::new(UnitType::Question, "Disputed?", "did:key:z6MkOther");
+    for q in [&open, &answered, &disputed] {
+        client.post(format!("{base}/v1/units")).json(q).send().await.unwrap();
+    }
+
+    let mut answer = SemanticUnit::new(UnitType::Assertion, "Yes.", "did:key:z6MkConformance");
+    answer.references = Some(vec![Reference { id: answered.id.clone(), rel: RelType::DerivesFrom }]);
+    let mut rebuttal = SemanticUnit::new(UnitType::Challenge, "Ill-posed.", "did:key:z6MkConformance");
+    rebuttal.references = Some(vec![Reference { id: disputed.id.clone(), rel: RelType::Rebuts }]);
+    for u in [&answer, &rebuttal] {
+        client.post(format!("{base}/v1/units")).json(u).send().await.unwrap();
+    }
+
+    let ids_for = |body: Value| -> Vec<String> {
+        body["questions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|q| q["unit"]["id"].as_str().map(String::from))
+            .collect()
+    };
+
+    for (status, expected) in [("open", &open), ("answered", &answered), ("disputed", &disputed)] {
+        let resp = client
+            .get(format!("{base}/v1/questions?status={status}"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        let body: Value = resp.json().await.unwrap();
+        assert_eq!(ids_for(body), vec![expected.id.clone()], "status={status}");
+    }
+
+    let resp = client
+        .get(format!("{base}/v1/questions?author=did:key:z6MkAsker"))
+        .send()
+        .await
+        .unwrap();
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["questions"][1]["status"], "answered");
+    assert_eq!(body["questions"][1]["answers"][0], answer.id.as_str());
+    assert_eq!(ids_for(body), vec![open.id.clone(), answered.id.clone()]);
+}
+
+#[tokio::test]
+async fn questions_filter_by_referenced_type_and_reject_bad_status() {
+    let (base, _storage) = spawn_node().await;
+    let client = make_client();
+
+    let claim = public_unit();
+    client.post(format!("{base}/v1/units")).json(&claim).send().await.unwrap();
+    let mut about_claim = SemanticUnit::new(UnitType::Question, "Source?", "did:key:z6MkAsker");
+    about_claim.references = Some(vec![Reference { id: claim.id.clone(), rel: RelType::Questions }]);
+    let standalone = SemanticUnit::new(UnitType::Question, "Why?", "did:key:z6MkAsker");
+    for q in [&about_claim, &standalone] {
+        client.post(format!("{base}/v1/units")).json(q).send().await.unwrap();
+    }
+
+    let resp = client
+        .get(format!("{base}/v1/questions?type=assertion"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    let questions = body["questions"].as_array().unwrap();
+    assert_eq!(questions.len(), 1);
+    assert_eq!(questions[0]["unit"]["id"], about_claim.id.as_str());
+    assert_eq!(questions[0]["status"], "open");
+
+    let resp = client
+        .get(format!("{base}/v1/questions?status=closed"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+}
+
+// ---------------------------------------------------------------------------
+// Similar — §5.7
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn similar_returns_near_duplicates_only() {
+    let (base, _storage) = spawn_node().await;
+    let client = make_client();
+
+    let original = SemanticUnit::new(
+        UnitType::Assertion,
+        "The boiling point of water at sea level is 100 degrees Celsius.",
+        "did:key:z6MkAlice",
+    );
+    let restated = SemanticUnit::new(
+        UnitType::Assertion,
+        "The boiling point of water at sea level is 100 degrees Celsius!",
+        "did:key:z6MkBob",
+    );
+    let unrelated = SemanticUnit::new(
+        UnitType::Assertion,
+        "Photosynthesis converts light energy into chemical energy.",
+        "did:key:z6MkBob",
+    );
+    let mut hidden = SemanticUnit::new(
+        UnitType::Assertion,
+        "The boiling point of water at sea level is 100 degrees Celsius.",
+        "did:key:z6MkCarol",
+    );
+    hidden.visibility = Some(Visibility::Network);
+    for u in [&original, &restated, &unrelated, &hidden] {
+        client.post(format!("{base}/v1/units")).json(u).send().await.unwrap();
+    }
+
+    let resp = client
+        .get(format!("{base}/v1/units/{}/similar", original.id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    let similar = body["similar"].as_array().unwrap();
+    assert_eq!(similar.len(), 1, "only the public restatement should match: {body}");
+    assert_eq!(similar[0]["unit"]["id"], restated.id.as_str());
+    assert!(similar[0]["score"].as_f64().unwrap() >= 0.9);
+
+    let resp = client
+        .get(format!("{base}/v1/units/{}/similar?threshold=2", original.id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+
+    let resp = client
+        .get(format!("{base}/v1/units/{}/similar", hidden.id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+}
+
+// ---------------------------------------------------------------------------
+// Claim triples — §4.4
+// ---------------------------------------------------------------------------
+
+fn unit_with_triple(subject: &str, predicate: &str, value: Value) -> SemanticUnit {
+    let mut unit = public_unit();
+    unit.extensions.insert(
+        "x-org.semanticweft.triples".into(),
+        serde_json::json!([{
+            "subject": subject,
+            "predicate": predicate,
+            "object": { "kind": "literal", "datatype": "decimal", "value": value }
+        }]),
+    );
+    unit
+}
+
+#[tokio::test]
+async fn list_units_filter_by_triple_pattern() {
+    let (base, _storage) = spawn_node().await;
+    let client = make_client();
+
+    let boils = unit_with_triple("water", "boiling-point-celsius", serde_json::json!(100));
+    let freezes = unit_with_triple("water", "freezing-point-celsius", serde_json::json!(0));
+    let other = unit_with_triple("ethanol", "boiling-point-celsius", serde_json::json!(78.4));
+    for u in [&boils, &freezes, &other, &public_unit()] {
+        let resp = client.post(format!("{base}/v1/units")).json(u).send().await.unwrap();
+        assert_eq!(resp.status(), 201);
+    }
+
+    let ids_for = |body: Value| -> Vec<String> {
+        body["units"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|u| u["id"].as_str().map(String::from))
+            .collect()
+    };
+
+    let resp = client
+        .get(format!("{base}/v1/units?subject=water"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(ids_for(resp.json().await.unwrap()), vec![boils.id.clone(), freezes.id.clone()]);
+
+    let resp = client
+        .get(format!("{base}/v1/units?subject=water&predicate=boiling-point-celsius"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(ids_for(resp.json().await.unwrap()), vec![boils.id.clone()]);
+
+    let resp = client
+        .get(format!("{base}/v1/units?predicate=boiling-point-celsius"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(ids_for(resp.json().await.unwrap()), vec![boils.id.clone(), other.id.clone()]);
+}
+
+#[tokio::test]
+async fn submit_malformed_triples_returns_422() {
+    let (base, _storage) = spawn_node().await;
+    let client = make_client();
+
+    let unit = unit_with_triple("water", "boiling-point-celsius", serde_json::json!("hot"));
+    let resp = client.post(format!("{base}/v1/units")).json(&unit).send().await.unwrap();
+    assert_eq!(resp.status(), 422);
+}
@@ -0,0 +1,296 @@
+//! Delegation tokens — letting a sub-key act for a root identity (ADR-0014).
+//!
+//! A root agent that spawns short-lived workers can give each one its own
+//! key and a [`Delegation`]: a token, signed by the root, that names the
+//! worker's `did:key` as `audience` and grants it a [`DelegationScope`] for
+//! a bounded time window. The worker then signs unit proofs and HTTP
+//! requests with its own key and presents the token, and verifiers attribute
+//! the work to the root (`issuer`) without the root seed ever leaving the
+//! orchestrator.
+//!
+//! The token's signature is a detached Ed25519 signature by the issuer's
+//! `did:key` over the JCS (RFC 8785) form of the token without `signature`,
+//! encoded like unit proofs. Tokens are single-level: a delegate cannot
+//! issue tokens of its own.
+
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::signing::{sign_detached, verify_detached};
+use crate::types::{SemanticUnit, UnitType, Visibility};
+
+/// Errors returned when checking a [`Delegation`].
+#[derive(Debug, Error, PartialEq)]
+pub enum DelegationError {
+    #[error("delegation timestamp is not ISO 8601: {0}")]
+    InvalidTimestamp(String),
+    #[error("delegation is not valid before {0}")]
+    NotYetValid(String),
+    #[error("delegation expired at {0}")]
+    Expired(String),
+    #[error("delegation signature invalid: {0}")]
+    InvalidSignature(String),
+    #[error("delegation was issued to {audience}, not {signer}")]
+    WrongAudience { audience: String, signer: String },
+    #[error("delegation does not cover {0}")]
+    OutOfScope(String),
+}
+
+/// What a delegate may do. `unit_types` and `visibilities` are unrestricted
+/// when empty; `endpoints` grants nothing when empty, so a token only lets
+/// its holder call the node for the requests it names.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DelegationScope {
+    /// Unit types the delegate may sign or submit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unit_types: Vec<UnitType>,
+
+    /// Visibility levels the delegate may sign or submit. A unit without
+    /// `visibility` counts as `public`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub visibilities: Vec<Visibility>,
+
+    /// Node endpoints the delegate may call, as `"<METHOD> <path>"`, e.g.
+    /// `"POST /v1/units"` or `"GET /v1/agents/{did}/inbox"`. `{did}` stands
+    /// for the issuer's DID, a method of `*` matches any method, and a path
+    /// ending in `*` matches any path with that prefix. Empty means the
+    /// delegate may sign units but not make requests.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<String>,
+}
+
+impl DelegationScope {
+    /// Whether a unit of this type and visibility is covered.
+    pub fn allows_unit(&self, unit: &SemanticUnit) -> bool {
+        let visibility = unit.visibility.clone().unwrap_or(Visibility::Public);
+        (self.unit_types.is_empty() || self.unit_types.contains(&unit.unit_type))
+            && (self.visibilities.is_empty() || self.visibilities.contains(&visibility))
+    }
+
+    /// Whether a request is covered. `path` is the percent-decoded request
+    /// path without its query string; `issuer` replaces `{did}` in patterns.
+    pub fn allows_request(&self, issuer: &str, method: &str, path: &str) -> bool {
+        self.endpoints.iter().any(|pattern| {
+            let Some((m, p)) = pattern.split_once(' ') else {
+                return false;
+            };
+            let p = p.trim().replace("{did}", issuer);
+            let method_ok = m == "*" || m.eq_ignore_ascii_case(method);
+            let path_ok = match p.strip_suffix('*') {
+                Some(prefix) => path.starts_with(prefix),
+                None => path == p,
+            };
+            method_ok && path_ok
+        })
+    }
+}
+
+/// A signed grant from `issuer` to the key `audience` (see the module docs).
+///
+/// # Example
+///
+/// ```json
+/// {
+///   "issuer": "did:key:z6MkRoot",
+///   "audience": "did:key:z6MkWorker",
+///   "scope": { "unit_types": ["assertion"], "endpoints": ["POST /v1/units"] },
+///   "not_before": "2026-03-01T12:00:00Z",
+///   "expires": "2026-03-01T13:00:00Z",
+///   "signature": "z4Nt…"
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Delegation {
+    /// The root DID the delegate acts for. Must be a `did:key`.
+    pub issuer: String,
+
+    /// The delegate's `did:key`.
+    pub audience: String,
+
+    /// What the delegate may do.
+    #[serde(default)]
+    pub scope: DelegationScope,
+
+    /// Start of the validity window (ISO 8601).
+    pub not_before: String,
+
+    /// End of the validity window (ISO 8601), exclusive.
+    pub expires: String,
+
+    /// The issuer's signature over the rest of the token.
+    pub signature: String,
+}
+
+impl Delegation {
+    /// An unsigned token valid from `not_before` until `expires`. Sign it
+    /// with [`Delegation::sign`], or sign [`Delegation::signing_payload`]
+    /// elsewhere and fill in `signature`.
+    pub fn new(
+        issuer: impl Into<String>,
+        audience: impl Into<String>,
+        scope: DelegationScope,
+        not_before: SystemTime,
+        expires: SystemTime,
+    ) -> Self {
+        Self {
+            issuer: issuer.into(),
+            audience: audience.into(),
+            scope,
+            not_before: timestamp(not_before),
+            expires: timestamp(expires),
+            signature: String::new(),
+        }
+    }
+
+    /// Sign the token with the issuer's key.
+    pub fn sign(mut self, issuer_key: &ed25519_dalek::SigningKey) -> Self {
+        self.signature = sign_detached(issuer_key, &self.signing_payload());
+        self
+    }
+
+    /// The bytes the issuer signs: the JCS form of the token without
+    /// `signature`.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut value = serde_json::to_value(self).expect("delegation serializes");
+        if let serde_json::Value::Object(map) = &mut value {
+            map.remove("signature");
+        }
+        serde_jcs::to_vec(&value).expect("JSON value canonicalizes")
+    }
+
+    /// Check the issuer's signature and that `at` falls in the validity
+    /// window.
+    pub fn verify(&self, at: SystemTime) -> Result<(), DelegationError> {
+        verify_detached(&self.issuer, &self.signing_payload(), &self.signature)
+            .map_err(|e| DelegationError::InvalidSignature(e.to_string()))?;
+        let at = DateTime::<Utc>::from(at);
+        if at < parse(&self.not_before)? {
+            return Err(DelegationError::NotYetValid(self.not_before.clone()));
+        }
+        if at >= parse(&self.expires)? {
+            return Err(DelegationError::Expired(self.expires.clone()));
+        }
+        Ok(())
+    }
+
+    /// [`verify`](Delegation::verify), and check that `signer` is the
+    /// audience and `unit` is in scope. Used for delegated unit proofs.
+    pub fn verify_for_unit(
+        &self,
+        signer: &str,
+        unit: &SemanticUnit,
+        at: SystemTime,
+    ) -> Result<(), DelegationError> {
+        if signer != self.audience {
+            return Err(DelegationError::WrongAudience {
+                audience: self.audience.clone(),
+                signer: signer.to_string(),
+            });
+        }
+        self.verify(at)?;
+        if !self.scope.allows_unit(unit) {
+            let visibility = unit.visibility.clone().unwrap_or(Visibility::Public);
+            return Err(DelegationError::OutOfScope(format!(
+                "{} units with {visibility} visibility",
+                unit.unit_type
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn timestamp(t: SystemTime) -> String {
+    DateTime::<Utc>::from(t).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn parse(s: &str) -> Result<DateTime<Utc>, DelegationError> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|_| DelegationError::InvalidTimestamp(s.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+    use rand::rngs::OsRng;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn did(key: &SigningKey) -> String {
+        let mut bytes = vec![0xed_u8, 0x01];
+        bytes.extend_from_slice(&key.verifying_key().to_bytes());
+        format!("did:key:z{}", bs58::encode(bytes).into_string())
+    }
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_772_366_400 + secs) // 2026-03-01T12:00:00Z
+    }
+
+    fn token(scope: DelegationScope) -> (SigningKey, Delegation) {
+        let root = SigningKey::generate(&mut OsRng);
+        let worker = SigningKey::generate(&mut OsRng);
+        let token = Delegation::new(did(&root), did(&worker), scope, at(0), at(3600)).sign(&root);
+        (root, token)
+    }
+
+    #[test]
+    fn verifies_inside_window_only() {
+        let (_, t) = token(DelegationScope::default());
+        assert_eq!(t.not_before, "2026-03-01T12:00:00Z");
+        assert_eq!(t.verify(at(60)), Ok(()));
+        assert!(matches!(t.verify(at(3600)), Err(DelegationError::Expired(_))));
+        assert!(matches!(
+            t.verify(UNIX_EPOCH),
+            Err(DelegationError::NotYetValid(_))
+        ));
+    }
+
+    #[test]
+    fn widened_scope_breaks_signature() {
+        let (_, mut t) = token(DelegationScope {
+            unit_types: vec![UnitType::Assertion],
+            ..Default::default()
+        });
+        t.scope.unit_types.clear();
+        assert!(matches!(
+            t.verify(at(60)),
+            Err(DelegationError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn unit_scope_and_audience() {
+        let (_, t) = token(DelegationScope {
+            unit_types: vec![UnitType::Assertion],
+            visibilities: vec![Visibility::Public],
+            ..Default::default()
+        });
+        let mut unit = SemanticUnit::new(UnitType::Assertion, "x", &t.issuer);
+        assert_eq!(t.verify_for_unit(&t.audience, &unit, at(60)), Ok(()));
+        assert!(matches!(
+            t.verify_for_unit(&t.issuer, &unit, at(60)),
+            Err(DelegationError::WrongAudience { .. })
+        ));
+        unit.visibility = Some(Visibility::Network);
+        assert!(matches!(
+            t.verify_for_unit(&t.audience, &unit, at(60)),
+            Err(DelegationError::OutOfScope(_))
+        ));
+    }
+
+    #[test]
+    fn endpoint_patterns() {
+        let scope = DelegationScope {
+            endpoints: vec!["POST /v1/units".into(), "* /v1/agents/{did}/*".into()],
+            ..Default::default()
+        };
+        assert!(scope.allows_request("did:key:zRoot", "post", "/v1/units"));
+        assert!(!scope.allows_request("did:key:zRoot", "GET", "/v1/units"));
+        assert!(scope.allows_request("did:key:zRoot", "GET", "/v1/agents/did:key:zRoot/inbox"));
+        assert!(!scope.allows_request("did:key:zRoot", "GET", "/v1/agents/did:key:zOther/inbox"));
+        assert!(!DelegationScope::default().allows_request("did:key:zRoot", "POST", "/v1/units"));
+    }
+}
@@ -111,7 +111,7 @@ impl Graph {
             .filter(|u| {
                 u.references
                     .as_ref()
-                    .map_or(false, |refs| refs.iter().any(|r| r.id == id))
+                    .is_some_and(|refs| refs.iter().any(|r| r.id == id))
             })
             .collect()
     }
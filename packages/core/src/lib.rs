@@ -13,7 +13,10 @@
 //! | [`types`] | Core data types: [`SemanticUnit`], [`UnitType`], [`RelType`], [`Reference`], [`Source`] |
 //! | [`validation`] | Spec-conformance checking via [`validate_unit`] |
 //! | [`graph`] | In-memory graph of units with traversal methods |
+//! | [`questions`] | Open / answered / disputed classification of `question` units |
 //! | [`render`] | Human-readable text rendering of units and graphs |
+//! | [`similarity`] | Near-duplicate detection via shingling, MinHash, and LSH |
+//! | [`triples`] | Subject–predicate–object claim triples, pattern index, contradictions |
 //!
 //! # Quick start
 //!
@@ -39,13 +42,24 @@
 //! - Unit format: `spec/semantic-unit.md`
 //! - Node API: `spec/node-api.md`
 
+pub mod delegation;
 pub mod graph;
+pub mod questions;
 pub mod render;
 pub mod signing;
+pub mod similarity;
+pub mod triples;
 pub mod types;
 pub mod validation;
 
+pub use delegation::{Delegation, DelegationError, DelegationScope};
 pub use graph::Graph;
-pub use signing::{sign_unit, verify_proof, ProofError, SigningError};
+pub use questions::{question_lifecycle, question_lifecycles, QuestionLifecycle, QuestionStatus};
+pub use signing::{
+    did_key_to_verifying_key, proof_author, proof_signer, sign_detached, sign_unit,
+    sign_unit_delegated, verify_detached, verify_proof, ProofError, SigningError,
+};
+pub use similarity::{MinHash, SimilarityIndex};
+pub use triples::{Triple, TripleIndex, TripleObject};
 pub use types::{Proof, Reference, RelType, SemanticUnit, Source, UnitType, Visibility};
 pub use validation::{validate_unit, ValidationError};
@@ -0,0 +1,256 @@
+//! Question lifecycle tracking over a [`Graph`].
+//!
+//! A `question` unit carries no explicit "resolved" flag — its state is
+//! implied by the units that reference it. This module reads those incoming
+//! edges and classifies each question as [`QuestionStatus::Open`],
+//! [`QuestionStatus::Answered`], or [`QuestionStatus::Disputed`]:
+//!
+//! | Incoming `rel` | Counts as |
+//! |----------------|-----------|
+//! | `derives-from`, `supports` | an answer |
+//! | `questions`, `rebuts` | a dispute |
+//! | `refines`, `notifies` | neither |
+//!
+//! A question with no answers and no disputes is open. Any dispute makes it
+//! disputed, whether or not it has also been answered — a contested question
+//! still needs attention. Otherwise a question with at least one answer is
+//! answered.
+//!
+//! Only units present in the graph are considered; answers held elsewhere
+//! are invisible here, consistent with [`Graph`]'s forward-reference policy.
+
+use serde::{Deserialize, Serialize};
+
+use crate::graph::Graph;
+use crate::types::{RelType, SemanticUnit, UnitType};
+
+/// The resolution state of a `question` unit.
+///
+/// Serialises as a lowercase string (e.g. `"open"`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum QuestionStatus {
+    /// No unit has answered or disputed the question yet.
+    Open,
+    /// At least one unit derives from or supports the question, and none
+    /// dispute it.
+    Answered,
+    /// At least one unit questions or rebuts the question.
+    Disputed,
+}
+
+/// Formats the status as its lowercase wire-format string (e.g. `"open"`).
+impl std::fmt::Display for QuestionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuestionStatus::Open => write!(f, "open"),
+            QuestionStatus::Answered => write!(f, "answered"),
+            QuestionStatus::Disputed => write!(f, "disputed"),
+        }
+    }
+}
+
+/// Parses a [`QuestionStatus`] from its lowercase wire-format string.
+///
+/// Returns `Err` with a descriptive message if the string is not recognised.
+impl std::str::FromStr for QuestionStatus {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(QuestionStatus::Open),
+            "answered" => Ok(QuestionStatus::Answered),
+            "disputed" => Ok(QuestionStatus::Disputed),
+            _ => Err(format!(
+                "unknown question status {:?}; expected one of: open, answered, disputed",
+                s
+            )),
+        }
+    }
+}
+
+/// A question together with the units that answer or dispute it.
+#[derive(Debug, Clone)]
+pub struct QuestionLifecycle<'a> {
+    /// The `question` unit itself.
+    pub question: &'a SemanticUnit,
+    /// The derived state of the question.
+    pub status: QuestionStatus,
+    /// Units that reference the question with `derives-from` or `supports`,
+    /// ordered by id.
+    pub answers: Vec<&'a SemanticUnit>,
+    /// Units that reference the question with `questions` or `rebuts`,
+    /// ordered by id.
+    pub disputes: Vec<&'a SemanticUnit>,
+}
+
+/// Classify a question from the relationships its incoming references carry.
+///
+/// This is the rule applied by [`question_lifecycle`], exposed separately so
+/// that callers who gather incoming edges themselves (e.g. a node querying
+/// storage) reach the same verdict.
+pub fn classify_question<'r>(incoming: impl IntoIterator<Item = &'r RelType>) -> QuestionStatus {
+    let mut answered = false;
+    for rel in incoming {
+        match rel {
+            RelType::Questions | RelType::Rebuts => return QuestionStatus::Disputed,
+            RelType::DerivesFrom | RelType::Supports => answered = true,
+            RelType::Refines | RelType::Notifies => {}
+        }
+    }
+    if answered {
+        QuestionStatus::Answered
+    } else {
+        QuestionStatus::Open
+    }
+}
+
+/// The lifecycle of the question identified by `id`.
+///
+/// Returns `None` if `id` is not in the graph or is not a `question` unit.
+pub fn question_lifecycle<'a>(graph: &'a Graph, id: &str) -> Option<QuestionLifecycle<'a>> {
+    let question = graph.get(id)?;
+    if question.unit_type != UnitType::Question {
+        return None;
+    }
+
+    let mut answers = Vec::new();
+    let mut disputes = Vec::new();
+    let mut rels = Vec::new();
+    for unit in graph.incoming(id) {
+        let Some(refs) = &unit.references else {
+            continue;
+        };
+        for r in refs.iter().filter(|r| r.id == id) {
+            match r.rel {
+                RelType::DerivesFrom | RelType::Supports => answers.push(unit),
+                RelType::Questions | RelType::Rebuts => disputes.push(unit),
+                RelType::Refines | RelType::Notifies => {}
+            }
+            rels.push(&r.rel);
+        }
+    }
+    answers.sort_by_key(|u| u.id.as_str());
+    answers.dedup_by_key(|u| u.id.as_str());
+    disputes.sort_by_key(|u| u.id.as_str());
+    disputes.dedup_by_key(|u| u.id.as_str());
+
+    Some(QuestionLifecycle {
+        question,
+        status: classify_question(rels),
+        answers,
+        disputes,
+    })
+}
+
+/// The lifecycle of every question in the graph, ordered by question id
+/// (UUIDv7 → chronological).
+pub fn question_lifecycles(graph: &Graph) -> Vec<QuestionLifecycle<'_>> {
+    let mut questions = graph.by_type(&UnitType::Question);
+    questions.sort_by_key(|u| u.id.as_str());
+    questions
+        .into_iter()
+        .filter_map(|q| question_lifecycle(graph, &q.id))
+        .collect()
+}
+
+// --- tests -------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Reference;
+    use std::collections::HashMap;
+
+    fn unit(id: &str, unit_type: UnitType, refs: Vec<(&str, RelType)>) -> SemanticUnit {
+        SemanticUnit {
+            id: id.into(),
+            unit_type,
+            content: "test".into(),
+            created_at: "2026-02-18T12:00:00Z".into(),
+            author: "test-agent".into(),
+            confidence: None,
+            assumptions: None,
+            source: None,
+            references: if refs.is_empty() {
+                None
+            } else {
+                Some(
+                    refs.into_iter()
+                        .map(|(id, rel)| Reference { id: id.into(), rel })
+                        .collect(),
+                )
+            },
+            visibility: None,
+            audience: None,
+            proof: None,
+            extensions: HashMap::new(),
+        }
+    }
+
+    const Q: &str = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6c";
+    const A: &str = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6d";
+    const B: &str = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6e";
+
+    #[test]
+    fn unanswered_question_is_open() {
+        let g = Graph::from_units(vec![unit(Q, UnitType::Question, vec![])]);
+        let lc = question_lifecycle(&g, Q).unwrap();
+        assert_eq!(lc.status, QuestionStatus::Open);
+        assert!(lc.answers.is_empty() && lc.disputes.is_empty());
+    }
+
+    #[test]
+    fn derived_answer_marks_answered() {
+        let g = Graph::from_units(vec![
+            unit(Q, UnitType::Question, vec![]),
+            unit(A, UnitType::Assertion, vec![(Q, RelType::DerivesFrom)]),
+            unit(B, UnitType::Assertion, vec![(Q, RelType::Refines)]),
+        ]);
+        let lc = question_lifecycle(&g, Q).unwrap();
+        assert_eq!(lc.status, QuestionStatus::Answered);
+        assert_eq!(lc.answers.len(), 1);
+        assert_eq!(lc.answers[0].id, A);
+    }
+
+    #[test]
+    fn rebuttal_marks_disputed_even_when_answered() {
+        let g = Graph::from_units(vec![
+            unit(Q, UnitType::Question, vec![]),
+            unit(A, UnitType::Assertion, vec![(Q, RelType::Supports)]),
+            unit(B, UnitType::Challenge, vec![(Q, RelType::Rebuts)]),
+        ]);
+        let lc = question_lifecycle(&g, Q).unwrap();
+        assert_eq!(lc.status, QuestionStatus::Disputed);
+        assert_eq!(lc.answers.len(), 1);
+        assert_eq!(lc.disputes.len(), 1);
+    }
+
+    #[test]
+    fn non_question_has_no_lifecycle() {
+        let g = Graph::from_units(vec![unit(A, UnitType::Assertion, vec![])]);
+        assert!(question_lifecycle(&g, A).is_none());
+        assert!(question_lifecycle(&g, Q).is_none());
+    }
+
+    #[test]
+    fn lifecycles_cover_every_question_in_order() {
+        let g = Graph::from_units(vec![
+            unit(B, UnitType::Question, vec![]),
+            unit(Q, UnitType::Question, vec![]),
+            unit(A, UnitType::Assertion, vec![(Q, RelType::DerivesFrom)]),
+        ]);
+        let all = question_lifecycles(&g);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].question.id, Q);
+        assert_eq!(all[0].status, QuestionStatus::Answered);
+        assert_eq!(all[1].status, QuestionStatus::Open);
+    }
+
+    #[test]
+    fn status_parses_and_displays() {
+        for s in ["open", "answered", "disputed"] {
+            assert_eq!(s.parse::<QuestionStatus>().unwrap().to_string(), s);
+        }
+        assert!("closed".parse::<QuestionStatus>().is_err());
+    }
+}
@@ -7,8 +7,10 @@
 //! in a human-facing context. It is not a canonical format — implementations
 //! may render differently. Only the JSON wire format is normative.
 
+use std::collections::{HashMap, HashSet};
+
 use crate::graph::Graph;
-use crate::types::{SemanticUnit, Source, UnitType};
+use crate::types::{RelType, SemanticUnit, Source, UnitType};
 
 /// Render a single [`SemanticUnit`] as indented plain text.
 ///
@@ -47,13 +49,7 @@ pub fn render_unit(unit: &SemanticUnit) -> String {
     // source
     if let Some(source) = &unit.source {
         out.push('\n');
-        match source {
-            Source::Uri(uri) => out.push_str(&format!("Source: {}", uri)),
-            Source::Labeled { label, uri } => match uri {
-                Some(u) => out.push_str(&format!("Source: {} <{}>", label, u)),
-                None => out.push_str(&format!("Source: {}", label)),
-            },
-        }
+        out.push_str(&format!("Source: {}", render_source(source)));
         out.push('\n');
     }
 
@@ -141,8 +137,274 @@ pub fn render_graph(graph: &Graph) -> String {
     out
 }
 
+/// Render an entire [`Graph`] as threaded conversations.
+///
+/// Each unit is nested under the unit it references, so a debate reads
+/// top-down as it happened: the original claim, then the challenges,
+/// refinements, and answers it drew, each labelled with its relationship.
+/// Siblings are ordered by id (UUIDv7 → chronological).
+///
+/// A unit that references several units in the graph is placed under the
+/// first of them; the remaining links are listed on an `also:` line. Units
+/// whose references all point outside the graph start their own thread.
+///
+/// ```text
+/// SemanticWeft Threads  3 units
+/// ─────────────────────────────
+///
+/// [019526b2] assertion  agent-climatesynthesizer
+///   "Global mean surface temperature in 2025 was 1.4°C above baseline."
+///   └─ derives-from  [019526b3] inference  agent-climatesynthesizer
+///      "At the observed rate of temperature increase, the 1.5°C threshold…"
+///      └─ rebuts  [019526b4] challenge  agent-skeptic
+///         "The 2035 threshold projection assumes linear extrapolation…"
+/// ```
+pub fn render_threads(graph: &Graph) -> String {
+    let total = graph.len();
+    let header = format!(
+        "SemanticWeft Threads  {} unit{}",
+        total,
+        if total == 1 { "" } else { "s" }
+    );
+    let rule = "─".repeat(header.chars().count());
+    let mut out = format!("{}\n{}\n", header, rule);
+
+    // Attach every unit to the first of its references that is present in
+    // the graph; everything else is a thread root.
+    let mut children: HashMap<&str, Vec<&SemanticUnit>> = HashMap::new();
+    let mut roots: Vec<&SemanticUnit> = Vec::new();
+    for unit in graph.units() {
+        match thread_parent(graph, unit) {
+            Some(parent) => children.entry(parent).or_default().push(unit),
+            None => roots.push(unit),
+        }
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by_key(|u| u.id.as_str());
+    }
+    roots.sort_by_key(|u| u.id.as_str());
+
+    let mut visited: HashSet<&str> = HashSet::new();
+    for root in roots {
+        out.push('\n');
+        render_thread_node(graph, root, None, 0, &children, &mut visited, &mut out);
+    }
+
+    // Reference cycles leave units with no root to hang from; render them as
+    // threads of their own so nothing in the graph is silently dropped.
+    let mut stranded: Vec<&SemanticUnit> = graph
+        .units()
+        .filter(|u| !visited.contains(u.id.as_str()))
+        .collect();
+    stranded.sort_by_key(|u| u.id.as_str());
+    for unit in stranded {
+        if visited.contains(unit.id.as_str()) {
+            continue;
+        }
+        out.push('\n');
+        render_thread_node(graph, unit, None, 0, &children, &mut visited, &mut out);
+    }
+
+    out
+}
+
+/// Render an entire [`Graph`] in Graphviz DOT format.
+///
+/// Each unit becomes a node labelled with its type and a content excerpt,
+/// filled by type; each reference between two units in the graph becomes an
+/// edge labelled with its relationship. References to units outside the
+/// graph are omitted. Nodes and edges are emitted in id order, so the output
+/// is stable for a given graph.
+///
+/// ```text
+/// digraph semanticweft {
+///   rankdir=BT;
+///   node [shape=box, style="rounded,filled", fontname="Helvetica"];
+///
+///   "019526b2-…" [label="assertion\nGlobal mean surface temperature…", fillcolor="#cfe2f3"];
+///   "019526b3-…" [label="inference\nAt the observed rate…", fillcolor="#d9ead3"];
+///
+///   "019526b3-…" -> "019526b2-…" [label="derives-from"];
+/// }
+/// ```
+pub fn render_dot(graph: &Graph) -> String {
+    let mut units: Vec<&SemanticUnit> = graph.units().collect();
+    units.sort_by_key(|u| u.id.as_str());
+
+    let mut out = String::from("digraph semanticweft {\n");
+    out.push_str("  rankdir=BT;\n");
+    out.push_str("  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n\n");
+
+    for u in &units {
+        let label = format!("{}\\n{}", u.unit_type, dot_escape(&truncate(&u.content, 48)));
+        out.push_str(&format!(
+            "  \"{}\" [label=\"{}\", fillcolor=\"{}\"];\n",
+            dot_escape(&u.id),
+            label,
+            dot_fill(&u.unit_type)
+        ));
+    }
+
+    let mut wrote_edge_gap = false;
+    for u in &units {
+        for r in u.references.iter().flatten() {
+            if graph.get(&r.id).is_none() {
+                continue;
+            }
+            if !wrote_edge_gap {
+                out.push('\n');
+                wrote_edge_gap = true;
+            }
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                dot_escape(&u.id),
+                dot_escape(&r.id),
+                r.rel
+            ));
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Render a [`Source`] as a single line: the URI, or `label <uri>`.
+pub fn render_source(source: &Source) -> String {
+    match source {
+        Source::Uri(uri) => uri.clone(),
+        Source::Labeled { label, uri } => match uri {
+            Some(u) => format!("{} <{}>", label, u),
+            None => label.clone(),
+        },
+    }
+}
+
+/// Shorten `s` to at most `max` bytes, ending in `…` when anything was cut.
+pub fn truncate(s: &str, max: usize) -> String {
+    let s = s.trim();
+    if s.len() <= max {
+        s.to_string()
+    } else {
+        // truncate at a character boundary
+        let boundary = s
+            .char_indices()
+            .take_while(|(i, _)| *i < max.saturating_sub(1))
+            .last()
+            .map(|(i, c)| i + c.len_utf8())
+            .unwrap_or(0);
+        format!("{}…", &s[..boundary])
+    }
+}
+
+/// The first eight characters of a unit id, as shown in summaries.
+pub fn short_id(id: &str) -> &str {
+    // first 8 hex chars
+    &id[..8.min(id.len())]
+}
+
 // --- helpers -----------------------------------------------------------------
 
+/// Escape a string for use inside a double-quoted DOT identifier or label.
+fn dot_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' | '\r' => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Node fill colour for a unit type.
+fn dot_fill(unit_type: &UnitType) -> &'static str {
+    match unit_type {
+        UnitType::Assertion => "#cfe2f3",
+        UnitType::Question => "#fff2cc",
+        UnitType::Inference => "#d9ead3",
+        UnitType::Challenge => "#f4cccc",
+        UnitType::Constraint => "#e6e6e6",
+    }
+}
+
+/// The id of the unit `unit` is threaded under: its first reference that is
+/// present in `graph`, or `None` if it starts a thread.
+fn thread_parent<'a>(graph: &Graph, unit: &'a SemanticUnit) -> Option<&'a str> {
+    unit.references
+        .as_ref()?
+        .iter()
+        .find(|r| r.id != unit.id && graph.get(&r.id).is_some())
+        .map(|r| r.id.as_str())
+}
+
+/// Append `unit` and, recursively, its replies to `out`.
+///
+/// `rel` is the relationship label linking `unit` to its parent (`None` for
+/// a thread root). A reply's `└─` branch starts under its parent's content,
+/// so each nesting level adds three columns.
+fn render_thread_node<'a>(
+    graph: &Graph,
+    unit: &'a SemanticUnit,
+    rel: Option<&RelType>,
+    depth: usize,
+    children: &HashMap<&str, Vec<&'a SemanticUnit>>,
+    visited: &mut HashSet<&'a str>,
+    out: &mut String,
+) {
+    if !visited.insert(unit.id.as_str()) {
+        return;
+    }
+
+    let (indent, branch) = match rel {
+        Some(rel) => (" ".repeat(2 + 3 * (depth - 1)), format!("└─ {}  ", rel)),
+        None => (String::new(), String::new()),
+    };
+    let body_indent = " ".repeat(2 + 3 * depth);
+    let confidence = unit
+        .confidence
+        .map(|c| format!("  confidence: {:.2}", c))
+        .unwrap_or_default();
+
+    out.push_str(&format!(
+        "{}{}[{}] {}  {}{}\n",
+        indent,
+        branch,
+        short_id(&unit.id),
+        unit.unit_type,
+        unit.author,
+        confidence
+    ));
+    out.push_str(&format!("{}\"{}\"\n", body_indent, truncate(&unit.content, 72)));
+
+    // Secondary links to other units in the graph.
+    if let Some(refs) = &unit.references {
+        let parent = thread_parent(graph, unit);
+        let also: Vec<String> = refs
+            .iter()
+            .filter(|r| Some(r.id.as_str()) != parent && graph.get(&r.id).is_some())
+            .map(|r| format!("{} [{}]", r.rel, short_id(&r.id)))
+            .collect();
+        if !also.is_empty() {
+            out.push_str(&format!("{}also: {}\n", body_indent, also.join(", ")));
+        }
+    }
+
+    let Some(replies) = children.get(unit.id.as_str()) else {
+        return;
+    };
+    for reply in replies {
+        // The label is the reply's relationship to *this* unit.
+        let reply_rel = reply
+            .references
+            .as_ref()
+            .and_then(|refs| refs.iter().find(|r| r.id == unit.id))
+            .map(|r| &r.rel);
+        render_thread_node(graph, reply, reply_rel, depth + 1, children, visited, out);
+    }
+}
+
 fn wrap_content(content: &str, width: usize) -> String {
     if content.len() <= width {
         return format!("\"{}\"", content);
@@ -165,27 +427,6 @@ fn wrap_content(content: &str, width: usize) -> String {
     result
 }
 
-fn truncate(s: &str, max: usize) -> String {
-    let s = s.trim();
-    if s.len() <= max {
-        s.to_string()
-    } else {
-        // truncate at a character boundary
-        let boundary = s
-            .char_indices()
-            .take_while(|(i, _)| *i < max - 1)
-            .last()
-            .map(|(i, c)| i + c.len_utf8())
-            .unwrap_or(max);
-        format!("{}…", &s[..boundary])
-    }
-}
-
-fn short_id(id: &str) -> &str {
-    // first 8 hex chars
-    &id[..8.min(id.len())]
-}
-
 // --- tests -------------------------------------------------------------------
 
 #[cfg(test)]
@@ -221,6 +462,105 @@ mod tests {
         assert!(rendered.contains("019526b2"));
     }
 
+    #[test]
+    fn render_source_formats_each_form() {
+        assert_eq!(render_source(&Source::Uri("https://example.com".into())), "https://example.com");
+        let labeled = Source::Labeled {
+            label: "NOAA dataset".into(),
+            uri: Some("https://noaa.gov/data".into()),
+        };
+        assert_eq!(render_source(&labeled), "NOAA dataset <https://noaa.gov/data>");
+        let bare = Source::Labeled { label: "field notes".into(), uri: None };
+        assert_eq!(render_source(&bare), "field notes");
+    }
+
+    #[test]
+    fn truncate_cuts_at_a_character_boundary() {
+        assert_eq!(truncate("  short  ", 10), "short");
+        assert_eq!(truncate("100°C at sea level", 6), "100°…");
+    }
+
+    #[test]
+    fn truncate_handles_tiny_limits() {
+        assert_eq!(truncate("abc", 0), "…");
+        assert_eq!(truncate("°C", 1), "…");
+        assert_eq!(truncate("", 0), "");
+    }
+
+    #[test]
+    fn render_threads_nests_replies_under_parent() {
+        use crate::types::{Reference, RelType};
+
+        let root = minimal_unit();
+        let mut challenge = minimal_unit();
+        challenge.id = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6e".into();
+        challenge.unit_type = UnitType::Challenge;
+        challenge.content = "Only at standard atmospheric pressure.".into();
+        challenge.references = Some(vec![Reference {
+            id: root.id.clone(),
+            rel: RelType::Rebuts,
+        }]);
+        let mut refinement = minimal_unit();
+        refinement.id = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6d".into();
+        refinement.unit_type = UnitType::Inference;
+        refinement.content = "Pressure at sea level is 101.325 kPa.".into();
+        refinement.references = Some(vec![Reference {
+            id: root.id.clone(),
+            rel: RelType::Refines,
+        }]);
+
+        let g = Graph::from_units(vec![challenge, root, refinement]);
+        let rendered = render_threads(&g);
+
+        assert!(rendered.contains("3 units"));
+        let lines: Vec<&str> = rendered.lines().collect();
+        let root_line = lines.iter().position(|l| l.starts_with("[019526b2] assertion")).unwrap();
+        let refines = lines.iter().position(|l| l.contains("└─ refines  [019526b2] inference")).unwrap();
+        let rebuts = lines.iter().position(|l| l.contains("└─ rebuts  [019526b2] challenge")).unwrap();
+        // Replies follow their parent, siblings in id order.
+        assert!(root_line < refines && refines < rebuts);
+        assert!(lines[rebuts].starts_with("  └─"));
+    }
+
+    #[test]
+    fn render_threads_keeps_units_in_reference_cycles() {
+        use crate::types::{Reference, RelType};
+
+        let mut a = minimal_unit();
+        let mut b = minimal_unit();
+        b.id = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6d".into();
+        a.references = Some(vec![Reference { id: b.id.clone(), rel: RelType::Supports }]);
+        b.references = Some(vec![Reference { id: a.id.clone(), rel: RelType::Supports }]);
+
+        let rendered = render_threads(&Graph::from_units(vec![a, b]));
+        assert_eq!(rendered.matches("assertion  agent-weathersim-v2").count(), 2);
+    }
+
+    #[test]
+    fn render_dot_emits_nodes_and_internal_edges() {
+        use crate::types::{Reference, RelType};
+
+        let root = minimal_unit();
+        let mut reply = minimal_unit();
+        reply.id = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6d".into();
+        reply.unit_type = UnitType::Challenge;
+        reply.content = "Only at \"standard\" pressure.".into();
+        reply.references = Some(vec![
+            Reference { id: root.id.clone(), rel: RelType::Rebuts },
+            Reference { id: "019526b2-0000-7000-8000-000000000000".into(), rel: RelType::Supports },
+        ]);
+        let root_id = root.id.clone();
+        let reply_id = reply.id.clone();
+
+        let dot = render_dot(&Graph::from_units(vec![reply, root]));
+        assert!(dot.starts_with("digraph semanticweft {"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert!(dot.contains(&format!("\"{reply_id}\" -> \"{root_id}\" [label=\"rebuts\"]")));
+        // Edges to units outside the graph are dropped; quotes are escaped.
+        assert!(!dot.contains("000000000000"));
+        assert!(dot.contains("Only at \\\"standard\\\" pressure."));
+    }
+
     #[test]
     fn render_graph_groups_by_type() {
         let mut g = Graph::new();
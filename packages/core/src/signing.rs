@@ -8,6 +8,7 @@ use chrono::Utc;
 use ed25519_dalek::{Signer, Verifier};
 use thiserror::Error;
 
+use crate::delegation::{Delegation, DelegationError};
 use crate::types::{Proof, SemanticUnit};
 
 /// Errors returned by [`sign_unit`].
@@ -32,6 +33,8 @@ pub enum ProofError {
     VerificationFailed,
     #[error("canonicalization failed: {0}")]
     Canonicalization(String),
+    #[error(transparent)]
+    Delegation(#[from] DelegationError),
 }
 
 /// Sign a unit in place.
@@ -54,19 +57,37 @@ pub fn sign_unit(
     }
 
     let payload = canonical_payload(unit).map_err(SigningError::Canonicalization)?;
-    let signature = signing_key.sign(&payload);
-    let sig_bytes = signature.to_bytes();
-    let encoded = format!("z{}", bs58::encode(sig_bytes).into_string());
+    let encoded = sign_detached(signing_key, &payload);
 
     unit.proof = Some(Proof {
         method: format!("{did}#{did}"),
         created: Utc::now().to_rfc3339(),
         value: encoded,
+        delegation: None,
     });
 
     Ok(())
 }
 
+/// Sign a unit in place with a delegate key (ADR-0014).
+///
+/// Like [`sign_unit`], but the proof's `method` is the delegation's
+/// `audience` — whose key `signing_key` must be — and the token travels in
+/// the proof, so verifiers attribute the unit to `delegation.issuer`. The
+/// unit's `author` should be the issuer.
+pub fn sign_unit_delegated(
+    unit: &mut SemanticUnit,
+    signing_key: &ed25519_dalek::SigningKey,
+    delegation: Delegation,
+) -> Result<(), SigningError> {
+    let did = delegation.audience.clone();
+    sign_unit(unit, signing_key, &did)?;
+    if let Some(proof) = unit.proof.as_mut() {
+        proof.delegation = Some(delegation);
+    }
+    Ok(())
+}
+
 /// Verify the proof attached to a unit.
 ///
 /// Resolves the signing key from `proof.method` using `did:key` decoding
@@ -79,16 +100,64 @@ pub fn sign_unit(
 /// - [`ProofError::InvalidMethod`] — `proof.method` is not a valid `did:key`.
 /// - [`ProofError::DecodingFailed`] — signature bytes cannot be decoded.
 /// - [`ProofError::VerificationFailed`] — signature is cryptographically invalid.
+/// - [`ProofError::Delegation`] — the proof carries a delegation that was not
+///   issued to the signer, was not valid at the unit's `created_at`, or does
+///   not cover the unit's type and visibility.
 pub fn verify_proof(unit: &SemanticUnit) -> Result<(), ProofError> {
     let proof = unit.proof.as_ref().ok_or(ProofError::ProofMissing)?;
 
-    // Decode the verifying key from proof.method.
-    // proof.method looks like "did:key:z6Mk...#did:key:z6Mk..." or "did:key:z6Mk...#z6Mk..."
-    // We need the part after "did:key:" and before "#" (or the whole thing if no "#").
-    let method_did = proof.method.split('#').next().unwrap_or(&proof.method);
-    let multibase = method_did
+    // proof.method looks like "did:key:z6Mk...#did:key:z6Mk..." or "did:key:z6Mk...#z6Mk...";
+    // the key is resolved from the DID before the fragment.
+    let method_did = proof_signer(unit).unwrap_or(&proof.method);
+    let verifying_key = did_key_to_verifying_key(method_did)?;
+
+    // Canonicalize the unit without proof.
+    let payload =
+        canonical_payload(unit).map_err(ProofError::Canonicalization)?;
+
+    verify_with_key(&verifying_key, &payload, &proof.value)?;
+
+    // The window is checked at `created_at` rather than `proof.created`:
+    // the signature covers the former but not the latter.
+    if let Some(delegation) = &proof.delegation {
+        let created = chrono::DateTime::parse_from_rfc3339(&unit.created_at)
+            .map_err(|_| DelegationError::InvalidTimestamp(unit.created_at.clone()))?;
+        delegation.verify_for_unit(method_did, unit, created.into())?;
+    }
+    Ok(())
+}
+
+/// The DID of the key that signed `unit`: `proof.method` up to the `#`
+/// fragment. Returns `None` if the unit has no proof.
+///
+/// This is the *claimed* signer; call [`verify_proof`] to check that the
+/// signature is genuine, and compare against `unit.author` to check that the
+/// author signed their own unit.
+pub fn proof_signer(unit: &SemanticUnit) -> Option<&str> {
+    let method = &unit.proof.as_ref()?.method;
+    Some(method.split('#').next().unwrap_or(method))
+}
+
+/// The DID a unit's proof speaks for: the delegation's issuer when the unit
+/// was signed by a delegate, otherwise the signer. Returns `None` if the unit
+/// has no proof.
+///
+/// Like [`proof_signer`], this is a claim until [`verify_proof`] succeeds.
+pub fn proof_author(unit: &SemanticUnit) -> Option<&str> {
+    match &unit.proof.as_ref()?.delegation {
+        Some(delegation) => Some(&delegation.issuer),
+        None => proof_signer(unit),
+    }
+}
+
+/// Resolve a `did:key` DID to its Ed25519 verifying key (no network calls).
+///
+/// Only Ed25519 keys (multicodec prefix `[0xed, 0x01]`) with base58btc
+/// multibase encoding are supported.
+pub fn did_key_to_verifying_key(did: &str) -> Result<ed25519_dalek::VerifyingKey, ProofError> {
+    let multibase = did
         .strip_prefix("did:key:")
-        .ok_or_else(|| ProofError::InvalidMethod(format!("not a did:key: {}", proof.method)))?;
+        .ok_or_else(|| ProofError::InvalidMethod(format!("not a did:key: {did}")))?;
 
     // multibase starts with 'z' (base58btc). Strip 'z', decode, strip [0xed, 0x01] multicodec prefix.
     let multibase_data = multibase
@@ -109,16 +178,38 @@ pub fn verify_proof(unit: &SemanticUnit) -> Result<(), ProofError> {
         .try_into()
         .map_err(|_| ProofError::InvalidMethod("key must be 32 bytes".into()))?;
 
-    let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&key_bytes)
-        .map_err(|e| ProofError::InvalidMethod(format!("invalid Ed25519 key: {e}")))?;
+    ed25519_dalek::VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| ProofError::InvalidMethod(format!("invalid Ed25519 key: {e}")))
+}
 
-    // Canonicalize the unit without proof.
-    let payload =
-        canonical_payload(unit).map_err(ProofError::Canonicalization)?;
+/// Sign arbitrary bytes, returning the signature in the same base58btc
+/// (`z`-prefix) encoding used by unit proofs.
+///
+/// Used for detached signatures over artefacts that are not units, such as
+/// evidence bundle manifests.
+pub fn sign_detached(signing_key: &ed25519_dalek::SigningKey, payload: &[u8]) -> String {
+    let sig_bytes = signing_key.sign(payload).to_bytes();
+    format!("z{}", bs58::encode(sig_bytes).into_string())
+}
+
+/// Verify a detached signature produced by [`sign_detached`] against the
+/// key of the `did:key` DID `signer`.
+pub fn verify_detached(signer: &str, payload: &[u8], signature: &str) -> Result<(), ProofError> {
+    let verifying_key = did_key_to_verifying_key(signer)?;
+    verify_with_key(&verifying_key, payload, signature)
+}
 
-    // Decode signature from proof.value (strip 'z', bs58-decode).
-    let sig_data = proof
-        .value
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
+/// Decode a `z`-prefixed base58btc signature and verify it over `payload`.
+fn verify_with_key(
+    verifying_key: &ed25519_dalek::VerifyingKey,
+    payload: &[u8],
+    signature: &str,
+) -> Result<(), ProofError> {
+    let sig_data = signature
         .strip_prefix('z')
         .ok_or_else(|| ProofError::DecodingFailed("value must start with 'z'".into()))?;
 
@@ -133,14 +224,10 @@ pub fn verify_proof(unit: &SemanticUnit) -> Result<(), ProofError> {
     let signature = ed25519_dalek::Signature::from_bytes(&sig_array);
 
     verifying_key
-        .verify(&payload, &signature)
+        .verify(payload, &signature)
         .map_err(|_| ProofError::VerificationFailed)
 }
 
-// ---------------------------------------------------------------------------
-// Internal helpers
-// ---------------------------------------------------------------------------
-
 /// Produce the JCS canonical bytes for a unit with `proof` removed.
 fn canonical_payload(unit: &SemanticUnit) -> Result<Vec<u8>, String> {
     // Clone and remove proof before serialising.
@@ -230,6 +317,89 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn proof_signer_strips_fragment() {
+        let (signing_key, did) = test_key();
+        let mut unit = test_unit();
+        assert_eq!(proof_signer(&unit), None);
+        sign_unit(&mut unit, &signing_key, &did).unwrap();
+        assert_eq!(proof_signer(&unit), Some(did.as_str()));
+    }
+
+    #[test]
+    fn detached_signature_round_trips() {
+        let (signing_key, did) = test_key();
+        let sig = sign_detached(&signing_key, b"manifest bytes");
+        assert_eq!(verify_detached(&did, b"manifest bytes", &sig), Ok(()));
+        assert_eq!(
+            verify_detached(&did, b"tampered bytes", &sig),
+            Err(ProofError::VerificationFailed)
+        );
+        assert!(matches!(
+            verify_detached("did:web:example.com", b"x", &sig),
+            Err(ProofError::InvalidMethod(_))
+        ));
+    }
+
+    #[test]
+    fn delegated_proof_is_attributed_to_issuer() {
+        let (root_key, root) = test_key();
+        let (worker_key, worker) = test_key();
+        let now = std::time::SystemTime::now();
+        let scope = crate::delegation::DelegationScope {
+            unit_types: vec![UnitType::Assertion],
+            ..Default::default()
+        };
+        let token = Delegation::new(
+            &root,
+            &worker,
+            scope,
+            now - std::time::Duration::from_secs(60),
+            now + std::time::Duration::from_secs(3600),
+        )
+        .sign(&root_key);
+
+        let mut unit = SemanticUnit::new(UnitType::Assertion, "delegated", &root);
+        sign_unit_delegated(&mut unit, &worker_key, token.clone()).unwrap();
+        assert_eq!(verify_proof(&unit), Ok(()));
+        assert_eq!(proof_signer(&unit), Some(worker.as_str()));
+        assert_eq!(proof_author(&unit), Some(root.as_str()));
+
+        let mut question = SemanticUnit::new(UnitType::Question, "out of scope?", &root);
+        sign_unit_delegated(&mut question, &worker_key, token).unwrap();
+        assert!(matches!(
+            verify_proof(&question),
+            Err(ProofError::Delegation(DelegationError::OutOfScope(_)))
+        ));
+    }
+
+    #[test]
+    fn delegated_proof_checks_window_at_signed_created_at() {
+        let (root_key, root) = test_key();
+        let (worker_key, worker) = test_key();
+        let now = std::time::SystemTime::now();
+        let hour = std::time::Duration::from_secs(3600);
+        let scope = crate::delegation::DelegationScope::default();
+        let token = Delegation::new(&root, &worker, scope, now - 2 * hour, now - hour)
+            .sign(&root_key);
+        let in_window = chrono::DateTime::<Utc>::from(now - hour - hour / 2).to_rfc3339();
+
+        // Signed after expiry, with `proof.created` backdated into the window.
+        let mut late = SemanticUnit::new(UnitType::Assertion, "late", &root);
+        sign_unit_delegated(&mut late, &worker_key, token.clone()).unwrap();
+        late.proof.as_mut().unwrap().created = in_window.clone();
+        assert!(matches!(
+            verify_proof(&late),
+            Err(ProofError::Delegation(DelegationError::Expired(_)))
+        ));
+
+        // Created inside the window, the unit stays valid after expiry.
+        let mut early = SemanticUnit::new(UnitType::Assertion, "early", &root);
+        early.created_at = in_window;
+        sign_unit_delegated(&mut early, &worker_key, token).unwrap();
+        assert_eq!(verify_proof(&early), Ok(()));
+    }
+
     #[test]
     fn missing_proof_returns_proof_missing() {
         let unit = test_unit();
@@ -0,0 +1,438 @@
+//! Near-duplicate detection over unit `content`.
+//!
+//! Agents frequently restate a claim that already exists, which splits
+//! support and challenges across copies. This module finds such
+//! near-duplicates without any external dependencies:
+//!
+//! 1. **Shingling** — content is normalised (lowercased, punctuation
+//!    removed, whitespace collapsed) and split into overlapping character
+//!    4-grams, each hashed to a `u64`.
+//! 2. **MinHash** — a fixed-length [`MinHash`] signature estimates the
+//!    Jaccard similarity of two shingle sets in constant time.
+//! 3. **LSH banding** — the signature is cut into [`BANDS`] bands; units that
+//!    agree on any whole band land in the same bucket. Only units that share
+//!    a bucket need to be compared, which is what makes [`SimilarityIndex`]
+//!    (and the node's storage-backed index) sub-linear.
+//!
+//! All hashing is deterministic and platform-independent, so signatures may
+//! be persisted and compared across processes and machines.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::graph::Graph;
+use crate::types::SemanticUnit;
+
+/// Number of hash functions in a [`MinHash`] signature.
+pub const SIGNATURE_LEN: usize = 128;
+
+/// Number of LSH bands a signature is divided into.
+///
+/// With [`ROWS_PER_BAND`] rows each, the banding threshold is about
+/// (1/32)^(1/4) ≈ 0.42, just under [`DEFAULT_THRESHOLD`]: a pair with true
+/// similarity 0.6 shares at least one band with probability about 0.99, a
+/// pair at 0.5 about 0.87, and a pair at 0.3 only about 0.23.
+pub const BANDS: usize = 32;
+
+/// Number of signature values per LSH band.
+pub const ROWS_PER_BAND: usize = SIGNATURE_LEN / BANDS;
+
+/// Similarity at or above which two units are considered near-duplicates
+/// when the caller does not choose a threshold.
+pub const DEFAULT_THRESHOLD: f64 = 0.5;
+
+/// Width of the character shingles.
+const SHINGLE_LEN: usize = 4;
+
+// --- shingling ---------------------------------------------------------------
+
+/// Lowercase, drop punctuation, and collapse whitespace so that trivial
+/// formatting differences do not affect similarity.
+fn normalise(content: &str) -> Vec<char> {
+    let mut out = Vec::with_capacity(content.len());
+    let mut pending_space = false;
+    for c in content.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            if pending_space && !out.is_empty() {
+                out.push(' ');
+            }
+            pending_space = false;
+            out.push(c);
+        } else if c.is_whitespace() {
+            pending_space = true;
+        }
+    }
+    out
+}
+
+/// The set of hashed character shingles for `content`.
+///
+/// Content shorter than one shingle yields a single shingle of the whole
+/// normalised text; empty content yields an empty set.
+pub fn shingles(content: &str) -> HashSet<u64> {
+    let chars = normalise(content);
+    if chars.is_empty() {
+        return HashSet::new();
+    }
+    if chars.len() < SHINGLE_LEN {
+        return HashSet::from([hash_chars(&chars)]);
+    }
+    chars.windows(SHINGLE_LEN).map(hash_chars).collect()
+}
+
+/// Exact Jaccard similarity of the shingle sets of two strings, in `[0, 1]`.
+///
+/// Two empty strings are considered dissimilar (`0.0`), so units with no
+/// meaningful content never match each other.
+pub fn jaccard(a: &str, b: &str) -> f64 {
+    let (a, b) = (shingles(a), shingles(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+// --- MinHash -----------------------------------------------------------------
+
+/// A MinHash signature of a unit's content.
+///
+/// Serialises as a JSON array of [`SIGNATURE_LEN`] integers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MinHash(Vec<u64>);
+
+impl MinHash {
+    /// Compute the signature of `content`.
+    pub fn of(content: &str) -> Self {
+        let mut values = vec![u64::MAX; SIGNATURE_LEN];
+        for shingle in shingles(content) {
+            for (i, slot) in values.iter_mut().enumerate() {
+                let h = mix(shingle ^ seed(i));
+                if h < *slot {
+                    *slot = h;
+                }
+            }
+        }
+        MinHash(values)
+    }
+
+    /// Rebuild a signature from persisted values.
+    ///
+    /// Returns `None` if `values` is not exactly [`SIGNATURE_LEN`] long.
+    pub fn from_values(values: Vec<u64>) -> Option<Self> {
+        (values.len() == SIGNATURE_LEN).then_some(MinHash(values))
+    }
+
+    /// The raw signature values.
+    pub fn values(&self) -> &[u64] {
+        &self.0
+    }
+
+    /// Estimated Jaccard similarity with `other`, in `[0, 1]`.
+    ///
+    /// Signatures of empty content (all slots `u64::MAX`) never match.
+    pub fn similarity(&self, other: &MinHash) -> f64 {
+        let agree = self
+            .0
+            .iter()
+            .zip(&other.0)
+            .filter(|(a, b)| a == b && **a != u64::MAX)
+            .count();
+        agree as f64 / SIGNATURE_LEN as f64
+    }
+
+    /// One bucket key per LSH band, in band order.
+    ///
+    /// Two signatures that share the key at the same band index agree on
+    /// every row of that band, which makes them candidates for comparison.
+    pub fn band_keys(&self) -> Vec<u64> {
+        self.0
+            .chunks(ROWS_PER_BAND)
+            .map(|band| band.iter().fold(FNV_OFFSET, |h, v| fnv(h, &v.to_le_bytes())))
+            .collect()
+    }
+}
+
+// --- index -------------------------------------------------------------------
+
+/// An in-memory LSH index of unit signatures, keyed by unit ID.
+#[derive(Debug, Clone, Default)]
+pub struct SimilarityIndex {
+    signatures: HashMap<String, MinHash>,
+    buckets: HashMap<(usize, u64), BTreeSet<String>>,
+}
+
+impl SimilarityIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index `id` under `signature`, replacing any previous entry for it.
+    pub fn insert(&mut self, id: impl Into<String>, signature: MinHash) {
+        let id = id.into();
+        self.remove(&id);
+        for (band, key) in signature.band_keys().into_iter().enumerate() {
+            self.buckets.entry((band, key)).or_default().insert(id.clone());
+        }
+        self.signatures.insert(id, signature);
+    }
+
+    /// Index a unit by its content.
+    pub fn insert_unit(&mut self, unit: &SemanticUnit) {
+        self.insert(unit.id.clone(), MinHash::of(&unit.content));
+    }
+
+    /// Remove `id` from the index. No-op if absent.
+    pub fn remove(&mut self, id: &str) {
+        let Some(signature) = self.signatures.remove(id) else {
+            return;
+        };
+        for (band, key) in signature.band_keys().into_iter().enumerate() {
+            if let Some(ids) = self.buckets.get_mut(&(band, key)) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    self.buckets.remove(&(band, key));
+                }
+            }
+        }
+    }
+
+    /// The stored signature for `id`, if indexed.
+    pub fn signature(&self, id: &str) -> Option<&MinHash> {
+        self.signatures.get(id)
+    }
+
+    /// IDs sharing at least one band bucket with `signature`, with their
+    /// signatures. Unordered; includes the queried unit itself if indexed.
+    pub fn candidates(&self, signature: &MinHash) -> Vec<(&str, &MinHash)> {
+        let mut seen = HashSet::new();
+        let mut out = Vec::new();
+        for (band, key) in signature.band_keys().into_iter().enumerate() {
+            for id in self.buckets.get(&(band, key)).into_iter().flatten() {
+                if seen.insert(id.as_str()) {
+                    out.push((id.as_str(), &self.signatures[id]));
+                }
+            }
+        }
+        out
+    }
+
+    /// Indexed units whose estimated similarity to `signature` is at least
+    /// `threshold`, most similar first (ties broken by ID).
+    pub fn query(&self, signature: &MinHash, threshold: f64) -> Vec<(&str, f64)> {
+        let mut hits: Vec<(&str, f64)> = self
+            .candidates(signature)
+            .into_iter()
+            .map(|(id, sig)| (id, signature.similarity(sig)))
+            .filter(|(_, score)| *score >= threshold)
+            .collect();
+        sort_by_score(&mut hits, |(id, score)| (*id, *score));
+        hits
+    }
+}
+
+// --- graph helpers -----------------------------------------------------------
+
+/// A pair of units whose content is near-identical.
+#[derive(Debug, Clone)]
+pub struct NearDuplicate<'a> {
+    /// The earlier unit of the pair (lower ID).
+    pub first: &'a SemanticUnit,
+    /// The later unit of the pair.
+    pub second: &'a SemanticUnit,
+    /// Exact Jaccard similarity of their shingle sets.
+    pub score: f64,
+}
+
+/// Units in `graph` whose content is at least `threshold` similar to the unit
+/// identified by `id`, most similar first. The unit itself is excluded.
+///
+/// Scores are exact Jaccard similarities. Returns an empty vector if `id` is
+/// not in the graph.
+pub fn find_similar<'a>(graph: &'a Graph, id: &str, threshold: f64) -> Vec<(&'a SemanticUnit, f64)> {
+    let Some(target) = graph.get(id) else {
+        return vec![];
+    };
+    let mut hits: Vec<(&SemanticUnit, f64)> = graph
+        .units()
+        .filter(|u| u.id != id)
+        .map(|u| (u, jaccard(&target.content, &u.content)))
+        .filter(|(_, score)| *score >= threshold)
+        .collect();
+    sort_by_score(&mut hits, |(u, score)| (u.id.as_str(), *score));
+    hits
+}
+
+/// Every pair of units in `graph` that are at least `threshold` similar,
+/// most similar first.
+///
+/// Candidate pairs come from an LSH index so the whole graph is not compared
+/// pairwise; each candidate is then confirmed with its exact Jaccard score.
+pub fn near_duplicates(graph: &Graph, threshold: f64) -> Vec<NearDuplicate<'_>> {
+    let mut index = SimilarityIndex::new();
+    for unit in graph.units() {
+        index.insert_unit(unit);
+    }
+
+    let mut pairs = Vec::new();
+    for unit in graph.units() {
+        let signature = index.signature(&unit.id).expect("unit was just indexed");
+        for (other_id, _) in index.candidates(signature) {
+            if other_id <= unit.id.as_str() {
+                continue;
+            }
+            let other = graph.get(other_id).expect("indexed from graph");
+            let score = jaccard(&unit.content, &other.content);
+            if score >= threshold {
+                pairs.push(NearDuplicate {
+                    first: unit,
+                    second: other,
+                    score,
+                });
+            }
+        }
+    }
+    sort_by_score(&mut pairs, |p| (p.first.id.as_str(), p.score));
+    pairs
+}
+
+// --- helpers -----------------------------------------------------------------
+
+fn sort_by_score<T>(items: &mut [T], key: impl Fn(&T) -> (&str, f64)) {
+    items.sort_by(|a, b| {
+        let (a_id, a_score) = key(a);
+        let (b_id, b_score) = key(b);
+        b_score.total_cmp(&a_score).then_with(|| a_id.cmp(b_id))
+    });
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv(mut h: u64, bytes: &[u8]) -> u64 {
+    for b in bytes {
+        h ^= u64::from(*b);
+        h = h.wrapping_mul(FNV_PRIME);
+    }
+    h
+}
+
+fn hash_chars(chars: &[char]) -> u64 {
+    chars
+        .iter()
+        .fold(FNV_OFFSET, |h, c| fnv(h, &u32::from(*c).to_le_bytes()))
+}
+
+/// SplitMix64 finaliser: a cheap, well-distributed 64-bit mixer.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Per-hash-function seed, derived deterministically from its index.
+fn seed(i: usize) -> u64 {
+    mix((i as u64).wrapping_add(0x9e37_79b9_7f4a_7c15))
+}
+
+// --- tests -------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::UnitType;
+
+    fn unit(id: &str, content: &str) -> SemanticUnit {
+        let mut u = SemanticUnit::new(UnitType::Assertion, content, "test-agent");
+        u.id = id.into();
+        u
+    }
+
+    const A: &str = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6c";
+    const B: &str = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6d";
+    const C: &str = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6e";
+
+    #[test]
+    fn formatting_differences_are_ignored() {
+        assert_eq!(
+            jaccard("Water boils at 100 C.", "water   boils at 100 c"),
+            1.0
+        );
+    }
+
+    #[test]
+    fn empty_content_never_matches() {
+        assert_eq!(jaccard("", ""), 0.0);
+        assert_eq!(MinHash::of("...").similarity(&MinHash::of("")), 0.0);
+    }
+
+    #[test]
+    fn banding_threshold_sits_just_below_the_default() {
+        let threshold = (1.0 / BANDS as f64).powf(1.0 / ROWS_PER_BAND as f64);
+        assert!((0.4..DEFAULT_THRESHOLD).contains(&threshold), "{threshold}");
+        assert_eq!(BANDS * ROWS_PER_BAND, SIGNATURE_LEN);
+    }
+
+    #[test]
+    fn minhash_estimate_tracks_jaccard() {
+        let a = "The boiling point of water at sea level is 100 degrees Celsius.";
+        let b = "The boiling point of water at sea level is 100 degrees Celsius!";
+        let c = "Photosynthesis converts light energy into chemical energy.";
+        assert_eq!(MinHash::of(a).similarity(&MinHash::of(b)), 1.0);
+        assert!(MinHash::of(a).similarity(&MinHash::of(c)) < 0.2);
+    }
+
+    #[test]
+    fn signature_is_deterministic_and_round_trips() {
+        let sig = MinHash::of("stable across runs");
+        assert_eq!(sig, MinHash::of("stable across runs"));
+        assert_eq!(sig.band_keys().len(), BANDS);
+        assert_eq!(MinHash::from_values(sig.values().to_vec()), Some(sig));
+        assert!(MinHash::from_values(vec![1, 2, 3]).is_none());
+    }
+
+    #[test]
+    fn index_query_finds_near_duplicates_only() {
+        let mut index = SimilarityIndex::new();
+        index.insert_unit(&unit(A, "The boiling point of water at sea level is 100 degrees Celsius."));
+        index.insert_unit(&unit(B, "The boiling point of water at sea level is about 100 degrees Celsius."));
+        index.insert_unit(&unit(C, "Photosynthesis converts light energy into chemical energy."));
+
+        let query = MinHash::of("The boiling point of water at sea level is 100 degrees Celsius.");
+        let ids: Vec<&str> = index.query(&query, DEFAULT_THRESHOLD).into_iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![A, B]);
+
+        index.remove(B);
+        let ids: Vec<&str> = index.query(&query, DEFAULT_THRESHOLD).into_iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![A]);
+    }
+
+    #[test]
+    fn find_similar_excludes_self_and_ranks_by_score() {
+        let g = Graph::from_units(vec![
+            unit(A, "Rust guarantees memory safety without a garbage collector."),
+            unit(B, "Rust guarantees memory safety without garbage collection."),
+            unit(C, "Cats sleep for most of the day."),
+        ]);
+        let hits = find_similar(&g, A, DEFAULT_THRESHOLD);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0.id, B);
+        assert!(find_similar(&g, "missing", 0.0).is_empty());
+    }
+
+    #[test]
+    fn near_duplicates_reports_each_pair_once() {
+        let g = Graph::from_units(vec![
+            unit(A, "Rust guarantees memory safety without a garbage collector."),
+            unit(B, "Rust guarantees memory safety without a garbage collector!"),
+            unit(C, "Cats sleep for most of the day."),
+        ]);
+        let pairs = near_duplicates(&g, DEFAULT_THRESHOLD);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!((pairs[0].first.id.as_str(), pairs[0].second.id.as_str()), (A, B));
+        assert_eq!(pairs[0].score, 1.0);
+    }
+}
@@ -0,0 +1,504 @@
+//! Structured claim triples — the `x-org.semanticweft.triples` extension.
+//!
+//! `content` is free text written for humans. Units may additionally carry
+//! machine-readable subject–predicate–object triples under the registered
+//! extension field [`TRIPLES_EXTENSION`]:
+//!
+//! ```json
+//! "x-org.semanticweft.triples": [
+//!   {
+//!     "subject": "water",
+//!     "predicate": "boiling-point-celsius",
+//!     "object": { "kind": "literal", "datatype": "decimal", "value": 100 }
+//!   },
+//!   {
+//!     "subject": "water",
+//!     "predicate": "composed-of",
+//!     "object": { "kind": "entity", "id": "hydrogen" }
+//!   }
+//! ]
+//! ```
+//!
+//! Subjects, predicates, and entity objects are opaque identifiers (IRIs are
+//! recommended but not required). Literal objects carry a [`Datatype`] that
+//! their JSON `value` must conform to.
+//!
+//! [`validate_unit`](crate::validate_unit) checks the extension's shape via
+//! [`unit_triples`]. [`TripleIndex`] answers triple-pattern queries over a
+//! [`Graph`] and flags [`Contradiction`]s: units asserting different objects
+//! for the same subject and predicate.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::graph::Graph;
+use crate::types::SemanticUnit;
+
+/// The registered extension field that carries a unit's triples.
+pub const TRIPLES_EXTENSION: &str = "x-org.semanticweft.triples";
+
+/// A single subject–predicate–object claim.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Triple {
+    /// The thing the claim is about.
+    pub subject: String,
+    /// The property or relationship being asserted.
+    pub predicate: String,
+    /// The value of the property: another entity or a typed literal.
+    pub object: TripleObject,
+}
+
+/// The object position of a [`Triple`].
+///
+/// Serialises with a `"kind"` tag: `{"kind": "entity", "id": ...}` or
+/// `{"kind": "literal", "datatype": ..., "value": ...}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum TripleObject {
+    /// A reference to another entity by identifier.
+    Entity {
+        /// Identifier of the entity (an IRI is recommended).
+        id: String,
+    },
+    /// A literal value with an explicit datatype.
+    Literal {
+        /// How `value` is to be interpreted.
+        datatype: Datatype,
+        /// The literal value; its JSON type must match `datatype`.
+        value: serde_json::Value,
+    },
+}
+
+/// Datatypes for literal triple objects.
+///
+/// Serialises as a lowercase kebab-case string (e.g. `"date-time"`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum Datatype {
+    /// A JSON string.
+    String,
+    /// A JSON integer literal (`42`, not `42.0`).
+    Integer,
+    /// Any JSON number.
+    Decimal,
+    /// A JSON boolean.
+    Boolean,
+    /// A JSON string holding an ISO 8601 / RFC 3339 date-time.
+    DateTime,
+}
+
+/// Formats the datatype as its wire-format string (e.g. `"date-time"`).
+impl std::fmt::Display for Datatype {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Datatype::String => write!(f, "string"),
+            Datatype::Integer => write!(f, "integer"),
+            Datatype::Decimal => write!(f, "decimal"),
+            Datatype::Boolean => write!(f, "boolean"),
+            Datatype::DateTime => write!(f, "date-time"),
+        }
+    }
+}
+
+impl TripleObject {
+    /// `true` if both objects denote the same value.
+    ///
+    /// Numeric literals compare exactly by value, so `integer 100` and
+    /// `decimal 100.0` are the same object. Date-times compare as instants.
+    pub fn same_as(&self, other: &TripleObject) -> bool {
+        match (self, other) {
+            (TripleObject::Entity { id: a }, TripleObject::Entity { id: b }) => a == b,
+            (
+                TripleObject::Literal { datatype: da, value: va },
+                TripleObject::Literal { datatype: db, value: vb },
+            ) => {
+                let numeric = |d: &Datatype| matches!(d, Datatype::Integer | Datatype::Decimal);
+                if numeric(da) && numeric(db) {
+                    return match (va.as_number(), vb.as_number()) {
+                        (Some(a), Some(b)) => same_number(a, b),
+                        _ => va == vb,
+                    };
+                }
+                if da != db {
+                    return false;
+                }
+                if *da == Datatype::DateTime {
+                    let parse = |v: &serde_json::Value| {
+                        v.as_str()
+                            .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                    };
+                    if let (Some(a), Some(b)) = (parse(va), parse(vb)) {
+                        return a == b;
+                    }
+                }
+                va == vb
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Exact numeric equality: integers compare as integers, anything else by
+/// its decimal digits, so no two distinct values collapse onto one `f64`.
+fn same_number(a: &serde_json::Number, b: &serde_json::Number) -> bool {
+    let int = |n: &serde_json::Number| {
+        n.as_i64()
+            .map(i128::from)
+            .or_else(|| n.as_u64().map(i128::from))
+    };
+    match (int(a), int(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => decimal_parts(a) == decimal_parts(b),
+    }
+}
+
+/// `n` as a sign, its significant digits and the power of ten of the last
+/// digit, with leading and trailing zeros dropped (zero is `(false, "", 0)`).
+fn decimal_parts(n: &serde_json::Number) -> (bool, String, i64) {
+    let text = n.to_string();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.as_str()),
+    };
+    let (mantissa, mut exponent) = match text.split_once(['e', 'E']) {
+        Some((m, e)) => (m, e.parse::<i64>().unwrap_or(0)),
+        None => (text, 0),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{whole}{fraction}");
+    let significant = digits.trim_end_matches('0');
+    exponent += (digits.len() - significant.len()) as i64 - fraction.len() as i64;
+    let significant = significant.trim_start_matches('0');
+    if significant.is_empty() {
+        return (false, String::new(), 0);
+    }
+    (negative, significant.to_string(), exponent)
+}
+
+// --- validation --------------------------------------------------------------
+
+/// Errors in the shape of the [`TRIPLES_EXTENSION`] field.
+#[derive(Debug, Error, PartialEq)]
+pub enum TripleError {
+    #[error("{TRIPLES_EXTENSION} must be a non-empty array of triples: {0}")]
+    Malformed(String),
+
+    #[error("triple at index {0} has an empty subject")]
+    EmptySubject(usize),
+
+    #[error("triple at index {0} has an empty predicate")]
+    EmptyPredicate(usize),
+
+    #[error("triple at index {0} has an empty entity id")]
+    EmptyEntity(usize),
+
+    #[error("triple at index {0} has a literal value that is not a valid {1}")]
+    LiteralTypeMismatch(usize, Datatype),
+}
+
+/// The triples carried by `unit`.
+///
+/// Returns `Ok(vec![])` when the extension is absent, and an error if it is
+/// present but malformed: not a non-empty array of triples, an empty
+/// identifier, or a literal whose value does not match its datatype.
+pub fn unit_triples(unit: &SemanticUnit) -> Result<Vec<Triple>, TripleError> {
+    let Some(raw) = unit.extensions.get(TRIPLES_EXTENSION) else {
+        return Ok(vec![]);
+    };
+    let triples: Vec<Triple> = serde_json::from_value(raw.clone())
+        .map_err(|e| TripleError::Malformed(e.to_string()))?;
+    if triples.is_empty() {
+        return Err(TripleError::Malformed("array is empty".into()));
+    }
+
+    for (i, t) in triples.iter().enumerate() {
+        if t.subject.is_empty() {
+            return Err(TripleError::EmptySubject(i));
+        }
+        if t.predicate.is_empty() {
+            return Err(TripleError::EmptyPredicate(i));
+        }
+        match &t.object {
+            TripleObject::Entity { id } if id.is_empty() => {
+                return Err(TripleError::EmptyEntity(i));
+            }
+            TripleObject::Entity { .. } => {}
+            TripleObject::Literal { datatype, value } => {
+                let ok = match datatype {
+                    Datatype::String => value.is_string(),
+                    Datatype::Integer => value.is_i64() || value.is_u64(),
+                    Datatype::Decimal => value.is_number(),
+                    Datatype::Boolean => value.is_boolean(),
+                    Datatype::DateTime => value
+                        .as_str()
+                        .is_some_and(|s| chrono::DateTime::parse_from_rfc3339(s).is_ok()),
+                };
+                if !ok {
+                    return Err(TripleError::LiteralTypeMismatch(i, *datatype));
+                }
+            }
+        }
+    }
+    Ok(triples)
+}
+
+/// Attach `triples` to `unit` under [`TRIPLES_EXTENSION`], replacing any
+/// existing triples. An empty slice removes the extension.
+pub fn set_unit_triples(unit: &mut SemanticUnit, triples: &[Triple]) {
+    if triples.is_empty() {
+        unit.extensions.remove(TRIPLES_EXTENSION);
+    } else {
+        let value = serde_json::to_value(triples).expect("triples always serialise");
+        unit.extensions.insert(TRIPLES_EXTENSION.into(), value);
+    }
+}
+
+// --- index -------------------------------------------------------------------
+
+/// A triple asserted by a particular unit.
+#[derive(Debug, Clone)]
+pub struct Claim<'a> {
+    /// The unit carrying the triple.
+    pub unit: &'a SemanticUnit,
+    /// The asserted triple.
+    pub triple: Triple,
+}
+
+/// Two or more units asserting different objects for one subject–predicate pair.
+///
+/// Predicates are treated as single-valued: for multi-valued predicates
+/// (e.g. "has-author") a reported contradiction may be legitimate, and
+/// callers should interpret the result accordingly.
+#[derive(Debug, Clone)]
+pub struct Contradiction<'a> {
+    /// The shared subject.
+    pub subject: String,
+    /// The shared predicate.
+    pub predicate: String,
+    /// Every claim for this subject and predicate, ordered by unit id.
+    pub claims: Vec<Claim<'a>>,
+}
+
+/// An index of the triples carried by the units of a [`Graph`].
+///
+/// Units whose triples extension is malformed are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct TripleIndex<'a> {
+    /// (subject, predicate) → claims, ordered by unit id.
+    by_key: BTreeMap<(String, String), Vec<Claim<'a>>>,
+}
+
+impl<'a> TripleIndex<'a> {
+    /// Index every unit in `graph` that carries triples.
+    pub fn build(graph: &'a Graph) -> Self {
+        let mut units: Vec<&SemanticUnit> = graph.units().collect();
+        units.sort_by_key(|u| u.id.as_str());
+
+        let mut by_key: BTreeMap<(String, String), Vec<Claim<'a>>> = BTreeMap::new();
+        for unit in units {
+            for triple in unit_triples(unit).unwrap_or_default() {
+                by_key
+                    .entry((triple.subject.clone(), triple.predicate.clone()))
+                    .or_default()
+                    .push(Claim { unit, triple });
+            }
+        }
+        Self { by_key }
+    }
+
+    /// Total number of indexed triples.
+    pub fn len(&self) -> usize {
+        self.by_key.values().map(Vec::len).sum()
+    }
+
+    /// `true` if no unit in the graph carries triples.
+    pub fn is_empty(&self) -> bool {
+        self.by_key.is_empty()
+    }
+
+    /// Claims matching a triple pattern. `None` in a position matches
+    /// anything; results are ordered by subject, predicate, then unit id.
+    pub fn matching(
+        &self,
+        subject: Option<&str>,
+        predicate: Option<&str>,
+        object: Option<&TripleObject>,
+    ) -> Vec<&Claim<'a>> {
+        self.by_key
+            .iter()
+            .filter(|((s, p), _)| {
+                subject.is_none_or(|want| want == s) && predicate.is_none_or(|want| want == p)
+            })
+            .flat_map(|(_, claims)| claims)
+            .filter(|c| object.is_none_or(|want| c.triple.object.same_as(want)))
+            .collect()
+    }
+
+    /// Every subject–predicate pair with claims that disagree on the object.
+    pub fn contradictions(&self) -> Vec<Contradiction<'a>> {
+        self.by_key
+            .iter()
+            .filter(|(_, claims)| {
+                let first = &claims[0].triple.object;
+                claims[1..].iter().any(|c| !c.triple.object.same_as(first))
+            })
+            .map(|((subject, predicate), claims)| Contradiction {
+                subject: subject.clone(),
+                predicate: predicate.clone(),
+                claims: claims.clone(),
+            })
+            .collect()
+    }
+}
+
+// --- tests -------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::UnitType;
+    use serde_json::json;
+
+    fn unit_with(id: &str, triples: serde_json::Value) -> SemanticUnit {
+        let mut u = SemanticUnit::new(UnitType::Assertion, "claim", "test-agent");
+        u.id = id.into();
+        u.extensions.insert(TRIPLES_EXTENSION.into(), triples);
+        u
+    }
+
+    fn boiling_point(value: serde_json::Value, datatype: &str) -> serde_json::Value {
+        json!([{
+            "subject": "water",
+            "predicate": "boiling-point-celsius",
+            "object": { "kind": "literal", "datatype": datatype, "value": value }
+        }])
+    }
+
+    const A: &str = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6c";
+    const B: &str = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6d";
+    const C: &str = "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6e";
+
+    #[test]
+    fn absent_extension_yields_no_triples() {
+        let u = SemanticUnit::new(UnitType::Assertion, "claim", "test-agent");
+        assert_eq!(unit_triples(&u), Ok(vec![]));
+    }
+
+    #[test]
+    fn set_and_read_round_trip() {
+        let mut u = SemanticUnit::new(UnitType::Assertion, "claim", "test-agent");
+        let t = Triple {
+            subject: "water".into(),
+            predicate: "composed-of".into(),
+            object: TripleObject::Entity { id: "hydrogen".into() },
+        };
+        set_unit_triples(&mut u, std::slice::from_ref(&t));
+        assert_eq!(unit_triples(&u).unwrap(), vec![t]);
+        set_unit_triples(&mut u, &[]);
+        assert!(!u.extensions.contains_key(TRIPLES_EXTENSION));
+    }
+
+    #[test]
+    fn literal_must_match_datatype() {
+        let u = unit_with(A, boiling_point(json!("hot"), "decimal"));
+        assert_eq!(
+            unit_triples(&u),
+            Err(TripleError::LiteralTypeMismatch(0, Datatype::Decimal))
+        );
+        let u = unit_with(A, boiling_point(json!(99.5), "integer"));
+        assert!(unit_triples(&u).is_err());
+        let u = unit_with(A, boiling_point(json!("2026-02-18T12:00:00Z"), "date-time"));
+        assert!(unit_triples(&u).is_ok());
+    }
+
+    #[test]
+    fn malformed_shapes_are_rejected() {
+        assert!(matches!(
+            unit_triples(&unit_with(A, json!([]))),
+            Err(TripleError::Malformed(_))
+        ));
+        assert!(matches!(
+            unit_triples(&unit_with(A, json!({"subject": "x"}))),
+            Err(TripleError::Malformed(_))
+        ));
+        let empty_subject = json!([{
+            "subject": "", "predicate": "p", "object": { "kind": "entity", "id": "o" }
+        }]);
+        assert_eq!(
+            unit_triples(&unit_with(A, empty_subject)),
+            Err(TripleError::EmptySubject(0))
+        );
+    }
+
+    #[test]
+    fn numeric_literals_compare_by_value() {
+        let int = TripleObject::Literal { datatype: Datatype::Integer, value: json!(100) };
+        let dec = TripleObject::Literal { datatype: Datatype::Decimal, value: json!(100.0) };
+        let s = TripleObject::Literal { datatype: Datatype::String, value: json!("100") };
+        assert!(int.same_as(&dec));
+        assert!(!int.same_as(&s));
+    }
+
+    #[test]
+    fn large_integers_stay_distinct() {
+        let lit = |datatype, value| TripleObject::Literal { datatype, value };
+        let int = |v| lit(Datatype::Integer, v);
+        let dec = |v| lit(Datatype::Decimal, v);
+        // 2^53 + 1 rounds to 2^53 as an f64.
+        let big = json!(9_007_199_254_740_993_u64);
+        assert!(!int(big.clone()).same_as(&int(json!(9_007_199_254_740_992_u64))));
+        assert!(!int(big).same_as(&dec(json!(9_007_199_254_740_992.0))));
+        assert!(!int(json!(u64::MAX)).same_as(&int(json!(-1))));
+        assert!(int(json!(1_000_000)).same_as(&dec(json!(1e6))));
+        assert!(dec(json!(0.5)).same_as(&dec(json!(5e-1))));
+        assert!(dec(json!(0.0)).same_as(&int(json!(0))));
+        assert!(!dec(json!(-2.5)).same_as(&dec(json!(2.5))));
+    }
+
+
+    #[test]
+    fn index_matches_patterns() {
+        let g = Graph::from_units(vec![
+            unit_with(A, boiling_point(json!(100), "integer")),
+            unit_with(
+                B,
+                json!([{ "subject": "water", "predicate": "composed-of",
+                         "object": { "kind": "entity", "id": "hydrogen" } }]),
+            ),
+        ]);
+        let index = TripleIndex::build(&g);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.matching(Some("water"), None, None).len(), 2);
+        let hits = index.matching(None, Some("composed-of"), None);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].unit.id, B);
+        let hundred = TripleObject::Literal { datatype: Datatype::Decimal, value: json!(100) };
+        assert_eq!(index.matching(None, None, Some(&hundred))[0].unit.id, A);
+    }
+
+    #[test]
+    fn conflicting_objects_are_contradictions() {
+        let g = Graph::from_units(vec![
+            unit_with(A, boiling_point(json!(100), "integer")),
+            unit_with(B, boiling_point(json!(100.0), "decimal")),
+            unit_with(C, boiling_point(json!(90), "integer")),
+        ]);
+        let index = TripleIndex::build(&g);
+        let found = index.contradictions();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].predicate, "boiling-point-celsius");
+        let ids: Vec<&str> = found[0].claims.iter().map(|c| c.unit.id.as_str()).collect();
+        assert_eq!(ids, vec![A, B, C]);
+    }
+
+    #[test]
+    fn agreeing_claims_are_not_contradictions() {
+        let g = Graph::from_units(vec![
+            unit_with(A, boiling_point(json!(100), "integer")),
+            unit_with(B, boiling_point(json!(100.0), "decimal")),
+        ]);
+        assert!(TripleIndex::build(&g).contradictions().is_empty());
+    }
+}
@@ -211,6 +211,10 @@ pub struct Proof {
     pub created: String,
     /// Base58btc-encoded Ed25519 signature with multibase `z` prefix.
     pub value: String,
+    /// Present when `method` is a delegate key acting for the unit's author
+    /// under a [`Delegation`](crate::delegation::Delegation) (ADR-0014).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegation: Option<crate::delegation::Delegation>,
 }
 
 /// A Semantic Unit — the fundamental record type of the SemanticWeft protocol.
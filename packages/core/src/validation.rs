@@ -14,6 +14,7 @@ use std::sync::LazyLock;
 use regex::Regex;
 use thiserror::Error;
 
+use crate::triples::{unit_triples, TripleError};
 use crate::types::{SemanticUnit, Visibility};
 
 /// Errors returned when a [`SemanticUnit`] fails conformance validation.
@@ -55,6 +56,9 @@ pub enum ValidationError {
     )]
     InvalidExtensionFieldName(String),
 
+    #[error("invalid triples extension: {0}")]
+    InvalidTriples(#[from] TripleError),
+
     #[error("audience is required and must be non-empty when visibility is \"limited\"")]
     AudienceRequiredForLimited,
 
@@ -132,6 +136,9 @@ pub fn validate_unit(unit: &SemanticUnit) -> Result<(), ValidationError> {
         }
     }
 
+    // §6.1 — the registered triples extension, if present, must be well-formed.
+    unit_triples(unit)?;
+
     // §4.5–4.6 — visibility/audience co-validation rules.
     match &unit.visibility {
         Some(Visibility::Limited) => {
@@ -355,6 +362,20 @@ mod tests {
         assert_eq!(validate_unit(&u), Ok(()));
     }
 
+    #[test]
+    fn malformed_triples_extension_rejected() {
+        let mut u = minimal();
+        u.extensions.insert(
+            crate::triples::TRIPLES_EXTENSION.into(),
+            serde_json::json!([{ "subject": "water", "predicate": "boils-at",
+                                 "object": { "kind": "literal", "datatype": "integer", "value": "hot" } }]),
+        );
+        assert!(matches!(
+            validate_unit(&u),
+            Err(ValidationError::InvalidTriples(_))
+        ));
+    }
+
     #[test]
     fn limited_visibility_requires_audience() {
         let mut u = minimal();
@@ -475,6 +496,7 @@ mod tests {
             method: "did:key:z6MkHaXXX#z6MkHaXXX".into(),
             created: "2026-02-18T12:00:00Z".into(),
             value: "zSomeBase58Signature".into(),
+            delegation: None,
         });
         assert_eq!(validate_unit(&u), Ok(()));
     }
@@ -486,6 +508,7 @@ mod tests {
             method: "did:key:z6MkHaXXX#z6MkHaXXX".into(),
             created: "2026-02-18T12:00:00Z".into(),
             value: "BadPrefix".into(),
+            delegation: None,
         });
         assert!(matches!(
             validate_unit(&u),
@@ -500,6 +523,7 @@ mod tests {
             method: String::new(),
             created: "2026-02-18T12:00:00Z".into(),
             value: "zSomeBase58Signature".into(),
+            delegation: None,
         });
         assert!(matches!(
             validate_unit(&u),
@@ -514,6 +538,7 @@ mod tests {
             method: "did:key:z6MkHaXXX#z6MkHaXXX".into(),
             created: "not-a-date".into(),
             value: "zSomeBase58Signature".into(),
+            delegation: None,
         });
         assert!(matches!(
             validate_unit(&u),
@@ -76,6 +76,17 @@ pub struct AgentProfile {
     /// credibility score for federated units.
     #[serde(default = "default_agent_reputation")]
     pub reputation: f32,
+
+    /// The DID this agent rotated away from, if it took over from an earlier
+    /// key via a succession statement (spec §8.7). OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub predecessor: Option<String>,
+
+    /// The DID that replaced this agent's key. Set once a succession
+    /// statement is accepted; the profile is then kept only so the old DID
+    /// still resolves to its successor. OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub successor: Option<String>,
 }
 
 /// Request body for `POST /v1/agents/{did}` — register or update an agent.
@@ -197,6 +208,8 @@ mod tests {
             status: AgentStatus::Full,
             contribution_count: 0,
             reputation: 0.5,
+            predecessor: None,
+            successor: None,
         };
         let json = serde_json::to_string(&profile).unwrap();
         let back: AgentProfile = serde_json::from_str(&json).unwrap();
@@ -213,6 +226,8 @@ mod tests {
             status: AgentStatus::Probationary,
             contribution_count: 3,
             reputation: 0.75,
+            predecessor: None,
+            successor: None,
         };
         let json = serde_json::to_string(&profile).unwrap();
         assert!(json.contains("\"status\":\"probationary\""));
@@ -229,6 +244,7 @@ mod tests {
         assert_eq!(profile.status, AgentStatus::Full);
         assert_eq!(profile.contribution_count, 0);
         assert!((profile.reputation - 0.5).abs() < f32::EPSILON);
+        assert!(profile.predecessor.is_none() && profile.successor.is_none());
     }
 
     #[test]
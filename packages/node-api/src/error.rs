@@ -21,6 +21,11 @@ pub struct ErrorResponse {
     /// | `invalid_json` | 400 |
     /// | `invalid_parameter` | 400 |
     /// | `signing_required` | 401 |
+    /// | `unauthorized` | 401 |
+    /// | `signature_missing` | 401 |
+    /// | `date_out_of_range` | 401 |
+    /// | `unknown_agent` | 401 |
+    /// | `signature_invalid` | 401 |
     /// | `not_found` | 404 |
     /// | `id_conflict` | 409 |
     /// | `validation_failed` | 422 |
@@ -45,6 +50,16 @@ pub mod codes {
     pub const INVALID_JSON: &str = "invalid_json";
     pub const INVALID_PARAMETER: &str = "invalid_parameter";
     pub const SIGNING_REQUIRED: &str = "signing_required";
+    /// HTTP Signature authentication failed for a reason not listed below.
+    pub const UNAUTHORIZED: &str = "unauthorized";
+    /// The `Signature` or `Date` header is absent.
+    pub const SIGNATURE_MISSING: &str = "signature_missing";
+    /// The `Date` header is unparseable or outside the replay window.
+    pub const DATE_OUT_OF_RANGE: &str = "date_out_of_range";
+    /// The signing key does not belong to an agent registered on the node.
+    pub const UNKNOWN_AGENT: &str = "unknown_agent";
+    /// The signature does not verify against the signing string.
+    pub const SIGNATURE_INVALID: &str = "signature_invalid";
     pub const NOT_FOUND: &str = "not_found";
     pub const ID_CONFLICT: &str = "id_conflict";
     pub const VALIDATION_FAILED: &str = "validation_failed";
@@ -13,8 +13,11 @@
 //! | GET | `/v1/units/{id}` | → [`semanticweft::SemanticUnit`] |
 //! | GET | `/v1/units` | [`ListQuery`] → [`ListResponse`] |
 //! | GET | `/v1/units/{id}/subgraph` | [`SubgraphQuery`] → [`SubgraphResponse`] |
+//! | GET | `/v1/units/{id}/similar` | → [`SimilarResponse`] |
 //! | GET | `/v1/sync` | [`ListQuery`] → [`ListResponse`] (+ SSE) |
+//! | GET | `/v1/questions` | → [`QuestionsResponse`] |
 //! | GET | `/.well-known/semanticweft` | → [`NodeInfo`] |
+//! | GET | `/.well-known/webfinger` | → [`Jrd`] |
 //! | GET | `/v1/peers` | → [`PeersResponse`] |
 //! | POST | `/v1/peers` | [`PeerInfo`] → [`PeerInfo`] |
 //! | PATCH | `/v1/peers/{node_id}` | [`ReputationUpdate`] → [`PeerInfo`] |
@@ -25,18 +28,29 @@
 //! | POST | `/v1/agents/{did}/following` | [`FollowRequest`] → `204` |
 //! | GET | `/v1/agents/{did}/following` | → [`FollowListResponse`] |
 //! | GET | `/v1/agents/{did}/followers` | → [`FollowListResponse`] |
+//! | POST | `/v1/agents/{did}/succession` | [`SuccessionStatement`] → [`AgentProfile`] |
+//! | GET | `/v1/agents/{did}/succession` | → [`SuccessionStatement`] |
 
 pub mod agent;
 pub mod error;
 pub mod follow;
 pub mod node;
 pub mod peer;
+pub mod question;
+pub mod succession;
 pub mod unit;
+pub mod webfinger;
 
 pub use agent::{AgentProfile, AgentReputationUpdate, AgentStatus, ApplyRequest, InboxResponse, RegisterRequest};
 pub use error::ErrorResponse;
 pub use follow::{FollowEntry, FollowListResponse, FollowRequest};
 pub use node::{Capability, NodeInfo, PowParams};
 pub use peer::{PeerInfo, PeersResponse, ReputationUpdate};
-pub use unit::{ListQuery, ListResponse, SubgraphQuery, SubgraphResponse, SubmitResponse};
+pub use question::{QuestionEntry, QuestionsResponse};
+pub use succession::SuccessionStatement;
+pub use unit::{
+    ListQuery, ListResponse, SimilarResponse, SimilarUnit, SubgraphQuery, SubgraphResponse,
+    SubmitResponse,
+};
+pub use webfinger::{Jrd, JrdLink};
 pub use semanticweft::Proof;
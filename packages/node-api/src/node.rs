@@ -60,6 +60,11 @@ pub struct NodeInfo {
     /// have not yet generated a keypair.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub public_key: Option<String>,
+
+    /// Contributions a probationary agent needs to graduate to full
+    /// membership (ADR-0013). Absent on nodes without self-service admission.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub probation_threshold: Option<u32>,
 }
 
 impl NodeInfo {
@@ -78,12 +83,29 @@ impl NodeInfo {
             pow_required: None,
             contact: None,
             public_key: None,
+            probation_threshold: None,
         }
     }
+
+    /// Whether this node speaks a protocol version this crate understands:
+    /// the same major version as [`PROTOCOL_VERSION`](Self::PROTOCOL_VERSION).
+    /// Minor versions only add optional fields and capabilities.
+    pub fn is_compatible(&self) -> bool {
+        major(&self.protocol_version) == major(Self::PROTOCOL_VERSION)
+    }
+
+    /// Whether the node advertises `capability`.
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+}
+
+fn major(version: &str) -> &str {
+    version.split('.').next().unwrap_or(version).trim()
 }
 
 /// Optional features a node may advertise in its discovery document.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum Capability {
     /// The `/v1/sync` endpoint is available (all conformant nodes; listed for
@@ -104,6 +126,40 @@ pub enum Capability {
 
     /// Follow-graph management endpoints are available (spec §8.3, ADR-0007).
     Follows,
+
+    /// The `/v1/questions` lifecycle endpoint is available (spec §5.6).
+    Questions,
+
+    /// The `/v1/units/{id}/similar` near-duplicate endpoint is available (spec §5.7).
+    Similar,
+
+    /// A capability this version does not know, e.g. from a newer node.
+    /// Parsed rather than rejected so discovery keeps working.
+    #[serde(other)]
+    Unknown,
+}
+
+impl Capability {
+    /// The capability's name in the discovery document, e.g. `"sse"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Capability::Sync => "sync",
+            Capability::Sse => "sse",
+            Capability::Subgraph => "subgraph",
+            Capability::Peers => "peers",
+            Capability::Agents => "agents",
+            Capability::Follows => "follows",
+            Capability::Questions => "questions",
+            Capability::Similar => "similar",
+            Capability::Unknown => "unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for Capability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Proof-of-work parameters advertised in the discovery document (ADR-0006).
@@ -158,5 +214,26 @@ mod tests {
         assert_eq!(json, r#""sse""#);
         let json = serde_json::to_string(&Capability::Subgraph).unwrap();
         assert_eq!(json, r#""subgraph""#);
+        assert_eq!(Capability::Subgraph.to_string(), "subgraph");
+    }
+
+    #[test]
+    fn unknown_capabilities_are_tolerated() {
+        let caps: Vec<Capability> =
+            serde_json::from_str(r#"["sync", "telepathy", "sse"]"#).unwrap();
+        assert_eq!(caps, [Capability::Sync, Capability::Unknown, Capability::Sse]);
+        assert_eq!(Capability::Unknown.to_string(), "unknown");
+    }
+
+    #[test]
+    fn compatibility_follows_major_version() {
+        let mut info = NodeInfo::new("did:key:z6Mk", "https://example.com");
+        assert!(info.is_compatible());
+        assert!(info.supports(Capability::Sync));
+        assert!(!info.supports(Capability::Sse));
+        info.protocol_version = "1.3".into();
+        assert!(info.is_compatible());
+        info.protocol_version = "2.0".into();
+        assert!(!info.is_compatible());
     }
 }
@@ -0,0 +1,52 @@
+//! Question lifecycle types — `GET /v1/questions` (spec §5.6).
+//!
+//! Each entry pairs a `question` unit with the status derived from the units
+//! that reference it (see [`semanticweft::questions`]).
+
+use serde::{Deserialize, Serialize};
+
+use semanticweft::{QuestionStatus, SemanticUnit};
+
+/// A single question with its derived lifecycle status.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuestionEntry {
+    /// The `question` unit.
+    pub unit: SemanticUnit,
+
+    /// Whether the question is open, answered, or disputed.
+    pub status: QuestionStatus,
+
+    /// IDs of units that answer the question (`derives-from` / `supports`).
+    #[serde(default)]
+    pub answers: Vec<String>,
+
+    /// IDs of units that dispute the question (`questions` / `rebuts`).
+    #[serde(default)]
+    pub disputes: Vec<String>,
+}
+
+/// Response body for `GET /v1/questions` (spec §5.6).
+///
+/// # Example
+///
+/// ```json
+/// {
+///   "questions": [ { "unit": { ... }, "status": "open", "answers": [], "disputes": [] } ],
+///   "cursor": "019526b2-f68a-7c3e-a0b4-1d2e3f4a5b6d",
+///   "has_more": false
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuestionsResponse {
+    /// Matching questions on this page, in ascending `id` order.
+    pub questions: Vec<QuestionEntry>,
+
+    /// The `id` of the last question the node examined for this page. Pass as
+    /// `?after=` to fetch the next page. Because filtering happens after the
+    /// scan, this may be later than the last entry in `questions`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+
+    /// `true` if there may be more matching questions after `cursor`.
+    pub has_more: bool,
+}
@@ -0,0 +1,77 @@
+//! Key rotation types — `POST/GET /v1/agents/{did}/succession` (spec §8.7).
+//!
+//! A `did:key` cannot change its key, so rotating a key means moving to a new
+//! DID. A succession statement links the two: the old key signs a statement
+//! naming the new DID, and the new key countersigns it. A node that accepts
+//! the statement moves the agent's profile, reputation and follow edges to
+//! the successor and keeps the statement, so units signed by the old key
+//! remain attributable to the same agent.
+//!
+//! Signing and verification live in `semanticweft-agent-core`
+//! (`succession` module); this crate only defines the wire format.
+
+use serde::{Deserialize, Serialize};
+
+/// A signed statement that `successor` replaces `predecessor`.
+///
+/// Both signatures are detached Ed25519 signatures (`z`-prefixed base58btc)
+/// over the JCS canonical form (RFC 8785) of the statement:
+///
+/// - `predecessor_signature` covers the statement without either signature;
+/// - `successor_signature` covers the statement with `predecessor_signature`
+///   included, countersigning the old key's consent.
+///
+/// # Example
+///
+/// ```json
+/// {
+///   "predecessor": "did:key:z6MkOld",
+///   "successor": "did:key:z6MkNew",
+///   "created_at": "2026-03-01T12:00:00Z",
+///   "reason": "scheduled rotation",
+///   "predecessor_signature": "z3Fq…",
+///   "successor_signature": "z5Lm…"
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SuccessionStatement {
+    /// The retiring DID, whose key signs first.
+    pub predecessor: String,
+
+    /// The DID that takes over, whose key countersigns.
+    pub successor: String,
+
+    /// When the statement was made (ISO 8601).
+    pub created_at: String,
+
+    /// Free-text reason for the rotation (e.g. "key compromised"). OPTIONAL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Signature by the predecessor's key.
+    pub predecessor_signature: String,
+
+    /// Countersignature by the successor's key.
+    pub successor_signature: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn succession_statement_roundtrip() {
+        let statement = SuccessionStatement {
+            predecessor: "did:key:z6MkOld".into(),
+            successor: "did:key:z6MkNew".into(),
+            created_at: "2026-03-01T12:00:00Z".into(),
+            reason: None,
+            predecessor_signature: "zOld".into(),
+            successor_signature: "zNew".into(),
+        };
+        let json = serde_json::to_string(&statement).unwrap();
+        assert!(!json.contains("reason"));
+        let back: SuccessionStatement = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, statement);
+    }
+}
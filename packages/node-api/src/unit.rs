@@ -52,6 +52,15 @@ pub struct ListQuery {
     /// ISO 8601 timestamp.
     pub since: Option<String>,
 
+    /// Filter: include only units carrying a claim triple
+    /// (`x-org.semanticweft.triples`) with this subject.
+    pub subject: Option<String>,
+
+    /// Filter: include only units carrying a claim triple with this
+    /// predicate. When combined with `subject`, both must match the same
+    /// triple.
+    pub predicate: Option<String>,
+
     /// Pagination cursor: include only units whose `id` is lexicographically
     /// after this UUIDv7 string.
     pub after: Option<String>,
@@ -163,6 +172,34 @@ pub struct SubgraphResponse {
     pub units: Vec<SemanticUnit>,
 }
 
+// ---------------------------------------------------------------------------
+// Similar
+// ---------------------------------------------------------------------------
+
+/// A unit whose content closely matches the queried unit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SimilarUnit {
+    /// The matching unit.
+    pub unit: SemanticUnit,
+
+    /// Estimated content similarity in `[0, 1]` (MinHash Jaccard estimate).
+    pub score: f64,
+}
+
+/// Response body for `GET /v1/units/{id}/similar` (spec §5.7).
+///
+/// # Example
+///
+/// ```json
+/// { "similar": [ { "unit": { ... }, "score": 0.92 } ] }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SimilarResponse {
+    /// Near-duplicates of the queried unit, most similar first. The queried
+    /// unit itself is never included.
+    pub similar: Vec<SimilarUnit>,
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -0,0 +1,88 @@
+//! WebFinger types — `GET /.well-known/webfinger` (spec §8.4, RFC 7033).
+//!
+//! A node resolves an agent address `acct:{did}@{host}` to a JSON Resource
+//! Descriptor whose links point at the agent's profile and inbox on that
+//! node. Resolution logic lives in `semanticweft-agent-core` (`webfinger`
+//! module); this crate only defines the wire format.
+
+use serde::{Deserialize, Serialize};
+
+/// Link relation of the agent's profile (`GET /v1/agents/{did}`).
+pub const REL_PROFILE: &str = "self";
+
+/// Link relation of the agent's inbox, as in W3C Linked Data Notifications.
+pub const REL_INBOX: &str = "http://www.w3.org/ns/ldp#inbox";
+
+/// A JSON Resource Descriptor (RFC 7033 §4.4).
+///
+/// # Example
+///
+/// ```json
+/// {
+///   "subject": "acct:did:key:z6MkAgent@node.example.com",
+///   "links": [
+///     { "rel": "self", "type": "application/json",
+///       "href": "https://node.example.com/v1/agents/did%3Akey%3Az6MkAgent" },
+///     { "rel": "http://www.w3.org/ns/ldp#inbox", "type": "application/json",
+///       "href": "https://node.example.com/v1/agents/did%3Akey%3Az6MkAgent/inbox" }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Jrd {
+    /// The resolved resource, e.g. `acct:did:key:z6Mk…@node.example.com`.
+    pub subject: String,
+
+    /// Other identifiers for the same resource. OPTIONAL.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+
+    /// Links from the resource to related endpoints.
+    #[serde(default)]
+    pub links: Vec<JrdLink>,
+}
+
+impl Jrd {
+    /// The `href` of the first link with relation `rel`.
+    pub fn href(&self, rel: &str) -> Option<&str> {
+        self.links
+            .iter()
+            .find(|link| link.rel == rel)
+            .and_then(|link| link.href.as_deref())
+    }
+}
+
+/// One link in a [`Jrd`] (RFC 7033 §4.4.4).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JrdLink {
+    /// Link relation type: a registered name or a URI.
+    pub rel: String,
+
+    /// Media type of the target. OPTIONAL.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+
+    /// Target URL. OPTIONAL in RFC 7033, always set by SemanticWeft nodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub href: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jrd_tolerates_foreign_links() {
+        let json = r#"{
+            "subject": "acct:did:key:z6MkA@example.com",
+            "links": [
+                { "rel": "http://webfinger.net/rel/avatar", "type": "image/png" },
+                { "rel": "self", "type": "application/json", "href": "https://example.com/v1/agents/a" }
+            ]
+        }"#;
+        let jrd: Jrd = serde_json::from_str(json).unwrap();
+        assert!(jrd.aliases.is_empty());
+        assert_eq!(jrd.href(REL_PROFILE), Some("https://example.com/v1/agents/a"));
+        assert_eq!(jrd.href(REL_INBOX), None);
+    }
+}
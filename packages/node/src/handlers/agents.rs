@@ -4,6 +4,8 @@
 //! - `GET  /v1/agents/{did}` — retrieve an agent profile.
 //! - `GET  /v1/agents/{did}/inbox` — retrieve the agent's pending inbox items.
 //! - `POST /v1/agents/{did}/inbox` — deliver a unit to the agent's inbox (S2S, requires NodeAuth).
+//! - `POST /v1/agents/{did}/succession` — rotate the agent's key to a new DID.
+//! - `GET  /v1/agents/{did}/succession` — the statement that retired `{did}`.
 //!
 //! # Inbox
 //!
@@ -19,13 +21,15 @@ use axum::{
 };
 use serde::Deserialize;
 use semanticweft::{validate_unit, SemanticUnit};
+use semanticweft_agent_core::verify_succession;
 use semanticweft_node_api::{
     AgentProfile, AgentReputationUpdate, AgentStatus, ApplyRequest, InboxResponse, RegisterRequest,
+    SuccessionStatement,
 };
 use tracing::{info, warn};
 
 use crate::error::AppError;
-use crate::middleware::auth::{NodeAuth, RequireAuth};
+use crate::middleware::auth::{KeyAuth, NodeAuth, RequireAuth, RequireOwnKey};
 
 use super::AppState;
 
@@ -38,14 +42,28 @@ pub struct InboxQueryParams {
     pub limit: Option<u32>,
 }
 
+/// Reject a `public_key` that is not the key embedded in a `did:key` DID.
+///
+/// Signatures are verified against the stored key, so accepting a different
+/// one would let whoever holds it act as `did`.
+fn check_public_key(did: &str, public_key: Option<&str>) -> Result<(), AppError> {
+    match (did.strip_prefix("did:key:"), public_key) {
+        (Some(embedded), Some(key)) if key != embedded => Err(AppError::BadRequest(
+            "public_key does not match the key embedded in the did:key DID".into(),
+        )),
+        _ => Ok(()),
+    }
+}
+
 /// `POST /v1/agents/{did}` — register or update an agent.
 ///
-/// The `did` in the path and in the request body must match; returns 400
-/// if they differ. Returns 201 with the stored profile.
+/// The `did` in the path and in the request body must match, and a
+/// `public_key` must be the one in a `did:key`; returns 400 otherwise.
+/// Delegated requests get 403. Returns 201 with the stored profile.
 pub async fn register(
     State(state): State<AppState>,
     Path(did): Path<String>,
-    auth: RequireAuth,
+    auth: RequireOwnKey,
     Json(req): Json<RegisterRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     if req.did != did {
@@ -59,15 +77,28 @@ pub async fn register(
             "cannot register as a different DID".into(),
         ));
     }
-
-    let profile = AgentProfile {
-        did: req.did,
-        inbox_url: req.inbox_url,
-        display_name: req.display_name,
-        public_key: req.public_key,
-        status: AgentStatus::Full,
-        contribution_count: 0,
-        reputation: 0.5,
+    check_public_key(&did, req.public_key.as_deref())?;
+
+    // Re-registration updates what the agent controls; status, reputation,
+    // contribution count and rotation links belong to the node.
+    let profile = match state.storage.get_agent(&did).await? {
+        Some(existing) => AgentProfile {
+            inbox_url: req.inbox_url,
+            display_name: req.display_name,
+            public_key: req.public_key,
+            ..existing
+        },
+        None => AgentProfile {
+            did: req.did,
+            inbox_url: req.inbox_url,
+            display_name: req.display_name,
+            public_key: req.public_key,
+            status: AgentStatus::Full,
+            contribution_count: 0,
+            reputation: 0.5,
+            predecessor: None,
+            successor: None,
+        },
     };
 
     state.storage.put_agent(&profile).await?;
@@ -136,7 +167,7 @@ pub async fn inbox(
 pub async fn delete_agent(
     State(state): State<AppState>,
     Path(did): Path<String>,
-    auth: RequireAuth,
+    auth: RequireOwnKey,
 ) -> Result<impl IntoResponse, AppError> {
     if auth.did != did {
         return Err(AppError::Forbidden(
@@ -155,6 +186,103 @@ pub async fn delete_agent(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// `POST /v1/agents/{did}/succession` — rotate an agent's key (spec §8.7).
+///
+/// The body is a [`SuccessionStatement`] retiring `{did}`. It authenticates
+/// itself — the old key signs it and the new key countersigns — so no HTTP
+/// Signature is needed, and a holder of the old key can still rotate after
+/// losing everything else.
+///
+/// On success the successor DID is registered with the predecessor's status,
+/// contribution count and reputation, follow edges and pending inbox items
+/// move to it, and the old profile keeps a `successor` link so units signed
+/// by the old key stay attributable. The old DID can no longer authenticate.
+///
+/// Returns 201 with the successor's profile, or 200 if this node already
+/// accepted a rotation to the same successor. Returns 400 if the statement
+/// does not retire `{did}`, 401 if a signature does not verify, 404 if
+/// `{did}` is not registered, and 409 if `{did}` was already rotated to a
+/// different DID or the successor is already registered.
+pub async fn succeed(
+    State(state): State<AppState>,
+    Path(did): Path<String>,
+    Json(statement): Json<SuccessionStatement>,
+) -> Result<impl IntoResponse, AppError> {
+    if statement.predecessor != did {
+        return Err(AppError::BadRequest(
+            "predecessor in the statement must match the {did} path parameter".into(),
+        ));
+    }
+    verify_succession(&statement).map_err(|e| AppError::Unauthorized(e.to_string()))?;
+
+    let old = state
+        .storage
+        .get_agent(&did)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("agent {did} not found")))?;
+
+    if let Some(existing) = old.successor.as_deref() {
+        if existing != statement.successor {
+            return Err(AppError::Conflict(format!(
+                "agent {did} was already rotated to {existing}"
+            )));
+        }
+        let profile = state
+            .storage
+            .get_agent(existing)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("agent {existing} not found")))?;
+        return Ok((StatusCode::OK, Json(profile)));
+    }
+    if state.storage.get_agent(&statement.successor).await?.is_some() {
+        return Err(AppError::Conflict(format!(
+            "agent {} is already registered",
+            statement.successor
+        )));
+    }
+
+    // The multibase key appears verbatim in both raw and percent-encoded
+    // DIDs, so this re-points an inbox URL hosted under the old DID.
+    let old_key = did.strip_prefix("did:key:").unwrap_or(&did);
+    let new_key = statement
+        .successor
+        .strip_prefix("did:key:")
+        .unwrap_or(&statement.successor);
+
+    let profile = AgentProfile {
+        did: statement.successor.clone(),
+        inbox_url: old.inbox_url.replace(old_key, new_key),
+        display_name: old.display_name,
+        public_key: Some(new_key.to_string()),
+        status: old.status,
+        contribution_count: old.contribution_count,
+        reputation: old.reputation,
+        predecessor: Some(did.clone()),
+        successor: None,
+    };
+
+    state.storage.apply_succession(&statement, &profile).await?;
+    info!("agent_succession: {} rotated to {}", did, statement.successor);
+    Ok((StatusCode::CREATED, Json(profile)))
+}
+
+/// `GET /v1/agents/{did}/succession` — the statement that retired `{did}`.
+///
+/// Lets anyone holding units signed by an old key find, and verify, the DID
+/// that now speaks for the same agent. Returns 404 if `{did}` has not been
+/// rotated on this node.
+pub async fn get_succession(
+    State(state): State<AppState>,
+    Path(did): Path<String>,
+) -> Result<Json<SuccessionStatement>, AppError> {
+    let statement = state
+        .storage
+        .get_succession(&did)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("no succession recorded for {did}")))?;
+    Ok(Json(statement))
+}
+
 /// `POST /v1/agents/{did}/inbox` — node-to-node push delivery (spec §8.6).
 ///
 /// Accepts a [`SemanticUnit`] from a remote node and delivers it to the
@@ -204,11 +332,16 @@ pub async fn inbox_deliver(
 /// (fire-and-forget) with the admission details. Webhook delivery failure does
 /// not affect the admission response.
 ///
+/// The applicant is not yet registered, so the HTTP Signature is verified
+/// against the key embedded in their `did:key` rather than a stored profile.
+/// When `public_key` is omitted it is taken from the `did:key`, so the new
+/// member can authenticate normally afterwards.
+///
 /// Returns 201 with the stored profile, 409 if the DID is already registered.
 pub async fn apply(
     State(state): State<AppState>,
     Path(did): Path<String>,
-    auth: RequireAuth,
+    auth: KeyAuth,
     Json(req): Json<ApplyRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     if req.did != did {
@@ -219,6 +352,7 @@ pub async fn apply(
     if auth.did != did {
         return Err(AppError::Forbidden("cannot apply as a different DID".into()));
     }
+    check_public_key(&did, req.public_key.as_deref())?;
 
     // Reject if already registered.
     if state.storage.get_agent(&did).await?.is_some() {
@@ -234,14 +368,20 @@ pub async fn apply(
         None => false,
     };
 
+    let public_key = req
+        .public_key
+        .or_else(|| did.strip_prefix("did:key:").map(str::to_string));
+
     let profile = AgentProfile {
         did: req.did.clone(),
         inbox_url: req.inbox_url,
         display_name: req.display_name,
-        public_key: req.public_key,
+        public_key,
         status: AgentStatus::Probationary,
         contribution_count: 0,
         reputation: 0.5,
+        predecessor: None,
+        successor: None,
     };
 
     state.storage.put_agent(&profile).await?;
@@ -385,7 +525,8 @@ mod tests {
     use ed25519_dalek::SigningKey;
     use rand::rngs::OsRng;
     use semanticweft::{SemanticUnit, UnitType};
-    use semanticweft_node_api::AgentProfile;
+    use semanticweft_agent_core::{sign_succession, AgentIdentity};
+    use semanticweft_node_api::{AgentProfile, SuccessionStatement};
     use tower::ServiceExt;
 
     use crate::{
@@ -443,6 +584,8 @@ mod tests {
             status: semanticweft_node_api::AgentStatus::Full,
             contribution_count: 0,
             reputation: 0.5,
+            predecessor: None,
+            successor: None,
         }).await.unwrap();
 
         let (node_key, node_did) = make_node_key_and_did();
@@ -479,6 +622,8 @@ mod tests {
             status: semanticweft_node_api::AgentStatus::Full,
             contribution_count: 0,
             reputation: 0.5,
+            predecessor: None,
+            successor: None,
         }).await.unwrap();
 
         let unit = make_unit("did:key:z6MkSomeSender");
@@ -543,6 +688,8 @@ mod tests {
                 status: semanticweft_node_api::AgentStatus::Full,
                 contribution_count: 0,
                 reputation: 0.5,
+                predecessor: None,
+                successor: None,
             })
             .await
             .unwrap();
@@ -611,6 +758,8 @@ mod tests {
                 status: semanticweft_node_api::AgentStatus::Full,
                 contribution_count: 0,
                 reputation: 0.5,
+                predecessor: None,
+                successor: None,
             })
             .await
             .unwrap();
@@ -672,4 +821,236 @@ mod tests {
         let resp = signed_delete(Arc::clone(&storage)).await;
         assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
     }
+
+    // -----------------------------------------------------------------------
+    // POST/GET /v1/agents/{did}/succession
+    // -----------------------------------------------------------------------
+
+    fn post_succession(did: &str, statement: &SuccessionStatement) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(format!("/v1/agents/{did}/succession"))
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(statement).unwrap()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn succession_moves_profile_follows_and_inbox() {
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStorage::new());
+        let old = AgentIdentity::generate();
+        let new = AgentIdentity::generate();
+        let old_key = SigningKey::from_bytes(&old.seed());
+        let (old_did, _) = seed_agent_with_key(&storage, &old_key).await;
+        storage.update_agent_reputation(&old_did, 0.9).await.unwrap();
+        let (_, friend) = make_node_key_and_did();
+        storage.add_follow(&old_did, &friend).await.unwrap();
+        storage.add_follow(&friend, &old_did).await.unwrap();
+        storage.deliver_to_inbox(&old_did, &make_unit(&friend)).await.unwrap();
+
+        let statement = sign_succession(&old, &new, "2026-03-01T12:00:00Z", None);
+        let resp = build_app(storage.clone())
+            .oneshot(post_succession(&old_did, &statement))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+
+        let successor = storage.get_agent(&new.did()).await.unwrap().unwrap();
+        assert_eq!(successor.reputation, 0.9);
+        assert_eq!(successor.predecessor.as_deref(), Some(old_did.as_str()));
+        assert_eq!(successor.public_key, Some(new.public_key_multibase()));
+        assert!(successor.inbox_url.contains(&new.did()));
+        let retired = storage.get_agent(&old_did).await.unwrap().unwrap();
+        assert_eq!(retired.successor, Some(new.did()));
+
+        assert_eq!(storage.list_following(&new.did()).await.unwrap(), [friend.as_str()]);
+        assert_eq!(storage.list_followers(&new.did()).await.unwrap(), [friend.as_str()]);
+        assert!(storage.list_followers(&old_did).await.unwrap().is_empty());
+        let (items, _) = storage.get_inbox(&new.did(), None, 10).await.unwrap();
+        assert_eq!(items.len(), 1);
+
+        assert_eq!(
+            storage.get_succession(&old_did).await.unwrap(),
+            Some(statement.clone())
+        );
+
+        // Replaying the same statement is a no-op; a second rotation is not.
+        let resp = build_app(storage.clone())
+            .oneshot(post_succession(&old_did, &statement))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let fork = sign_succession(&old, &AgentIdentity::generate(), "2026-03-01T12:00:00Z", None);
+        let resp = build_app(storage.clone())
+            .oneshot(post_succession(&old_did, &fork))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        // The old key no longer authenticates.
+        let path = format!("/v1/agents/{old_did}");
+        let (date, sig) = build_outbound_signature(&old_key, &old_did, "delete", &path, "localhost");
+        let req = Request::builder()
+            .method("DELETE")
+            .uri(&path)
+            .header("host", "localhost")
+            .header("date", &date)
+            .header("signature", &sig)
+            .body(Body::empty())
+            .unwrap();
+        let resp = build_app(storage).oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn succession_rejects_bad_statements() {
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStorage::new());
+        let old = AgentIdentity::generate();
+        let new = AgentIdentity::generate();
+        let (old_did, _) =
+            seed_agent_with_key(&storage, &SigningKey::from_bytes(&old.seed())).await;
+        let statement = sign_succession(&old, &new, "2026-03-01T12:00:00Z", None);
+
+        let mut forged = statement.clone();
+        forged.successor = AgentIdentity::generate().did();
+        let resp = build_app(storage.clone())
+            .oneshot(post_succession(&old_did, &forged))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let resp = build_app(storage.clone())
+            .oneshot(post_succession(&new.did(), &statement))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let unregistered = sign_succession(&new, &old, "2026-03-01T12:00:00Z", None);
+        let resp = build_app(storage.clone())
+            .oneshot(post_succession(&new.did(), &unregistered))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        assert!(storage.get_succession(&old_did).await.unwrap().is_none());
+    }
+
+    // -----------------------------------------------------------------------
+    // POST /v1/agents/{did}
+    // -----------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn re_register_keeps_node_managed_fields() {
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStorage::new());
+        let (key, _) = make_node_key_and_did();
+        let (did, multibase) = seed_agent_with_key(&storage, &key).await;
+        let mut seeded = storage.get_agent(&did).await.unwrap().unwrap();
+        seeded.status = semanticweft_node_api::AgentStatus::Probationary;
+        seeded.contribution_count = 7;
+        seeded.reputation = 0.9;
+        seeded.predecessor = Some("did:key:z6MkEarlier".into());
+        storage.put_agent(&seeded).await.unwrap();
+
+        let path = format!("/v1/agents/{did}");
+        let (date, sig) = build_outbound_signature(&key, &did, "post", &path, "localhost");
+        let body = serde_json::json!({
+            "did": did,
+            "inbox_url": "https://elsewhere.example/inbox",
+            "display_name": "Renamed",
+            "public_key": multibase,
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri(&path)
+            .header("content-type", "application/json")
+            .header("host", "localhost")
+            .header("date", &date)
+            .header("signature", &sig)
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let resp = build_app(storage.clone()).oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+
+        let stored = storage.get_agent(&did).await.unwrap().unwrap();
+        assert_eq!(stored.display_name.as_deref(), Some("Renamed"));
+        assert_eq!(stored.inbox_url, "https://elsewhere.example/inbox");
+        assert_eq!(stored.status, semanticweft_node_api::AgentStatus::Probationary);
+        assert_eq!(stored.contribution_count, 7);
+        assert_eq!(stored.reputation, 0.9);
+        assert_eq!(stored.predecessor.as_deref(), Some("did:key:z6MkEarlier"));
+    }
+
+    #[tokio::test]
+    async fn register_rejects_public_key_not_matching_did() {
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStorage::new());
+        let (key, _) = make_node_key_and_did();
+        let (did, multibase) = seed_agent_with_key(&storage, &key).await;
+        let (_, other_did) = make_node_key_and_did();
+
+        let path = format!("/v1/agents/{did}");
+        let (date, sig) = build_outbound_signature(&key, &did, "post", &path, "localhost");
+        let body = serde_json::json!({
+            "did": did,
+            "inbox_url": format!("http://localhost/v1/agents/{did}/inbox"),
+            "public_key": other_did.strip_prefix("did:key:").unwrap(),
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri(&path)
+            .header("content-type", "application/json")
+            .header("host", "localhost")
+            .header("date", &date)
+            .header("signature", &sig)
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let resp = build_app(storage.clone()).oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let stored = storage.get_agent(&did).await.unwrap().unwrap();
+        assert_eq!(stored.public_key, Some(multibase));
+    }
+
+    // -----------------------------------------------------------------------
+    // POST /v1/agents/{did}/apply
+    // -----------------------------------------------------------------------
+
+    fn signed_apply(key: &SigningKey, did: &str, public_key: Option<&str>) -> Request<Body> {
+        let path = format!("/v1/agents/{did}/apply");
+        let (date, sig) = build_outbound_signature(key, did, "post", &path, "localhost");
+        let body = serde_json::json!({
+            "did": did,
+            "inbox_url": format!("http://localhost/v1/agents/{did}/inbox"),
+            "public_key": public_key,
+        });
+        Request::builder()
+            .method("POST")
+            .uri(&path)
+            .header("content-type", "application/json")
+            .header("host", "localhost")
+            .header("date", &date)
+            .header("signature", &sig)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn apply_rejects_public_key_not_matching_did() {
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStorage::new());
+        let (key, did) = make_node_key_and_did();
+        let (_, other_did) = make_node_key_and_did();
+        let other_key = other_did.strip_prefix("did:key:").unwrap();
+
+        let resp = build_app(storage.clone())
+            .oneshot(signed_apply(&key, &did, Some(other_key)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert!(storage.get_agent(&did).await.unwrap().is_none());
+
+        let own_key = did.strip_prefix("did:key:").unwrap();
+        let resp = build_app(storage.clone())
+            .oneshot(signed_apply(&key, &did, Some(own_key)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
 }
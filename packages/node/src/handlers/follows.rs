@@ -20,7 +20,7 @@ use semanticweft_node_api::{FollowEntry, FollowListResponse, FollowRequest};
 use tracing::info;
 
 use crate::error::AppError;
-use crate::middleware::auth::RequireAuth;
+use crate::middleware::auth::RequireOwnKey;
 
 use super::AppState;
 
@@ -36,7 +36,7 @@ use super::AppState;
 pub async fn follow(
     State(state): State<AppState>,
     Path(did): Path<String>,
-    auth: RequireAuth,
+    auth: RequireOwnKey,
     Json(req): Json<FollowRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     if auth.did != did {
@@ -75,7 +75,7 @@ pub async fn follow(
 pub async fn unfollow(
     State(state): State<AppState>,
     Path((did, target)): Path<(String, String)>,
-    auth: RequireAuth,
+    auth: RequireOwnKey,
 ) -> Result<impl IntoResponse, AppError> {
     if auth.did != did {
         return Err(AppError::Forbidden(
@@ -190,6 +190,8 @@ mod tests {
                 status: semanticweft_node_api::AgentStatus::Full,
                 contribution_count: 0,
                 reputation: 0.5,
+                predecessor: None,
+                successor: None,
             })
             .await
             .unwrap();
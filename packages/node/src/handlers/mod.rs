@@ -10,6 +10,7 @@ pub mod agents;
 pub mod follows;
 pub mod node;
 pub mod peers;
+pub mod questions;
 pub mod units;
 pub mod webfinger;
 
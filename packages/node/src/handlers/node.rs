@@ -15,6 +15,7 @@ pub async fn well_known(State(state): State<AppState>) -> Json<NodeInfo> {
     info.name = cfg.name.clone();
     info.contact = cfg.contact.clone();
     info.public_key = cfg.public_key.clone();
+    info.probation_threshold = Some(cfg.probation_threshold);
     info.capabilities = vec![
         Capability::Sync,
         Capability::Sse,
@@ -22,6 +23,8 @@ pub async fn well_known(State(state): State<AppState>) -> Json<NodeInfo> {
         Capability::Peers,
         Capability::Agents,
         Capability::Follows,
+        Capability::Questions,
+        Capability::Similar,
     ];
     Json(info)
 }
@@ -0,0 +1,266 @@
+//! Question lifecycle handler — `GET /v1/questions` (spec §5.6).
+//!
+//! Lists `question` units together with their derived status (open,
+//! answered, or disputed). Classification is delegated to
+//! [`semanticweft::questions`]; this module only gathers the incoming
+//! references from storage.
+//!
+//! Like the subgraph endpoint, this view is public-only: both the questions
+//! and the units counted as answers or disputes must be `public`, so the
+//! reported status never leaks the existence of restricted units.
+
+use std::collections::{BTreeSet, HashSet};
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::Deserialize;
+use semanticweft::{
+    questions::question_lifecycle, Graph, QuestionStatus, SemanticUnit, UnitType, Visibility,
+};
+use semanticweft_node_api::{QuestionEntry, QuestionsResponse};
+
+use crate::{error::AppError, storage::UnitFilter};
+
+use super::AppState;
+
+/// Storage pages one request may scan looking for matches. A selective
+/// `status` or `type` filter otherwise walks every question on the node.
+const MAX_SCAN_PAGES: usize = 5;
+
+/// Query parameters for `GET /v1/questions`.
+#[derive(Debug, Deserialize, Default)]
+pub struct QuestionQueryParams {
+    /// Include only questions in this state (`open`, `answered`, `disputed`).
+    pub status: Option<String>,
+
+    /// Include only questions whose `author` exactly matches this DID.
+    pub author: Option<String>,
+
+    /// Include only questions that reference at least one unit of this type
+    /// (e.g. `?type=assertion` — questions raised about assertions).
+    #[serde(rename = "type")]
+    pub about_type: Option<String>,
+
+    /// Keyset pagination cursor (the `cursor` of the previous page).
+    pub after: Option<String>,
+
+    /// Page size (1–500, default 50).
+    pub limit: Option<u32>,
+}
+
+/// `GET /v1/questions` — list public questions with their lifecycle status.
+///
+/// Returns 400 if `status` or `type` is not a recognised value. Questions are
+/// scanned in ascending `id` order; filtering on status and type happens
+/// after classification, so the returned `cursor` is the last question
+/// examined rather than the last one returned. A request scans at most
+/// [`MAX_SCAN_PAGES`] storage pages; when it stops there, it returns what it
+/// found so far with `has_more` set.
+pub async fn list(
+    State(state): State<AppState>,
+    Query(params): Query<QuestionQueryParams>,
+) -> Result<Json<QuestionsResponse>, AppError> {
+    let status: Option<QuestionStatus> = params
+        .status
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(AppError::BadRequest)?;
+    let about_type: Option<UnitType> = params
+        .about_type
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(AppError::BadRequest)?;
+    let limit = params.limit.map(|l| l.clamp(1, 500)).unwrap_or(50) as usize;
+
+    let mut filter = UnitFilter {
+        unit_types: vec![UnitType::Question],
+        author: params.author,
+        since: None,
+        subject: None,
+        predicate: None,
+        after: params.after,
+        limit: limit as u32,
+        visibilities: vec![Visibility::Public],
+        network_for_authors: vec![],
+    };
+
+    let mut questions = Vec::new();
+    let mut cursor = None;
+    let mut has_more = false;
+
+    'scan: for scanned in 1..=MAX_SCAN_PAGES {
+        let (page, page_has_more) = state.storage.list_units(&filter).await?;
+        let about = match about_type {
+            Some(ref wanted) => Some(referenced_of_type(&state, &page, wanted).await?),
+            None => None,
+        };
+        for (i, question) in page.iter().enumerate() {
+            cursor = Some(question.id.clone());
+
+            if let Some(ref about) = about {
+                if !question.references.iter().flatten().any(|r| about.contains(&r.id)) {
+                    continue;
+                }
+            }
+
+            let entry = classify(&state, question.clone()).await?;
+            if status.is_none_or(|s| s == entry.status) {
+                questions.push(entry);
+                if questions.len() == limit {
+                    has_more = page_has_more || i + 1 < page.len();
+                    break 'scan;
+                }
+            }
+        }
+        if !page_has_more {
+            break;
+        }
+        if scanned == MAX_SCAN_PAGES {
+            has_more = true;
+            break;
+        }
+        filter.after = cursor.clone();
+    }
+
+    Ok(Json(QuestionsResponse {
+        questions,
+        cursor,
+        has_more,
+    }))
+}
+
+/// Derive the lifecycle entry for a question from its public incoming references.
+async fn classify(state: &AppState, question: SemanticUnit) -> Result<QuestionEntry, AppError> {
+    let id = question.id.clone();
+    let mut units = state.storage.get_referencing_units(&id).await?;
+    units.retain(is_public);
+    units.push(question);
+    let graph = Graph::from_units(units);
+    let lifecycle = question_lifecycle(&graph, &id).expect("question is in the graph");
+
+    Ok(QuestionEntry {
+        status: lifecycle.status,
+        unit: lifecycle.question.clone(),
+        answers: lifecycle.answers.iter().map(|u| u.id.clone()).collect(),
+        disputes: lifecycle.disputes.iter().map(|u| u.id.clone()).collect(),
+    })
+}
+
+/// IDs of the public units of type `wanted`, held here, that the questions
+/// in `page` reference — fetched in one storage call per page.
+async fn referenced_of_type(
+    state: &AppState,
+    page: &[SemanticUnit],
+    wanted: &UnitType,
+) -> Result<HashSet<String>, AppError> {
+    let ids: BTreeSet<&str> = page
+        .iter()
+        .flat_map(|q| q.references.iter().flatten())
+        .map(|r| r.id.as_str())
+        .collect();
+    let ids: Vec<String> = ids.into_iter().map(String::from).collect();
+    Ok(state
+        .storage
+        .get_units(&ids)
+        .await?
+        .into_iter()
+        .filter(|u| is_public(u) && u.unit_type == *wanted)
+        .map(|u| u.id)
+        .collect())
+}
+
+fn is_public(unit: &SemanticUnit) -> bool {
+    matches!(unit.visibility, None | Some(Visibility::Public))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use ed25519_dalek::SigningKey;
+    use rand::rngs::OsRng;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::config::NodeConfig;
+    use crate::router::build_router;
+    use crate::storage::{memory::MemoryStorage, Storage};
+
+    #[tokio::test]
+    async fn selective_filter_stops_after_max_scan_pages() {
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStorage::new());
+        let mut ids = Vec::new();
+        for i in 0..MAX_SCAN_PAGES + 2 {
+            let q = SemanticUnit::new(UnitType::Question, format!("q{i}"), "did:key:z6MkA");
+            storage.put_unit(&q).await.unwrap();
+            ids.push(q.id);
+        }
+        let signing_key = Arc::new(SigningKey::generate(&mut OsRng));
+        let app = build_router(storage, NodeConfig::from_env(), signing_key).0;
+
+        let resp = app
+            .oneshot(
+                Request::get("/v1/questions?status=answered&limit=1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let page: QuestionsResponse = serde_json::from_slice(&body).unwrap();
+        assert!(page.questions.is_empty());
+        assert!(page.has_more);
+        ids.sort();
+        assert_eq!(page.cursor.as_deref(), Some(ids[MAX_SCAN_PAGES - 1].as_str()));
+    }
+
+    #[tokio::test]
+    async fn type_filter_keeps_questions_about_public_units_of_that_type() {
+        use semanticweft::{Reference, RelType};
+
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStorage::new());
+        let author = "did:key:z6MkA";
+        let claim = SemanticUnit::new(UnitType::Assertion, "claim", author);
+        let inference = SemanticUnit::new(UnitType::Inference, "inference", author);
+        let mut hidden = SemanticUnit::new(UnitType::Assertion, "hidden", author);
+        hidden.visibility = Some(Visibility::Network);
+        let question = |about: &SemanticUnit| {
+            let mut q = SemanticUnit::new(UnitType::Question, "why?", author);
+            q.references = Some(vec![Reference {
+                id: about.id.clone(),
+                rel: RelType::Questions,
+            }]);
+            q
+        };
+        let wanted = question(&claim);
+        for u in [&claim, &inference, &hidden] {
+            storage.put_unit(u).await.unwrap();
+        }
+        for q in [&wanted, &question(&inference), &question(&hidden)] {
+            storage.put_unit(q).await.unwrap();
+        }
+        let signing_key = Arc::new(SigningKey::generate(&mut OsRng));
+        let app = build_router(storage, NodeConfig::from_env(), signing_key).0;
+
+        let resp = app
+            .oneshot(
+                Request::get("/v1/questions?type=assertion")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let page: QuestionsResponse = serde_json::from_slice(&body).unwrap();
+        let ids: Vec<&str> = page.questions.iter().map(|q| q.unit.id.as_str()).collect();
+        assert_eq!(ids, [wanted.id.as_str()]);
+    }
+}
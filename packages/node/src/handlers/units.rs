@@ -1,4 +1,4 @@
-//! Unit handlers — submit, retrieve, list, subgraph, and sync (spec §5).
+//! Unit handlers — submit, retrieve, list, subgraph, similar, and sync (spec §5).
 //!
 //! # Visibility model
 //!
@@ -25,8 +25,8 @@ use axum::{
 };
 use serde::Deserialize;
 use semanticweft::{validate_unit, Graph, Reference, RelType, SemanticUnit, UnitType, Visibility};
-use semanticweft_agent_core::AgentAddress;
-use semanticweft_node_api::{ListResponse, SubgraphResponse};
+use semanticweft_agent_core::{resolve_jrd, AgentAddress};
+use semanticweft_node_api::{ListResponse, SimilarResponse, SimilarUnit, SubgraphResponse};
 use tokio_stream::{wrappers::BroadcastStream, StreamExt};
 
 use crate::{
@@ -102,6 +102,12 @@ pub struct UnitQueryParams {
     /// ISO 8601 lower bound on `created_at`.
     pub since: Option<String>,
 
+    /// Claim-triple subject filter (`x-org.semanticweft.triples`).
+    pub subject: Option<String>,
+
+    /// Claim-triple predicate filter (`x-org.semanticweft.triples`).
+    pub predicate: Option<String>,
+
     /// Keyset pagination cursor (UUIDv7 `id` of the last seen unit).
     pub after: Option<String>,
 
@@ -116,6 +122,17 @@ pub struct SubgraphQueryParams {
     pub depth: Option<u32>,
 }
 
+/// Query parameters for `GET /v1/units/{id}/similar`.
+#[derive(Debug, Deserialize, Default)]
+pub struct SimilarQueryParams {
+    /// Minimum similarity score in `[0, 1]`. Defaults to
+    /// [`semanticweft::similarity::DEFAULT_THRESHOLD`].
+    pub threshold: Option<f64>,
+
+    /// Maximum number of results (1–100, default 10).
+    pub limit: Option<u32>,
+}
+
 // ---------------------------------------------------------------------------
 // POST /v1/units
 // ---------------------------------------------------------------------------
@@ -133,8 +150,32 @@ pub async fn submit(
     Json(unit): Json<SemanticUnit>,
 ) -> Result<Response, AppError> {
     validate_unit(&unit).map_err(|e| AppError::UnprocessableEntity(e.to_string()))?;
-    if unit.proof.is_some() {
+    if let Some(proof) = &unit.proof {
         semanticweft::verify_proof(&unit).map_err(|e| AppError::BadRequest(e.to_string()))?;
+        // A delegated proof must speak for the author, and its token must
+        // still be valid — `verify_proof` only checks it at `created_at`,
+        // which the delegate chose (ADR-0014).
+        if let Some(delegation) = &proof.delegation {
+            if delegation.issuer != unit.author {
+                return Err(AppError::BadRequest(format!(
+                    "proof is delegated by {}, not the author {}",
+                    delegation.issuer, unit.author
+                )));
+            }
+            delegation
+                .verify(std::time::SystemTime::now())
+                .map_err(|e| AppError::BadRequest(e.to_string()))?;
+        }
+    }
+
+    // A delegated caller may only submit units its token covers.
+    if let Some(delegation) = &auth.1 {
+        if !delegation.scope.allows_unit(&unit) {
+            return Err(AppError::Forbidden(format!(
+                "delegation does not cover {} units with this visibility",
+                unit.unit_type
+            )));
+        }
     }
 
     // Non-public units require authentication as the unit's author.
@@ -150,6 +191,18 @@ pub async fn submit(
         }
     }
 
+    // A rotated-out key no longer speaks for its agent (spec §8.7). Units
+    // stored before the rotation may still be re-submitted.
+    let retired = state.storage.get_agent(&unit.author).await?.and_then(|a| a.successor);
+    if let Some(successor) = retired {
+        if state.storage.get_unit(&unit.id).await?.as_ref() != Some(&unit) {
+            return Err(AppError::Forbidden(format!(
+                "{} rotated its key to {successor}; author new units as the successor",
+                unit.author
+            )));
+        }
+    }
+
     match state.storage.put_unit(&unit).await {
         Ok(()) => {}
         Err(StorageError::Conflict(_)) => {
@@ -275,7 +328,9 @@ async fn local_fanout(
 ///   `did:key:z6Mk…@hostname`) identify recipients on remote nodes. For each
 ///   unique hostname the node's `/.well-known/semanticweft` document is
 ///   fetched once to discover `api_base`; results are cached so multiple
-///   recipients on the same node only trigger one discovery request.
+///   recipients on the same node only trigger one discovery request. Each
+///   recipient's inbox is then resolved over WebFinger, falling back to the
+///   conventional path under `api_base` if the node does not answer.
 ///
 /// **Delivery failure notifications**: when a push attempt fails (network
 /// error, 4xx, 5xx), this node generates a `constraint` unit and delivers it
@@ -445,12 +500,50 @@ async fn remote_fanout(
             continue; // discovery failed; already notified above
         };
         for addr in addrs {
-            let inbox_url = addr.inbox_url(api_base);
+            let inbox_url = resolve_inbox(&client, addr, api_base).await;
             push_to_inbox(&client, &unit, &inbox_url, &signing_key, &node_did, &storage).await;
         }
     }
 }
 
+/// The inbox a remote agent's node advertises over WebFinger, or the
+/// conventional `{api_base}/agents/{did}/inbox` if it does not answer or
+/// advertises an inbox this node should not push to (see [`trusted_inbox`]).
+async fn resolve_inbox(client: &reqwest::Client, addr: &AgentAddress, api_base: &str) -> String {
+    let resolved = match client.get(addr.webfinger_url()).send().await {
+        Ok(resp) if resp.status().is_success() => resp
+            .text()
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|body| resolve_jrd(addr, &body).map_err(|e| e.to_string()))
+            .and_then(|agent| trusted_inbox(addr, &agent.inbox_url)),
+        Ok(resp) => Err(format!("HTTP {}", resp.status().as_u16())),
+        Err(e) => Err(e.to_string()),
+    };
+    match resolved {
+        Ok(inbox_url) => inbox_url,
+        Err(e) => {
+            tracing::debug!("remote_fanout: WebFinger for {addr} failed ({e}); using {api_base}");
+            addr.inbox_url(api_base)
+        }
+    }
+}
+
+/// Accept an advertised inbox only over https on the address's own host, so
+/// a JRD cannot point signed deliveries at a third party.
+fn trusted_inbox(addr: &AgentAddress, inbox_url: &str) -> Result<String, String> {
+    let url = reqwest::Url::parse(inbox_url).map_err(|e| format!("bad inbox {inbox_url:?}: {e}"))?;
+    let authority = match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{host}:{port}"),
+        (Some(host), None) => host.to_string(),
+        (None, _) => String::new(),
+    };
+    if url.scheme() != "https" || !authority.eq_ignore_ascii_case(&addr.hostname) {
+        return Err(format!("inbox {inbox_url} is not https on {}", addr.hostname));
+    }
+    Ok(inbox_url.to_string())
+}
+
 /// POST a unit to a remote agent inbox with an HTTP Signature.
 ///
 /// Retries up to 3 times with exponential backoff (2 s, 4 s) on 5xx or
@@ -618,38 +711,38 @@ pub async fn get_by_id(
         .await?
         .ok_or_else(|| AppError::NotFound(format!("unit {id} not found")))?;
 
-    let vis = unit.visibility.as_ref().unwrap_or(&Visibility::Public);
-    match vis {
-        Visibility::Public => {} // always visible
-        Visibility::Network => {
-            // Visible to authenticated followers of the author.
-            let caller_did = auth.0.as_deref().ok_or_else(|| {
-                AppError::NotFound(format!("unit {id} not found"))
-            })?;
-            let is_follower = state
-                .storage
-                .is_following(caller_did, &unit.author)
-                .await
-                .unwrap_or(false);
-            if !is_follower {
-                return Err(AppError::NotFound(format!("unit {id} not found")));
-            }
-        }
-        Visibility::Limited => {
-            // Visible only if caller DID is in unit.audience.
-            let caller_did = auth.0.as_deref().ok_or_else(|| {
-                AppError::NotFound(format!("unit {id} not found"))
-            })?;
-            let audience = unit.audience.as_deref().unwrap_or(&[]);
-            if !audience.iter().any(|a| a == caller_did) {
-                return Err(AppError::NotFound(format!("unit {id} not found")));
-            }
-        }
+    if !visible_to(&state, &unit, auth.0.as_deref()).await {
+        return Err(AppError::NotFound(format!("unit {id} not found")));
     }
 
     Ok(Json(unit))
 }
 
+/// `true` if `caller` (or an unauthenticated caller, when `None`) may see
+/// `unit` under the visibility model described in the module docs.
+async fn visible_to(state: &AppState, unit: &SemanticUnit, caller: Option<&str>) -> bool {
+    match unit.visibility.as_ref().unwrap_or(&Visibility::Public) {
+        Visibility::Public => true,
+        // Visible to authenticated followers of the author.
+        Visibility::Network => match caller {
+            Some(did) => state
+                .storage
+                .is_following(did, &unit.author)
+                .await
+                .unwrap_or(false),
+            None => false,
+        },
+        // Visible only if the caller DID is in unit.audience.
+        Visibility::Limited => caller.is_some_and(|did| {
+            unit.audience
+                .as_deref()
+                .unwrap_or(&[])
+                .iter()
+                .any(|a| a == did)
+        }),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // GET /v1/units
 // ---------------------------------------------------------------------------
@@ -783,13 +876,17 @@ pub async fn sync(
 /// `GET /v1/units/{id}/subgraph` — retrieve the connected subgraph around a unit.
 ///
 /// Traverses outgoing (referenced) and incoming (referencing) edges up to
-/// `depth` hops in each direction. Unreachable or non-public units are
-/// silently omitted.
+/// `depth` hops in each direction. Visibility is enforced per auth state, as
+/// for `GET /v1/units/{id}`: units the caller may not see are omitted and not
+/// traversed through, so the response never reveals their neighbours.
 pub async fn subgraph(
     State(state): State<AppState>,
     Path(id): Path<String>,
     Query(params): Query<SubgraphQueryParams>,
+    auth: OptionalAuth,
 ) -> Result<Json<SubgraphResponse>, AppError> {
+    let caller = auth.0.as_deref();
+
     // Verify the root unit exists and is visible.
     let root = state
         .storage
@@ -797,8 +894,7 @@ pub async fn subgraph(
         .await?
         .ok_or_else(|| AppError::NotFound(format!("unit {id} not found")))?;
 
-    let vis = root.visibility.as_ref().unwrap_or(&Visibility::Public);
-    if *vis != Visibility::Public {
+    if !visible_to(&state, &root, caller).await {
         return Err(AppError::NotFound(format!("unit {id} not found")));
     }
 
@@ -827,8 +923,10 @@ pub async fn subgraph(
             for ref_id in outgoing_ids {
                 if graph.get(&ref_id).is_none() {
                     if let Ok(Some(u)) = state.storage.get_unit(&ref_id).await {
-                        next_frontier.push(u.id.clone());
-                        graph.add(u);
+                        if visible_to(&state, &u, caller).await {
+                            next_frontier.push(u.id.clone());
+                            graph.add(u);
+                        }
                     }
                 }
             }
@@ -836,7 +934,7 @@ pub async fn subgraph(
             // Incoming edges: units that reference node_id.
             let incoming = state.storage.get_referencing_units(node_id).await?;
             for u in incoming {
-                if graph.get(&u.id).is_none() {
+                if graph.get(&u.id).is_none() && visible_to(&state, &u, caller).await {
                     next_frontier.push(u.id.clone());
                     graph.add(u);
                 }
@@ -853,6 +951,75 @@ pub async fn subgraph(
     Ok(Json(SubgraphResponse { units }))
 }
 
+// ---------------------------------------------------------------------------
+// GET /v1/units/:id/similar
+// ---------------------------------------------------------------------------
+
+/// `GET /v1/units/{id}/similar` — find near-duplicates of a unit's content.
+///
+/// Candidates come from the storage-backed LSH index (maintained on every
+/// `put_unit`) and are ranked by estimated similarity. Like the subgraph
+/// endpoint this is public-only: the root must be public, and non-public
+/// matches are silently omitted.
+pub async fn similar(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<SimilarQueryParams>,
+) -> Result<Json<SimilarResponse>, AppError> {
+    uuid::Uuid::parse_str(&id)
+        .map_err(|_| AppError::BadRequest(format!("{id:?} is not a valid UUID")))?;
+
+    let threshold = params
+        .threshold
+        .unwrap_or(semanticweft::similarity::DEFAULT_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(AppError::BadRequest(format!(
+            "threshold must be between 0 and 1, got {threshold}"
+        )));
+    }
+    let limit = params.limit.map(|l| l.clamp(1, 100)).unwrap_or(10) as usize;
+
+    let root = state
+        .storage
+        .get_unit(&id)
+        .await?
+        .filter(|u| matches!(u.visibility, None | Some(Visibility::Public)))
+        .ok_or_else(|| AppError::NotFound(format!("unit {id} not found")))?;
+
+    let signature = match state.storage.get_unit_signature(&id).await? {
+        Some(sig) => sig,
+        None => semanticweft::MinHash::of(&root.content),
+    };
+
+    let mut scored: Vec<(String, f64)> = state
+        .storage
+        .similarity_candidates(&signature)
+        .await?
+        .into_iter()
+        .filter(|(candidate, _)| *candidate != id)
+        .map(|(candidate, sig)| {
+            let score = signature.similarity(&sig);
+            (candidate, score)
+        })
+        .filter(|(_, score)| *score >= threshold)
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut similar = Vec::new();
+    for (candidate, score) in scored {
+        if similar.len() == limit {
+            break;
+        }
+        if let Some(unit) = state.storage.get_unit(&candidate).await? {
+            if matches!(unit.visibility, None | Some(Visibility::Public)) {
+                similar.push(SimilarUnit { unit, score });
+            }
+        }
+    }
+
+    Ok(Json(SimilarResponse { similar }))
+}
+
 // ---------------------------------------------------------------------------
 // Shared helpers
 // ---------------------------------------------------------------------------
@@ -873,6 +1040,8 @@ fn build_filter(params: UnitQueryParams, visibilities: Vec<Visibility>) -> UnitF
         unit_types,
         author: params.author,
         since: params.since,
+        subject: params.subject,
+        predicate: params.predicate,
         after: params.after,
         limit,
         visibilities,
@@ -935,6 +1104,63 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::CREATED);
     }
 
+    #[test]
+    fn advertised_inbox_must_be_https_on_the_address_host() {
+        let addr = AgentAddress::new("did:key:z6MkFoo", "sweft.example.com");
+        let ok = "https://sweft.example.com/v1/agents/did%3Akey%3Az6MkFoo/inbox";
+        assert_eq!(trusted_inbox(&addr, ok).as_deref(), Ok(ok));
+        assert!(trusted_inbox(&addr, "https://SWEFT.example.com/inbox").is_ok());
+        assert!(trusted_inbox(&addr, "http://sweft.example.com/inbox").is_err());
+        assert!(trusted_inbox(&addr, "https://internal.example/inbox").is_err());
+        assert!(trusted_inbox(&addr, "https://sweft.example.com.evil.example/inbox").is_err());
+        assert!(trusted_inbox(&addr, "https://sweft.example.com:8443/inbox").is_err());
+        assert!(trusted_inbox(&addr, "not a url").is_err());
+    }
+
+    #[tokio::test]
+    async fn submit_from_rotated_author_is_rejected() {
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStorage::new());
+        let old = "did:key:z6MkRetired";
+        storage
+            .put_agent(&semanticweft_node_api::AgentProfile {
+                did: old.into(),
+                inbox_url: format!("http://localhost/v1/agents/{old}/inbox"),
+                display_name: None,
+                public_key: None,
+                status: semanticweft_node_api::AgentStatus::Full,
+                contribution_count: 0,
+                reputation: 0.5,
+                predecessor: None,
+                successor: Some("did:key:z6MkSuccessor".into()),
+            })
+            .await
+            .unwrap();
+        let earlier = SemanticUnit::new(UnitType::Assertion, "Before the rotation.", old);
+        storage.put_unit(&earlier).await.unwrap();
+        let app = build_router(
+            Arc::clone(&storage),
+            NodeConfig::from_env(),
+            Arc::new(SigningKey::generate(&mut OsRng)),
+        )
+        .0;
+        let post = |unit: &SemanticUnit| {
+            Request::builder()
+                .method("POST")
+                .uri("/v1/units")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(unit).unwrap()))
+                .unwrap()
+        };
+
+        let later = SemanticUnit::new(UnitType::Assertion, "After the rotation.", old);
+        let resp = app.clone().oneshot(post(&later)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        assert!(storage.get_unit(&later.id).await.unwrap().is_none());
+
+        let resp = app.oneshot(post(&earlier)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn submit_unit_with_valid_proof_returns_201() {
         let app = build_app();
@@ -971,6 +1197,46 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[tokio::test]
+    async fn submit_unit_with_delegated_proof_for_another_author_returns_400() {
+        use std::time::{Duration, SystemTime};
+        let app = build_app();
+        let (root_key, root) = make_signing_key_and_did();
+        let (worker_key, worker) = make_signing_key_and_did();
+        let now = SystemTime::now();
+        let token = semanticweft::Delegation::new(
+            &root,
+            &worker,
+            Default::default(),
+            now,
+            now + Duration::from_secs(600),
+        )
+        .sign(&root_key);
+
+        // `make_unit` is authored by someone other than the issuer.
+        let mut unit = make_unit();
+        semanticweft::sign_unit_delegated(&mut unit, &worker_key, token.clone()).unwrap();
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/units")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&unit).unwrap()))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let mut unit = SemanticUnit::new(UnitType::Assertion, "on behalf of root", &root);
+        semanticweft::sign_unit_delegated(&mut unit, &worker_key, token).unwrap();
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/units")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&unit).unwrap()))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+
     #[tokio::test]
     async fn sync_with_sse_accept_returns_event_stream() {
         use std::time::Duration;
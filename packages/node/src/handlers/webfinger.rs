@@ -1,7 +1,8 @@
 //! WebFinger handler — `GET /.well-known/webfinger` (RFC 7033).
 //!
 //! Resolves an agent address of the form `did:key:z6Mk…@hostname` to a JSON
-//! Resource Descriptor (JRD) linking to the agent's profile on this node.
+//! Resource Descriptor (JRD) linking to the agent's profile and inbox on this
+//! node.
 //!
 //! # Request
 //!
@@ -21,6 +22,11 @@
 //!       "rel": "self",
 //!       "type": "application/json",
 //!       "href": "https://example.com/v1/agents/did:key:z6Mk…"
+//!     },
+//!     {
+//!       "rel": "http://www.w3.org/ns/ldp#inbox",
+//!       "type": "application/json",
+//!       "href": "https://example.com/v1/agents/did:key:z6Mk…/inbox"
 //!     }
 //!   ]
 //! }
@@ -35,7 +41,11 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
+use semanticweft_node_api::{
+    webfinger::{REL_INBOX, REL_PROFILE},
+    Jrd, JrdLink,
+};
 
 use crate::error::AppError;
 use super::AppState;
@@ -49,32 +59,14 @@ pub struct WebFingerQuery {
     pub resource: Option<String>,
 }
 
-// ---------------------------------------------------------------------------
-// JRD types
-// ---------------------------------------------------------------------------
-
-/// A JSON Resource Descriptor (JRD) per RFC 7033 §4.4.
-#[derive(Serialize)]
-pub struct Jrd {
-    pub subject: String,
-    pub links: Vec<JrdLink>,
-}
-
-#[derive(Serialize)]
-pub struct JrdLink {
-    pub rel: String,
-    #[serde(rename = "type")]
-    pub link_type: String,
-    pub href: String,
-}
-
 // ---------------------------------------------------------------------------
 // Handler
 // ---------------------------------------------------------------------------
 
 /// `GET /.well-known/webfinger?resource=acct:{did}@{host}`
 ///
-/// Resolves a SemanticWeft agent address to its profile URL on this node.
+/// Resolves a SemanticWeft agent address to its profile and inbox URLs on
+/// this node.
 pub async fn webfinger(
     State(state): State<AppState>,
     Query(params): Query<WebFingerQuery>,
@@ -103,26 +95,42 @@ pub async fn webfinger(
     }
 
     // Look up the agent in storage — 404 if not registered here.
-    let _profile = state
+    let profile = state
         .storage
         .get_agent(did)
         .await
         .map_err(AppError::from)?
         .ok_or_else(|| AppError::NotFound(format!("agent {did} not found on this node")))?;
 
-    // Build the profile URL: api_base already ends with "/v1", so append the path.
+    // Build the profile URL. `api_base` is configured as the node's public
+    // URL, with or without the `/v1` suffix.
     let encoded_did = urlencoding::encode(did);
-    let profile_href = format!("{}/agents/{encoded_did}", state.config.api_base);
+    let origin = state
+        .config
+        .api_base
+        .trim_end_matches('/')
+        .trim_end_matches("/v1");
+    let profile_href = format!("{origin}/v1/agents/{encoded_did}");
 
     let subject = format!("acct:{address}");
 
+    // The inbox is the one the agent registered, which need not follow this
+    // node's path layout.
     let jrd = Jrd {
         subject,
-        links: vec![JrdLink {
-            rel: "self".into(),
-            link_type: "application/json".into(),
-            href: profile_href,
-        }],
+        aliases: vec![],
+        links: vec![
+            JrdLink {
+                rel: REL_PROFILE.into(),
+                media_type: Some("application/json".into()),
+                href: Some(profile_href),
+            },
+            JrdLink {
+                rel: REL_INBOX.into(),
+                media_type: Some("application/json".into()),
+                href: Some(profile.inbox_url),
+            },
+        ],
     };
 
     // RFC 7033 §10.2 requires Content-Type: application/jrd+json.
@@ -193,6 +201,8 @@ mod tests {
                 status: semanticweft_node_api::AgentStatus::Full,
                 contribution_count: 0,
                 reputation: 0.5,
+                predecessor: None,
+                successor: None,
             })
             .await
             .unwrap();
@@ -208,6 +218,13 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::OK);
         let ct = resp.headers().get("content-type").unwrap().to_str().unwrap();
         assert!(ct.contains("application/jrd+json"));
+
+        let body = http_body_util::BodyExt::collect(resp.into_body()).await.unwrap().to_bytes();
+        let jrd: semanticweft_node_api::Jrd = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            jrd.href(semanticweft_node_api::webfinger::REL_INBOX),
+            Some(format!("http://localhost/v1/agents/{did}/inbox").as_str())
+        );
     }
 
     #[tokio::test]
@@ -253,6 +270,8 @@ mod tests {
                 status: semanticweft_node_api::AgentStatus::Full,
                 contribution_count: 0,
                 reputation: 0.5,
+                predecessor: None,
+                successor: None,
             })
             .await
             .unwrap();
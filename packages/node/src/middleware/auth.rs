@@ -1,37 +1,94 @@
 //! HTTP Signature authentication extractors (draft-cavage-http-signatures-12).
 //!
-//! Provides three extractors:
+//! Provides five extractors:
 //! - [`RequireAuth`]: requires a valid HTTP Signature from a registered agent; returns 401 if absent or invalid.
+//! - [`RequireOwnKey`]: like [`RequireAuth`], but refuses delegated requests with 403.
 //! - [`OptionalAuth`]: accepts requests with or without a valid HTTP Signature.
 //! - [`NodeAuth`]: requires a valid HTTP Signature from a delivering node, verified via did:key.
+//! - [`KeyAuth`]: requires a valid HTTP Signature from any `did:key` holder, registered or not.
 //!
 //! Also exposes [`build_outbound_signature`] for constructing HTTP Signature
-//! headers on outbound S2S requests.
+//! headers on outbound S2S requests, and the [`verify_digest`] middleware.
+//! Parsing the `Signature` header and rebuilding the signing string are
+//! [`SignatureParams`]'s job, shared with the agent-side signer.
+//!
+//! # Body digests
+//!
+//! The extractors only see request headers, so a signature that covers a
+//! `Digest` header says nothing about the body by itself. [`verify_digest`]
+//! closes the gap: it buffers any request carrying a `Digest` header and
+//! rejects it with 401 unless the header's `SHA-256` value matches the body.
+//!
+//! # Delegated requests
+//!
+//! [`RequireAuth`] and [`OptionalAuth`] also accept requests signed by a
+//! delegate key (ADR-0014). The request carries the issuer's
+//! [`Delegation`] token as compact JSON in a `Delegation` header, which the
+//! signature must cover; `keyId` is the delegate's `did:key`. The caller is
+//! then the token's issuer, provided it is registered here, and the token is
+//! handed to the handler so it can enforce the unit scope. Endpoints that
+//! manage the issuer itself — registration, deregistration, admission and
+//! follows — take [`RequireOwnKey`] or [`KeyAuth`] instead, which refuse a
+//! `Delegation` header outright so a delegate can never take over its issuer.
 
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use axum::{
-    extract::{FromRef, FromRequestParts},
+    body::Body,
+    extract::{FromRef, FromRequestParts, Request},
     http::{request::Parts, StatusCode},
+    middleware::Next,
     response::{IntoResponse, Response},
     Json,
 };
-use ed25519_dalek::{Signer, Verifier};
-use semanticweft_node_api::ErrorResponse;
+use semanticweft::Delegation;
+use semanticweft_agent_core::{
+    body_digest, AgentIdentity, SignableRequest, SignatureError, SignatureParams,
+};
+use semanticweft_node_api::{error::codes, AgentProfile, ErrorResponse};
 
-use crate::{handlers::AppState, storage::Storage};
+use crate::{error::AppError, handlers::AppState, storage::Storage};
 
 // ---------------------------------------------------------------------------
 // Auth errors
 // ---------------------------------------------------------------------------
 
 /// An authentication failure that maps to HTTP 401.
-#[derive(Debug)]
-pub struct AuthError(pub String);
+///
+/// `code` names the cause when a client can act on it (a stripped header, a
+/// skewed clock, an unregistered key, a bad signature); everything else is
+/// plain `unauthorized`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AuthError {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl AuthError {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<String> for AuthError {
+    fn from(message: String) -> Self {
+        Self::new(codes::UNAUTHORIZED, message)
+    }
+}
+
+impl From<&str> for AuthError {
+    fn from(message: &str) -> Self {
+        Self::new(codes::UNAUTHORIZED, message)
+    }
+}
 
 impl IntoResponse for AuthError {
     fn into_response(self) -> Response {
-        let body = ErrorResponse::new("unauthorized", self.0.clone());
+        let body = ErrorResponse::new(self.code, self.message);
         (StatusCode::UNAUTHORIZED, Json(body)).into_response()
     }
 }
@@ -44,7 +101,8 @@ impl IntoResponse for AuthError {
 ///
 /// Returns 401 if the `Signature` header is absent or the signature is invalid.
 pub struct RequireAuth {
-    /// The DID extracted from `keyId` in the `Signature` header.
+    /// The DID extracted from `keyId` in the `Signature` header, or the
+    /// issuer of the `Delegation` token for a delegated request.
     pub did: String,
 }
 
@@ -61,22 +119,63 @@ where
     ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
         let app_state = AppState::from_ref(state);
         async move {
-            let did = verify_http_signature(parts, &app_state.storage)
-                .await
-                .map_err(AuthError)?;
+            let (did, _) = verify_http_signature(parts, &app_state.storage).await?;
             Ok(RequireAuth { did })
         }
     }
 }
 
+// ---------------------------------------------------------------------------
+// RequireOwnKey extractor
+// ---------------------------------------------------------------------------
+
+/// Axum extractor that requires a valid HTTP Signature made with the
+/// registered agent's own key.
+///
+/// Returns 401 like [`RequireAuth`], and 403 for a delegated request.
+pub struct RequireOwnKey {
+    /// The DID extracted from `keyId` in the `Signature` header.
+    pub did: String,
+}
+
+impl<S> FromRequestParts<S> for RequireOwnKey
+where
+    S: Send + Sync,
+    AppState: FromRef<S>,
+{
+    type Rejection = Response;
+
+    fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
+        let app_state = AppState::from_ref(state);
+        async move {
+            let (did, delegation) = verify_http_signature(parts, &app_state.storage)
+                .await
+                .map_err(IntoResponse::into_response)?;
+            if delegation.is_some() {
+                return Err(delegation_refused());
+            }
+            Ok(RequireOwnKey { did })
+        }
+    }
+}
+
+/// The 403 for a delegate calling an endpoint reserved for the issuer's key.
+fn delegation_refused() -> Response {
+    AppError::Forbidden("delegated keys cannot call this endpoint".into()).into_response()
+}
+
 // ---------------------------------------------------------------------------
 // OptionalAuth extractor
 // ---------------------------------------------------------------------------
 
 /// Axum extractor that accepts requests with or without a valid HTTP Signature.
 ///
-/// Yields `Some(did)` if a valid signature is present, `None` otherwise.
-pub struct OptionalAuth(pub Option<String>);
+/// Yields `Some(did)` if a valid signature is present, `None` otherwise. The
+/// second field carries the token of a delegated request.
+pub struct OptionalAuth(pub Option<String>, pub Option<Delegation>);
 
 impl<S> FromRequestParts<S> for OptionalAuth
 where
@@ -91,8 +190,10 @@ where
     ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
         let app_state = AppState::from_ref(state);
         async move {
-            let result = verify_http_signature(parts, &app_state.storage).await;
-            Ok(OptionalAuth(result.ok()))
+            match verify_http_signature(parts, &app_state.storage).await {
+                Ok((did, delegation)) => Ok(OptionalAuth(Some(did), delegation)),
+                Err(_) => Ok(OptionalAuth(None, None)),
+            }
         }
     }
 }
@@ -116,38 +217,64 @@ where
 {
     type Rejection = AuthError;
 
-    fn from_request_parts(
-        parts: &mut Parts,
-        _state: &S,
-    ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
-        async move {
-            verify_node_signature(parts).map_err(AuthError)?;
-            Ok(NodeAuth)
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        verify_node_signature(parts)?;
+        Ok(NodeAuth)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// KeyAuth extractor
+// ---------------------------------------------------------------------------
+
+/// Axum extractor that requires a valid HTTP Signature whose key is embedded
+/// in the caller's `did:key`.
+///
+/// Verification is the same as [`NodeAuth`], but the caller's DID is kept.
+/// Used by self-service admission (ADR-0013), where the applicant is not yet
+/// registered and [`RequireAuth`] could never succeed. A request carrying a
+/// `Delegation` header is refused with 403.
+pub struct KeyAuth {
+    /// The DID extracted from `keyId` in the `Signature` header.
+    pub did: String,
+}
+
+impl<S> FromRequestParts<S> for KeyAuth
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let did = verify_node_signature(parts).map_err(IntoResponse::into_response)?;
+        if parts.headers.contains_key("delegation") {
+            return Err(delegation_refused());
         }
+        Ok(KeyAuth { did })
     }
 }
 
 /// Verify a node-to-node HTTP Signature by decoding the key from the `did:key`.
 ///
-/// Returns the node DID on success, or an error string describing the failure.
-pub(crate) fn verify_node_signature(parts: &Parts) -> Result<String, String> {
+/// Returns the node DID on success, or the reason for the failure.
+pub(crate) fn verify_node_signature(parts: &Parts) -> Result<String, AuthError> {
     // --- 1. Extract Signature header ------------------------------------------
     let sig_header = parts
         .headers
         .get("signature")
         .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| "missing Signature header".to_string())?;
+        .ok_or_else(|| AuthError::new(codes::SIGNATURE_MISSING, "missing Signature header"))?;
 
     // --- 2. Parse the Signature header ----------------------------------------
-    let parsed = parse_signature_header(sig_header)
-        .map_err(|e| format!("invalid Signature header: {e}"))?;
+    let parsed = SignatureParams::parse(sig_header).map_err(|e| e.to_string())?;
 
     // --- 3. Validate the key_id is a did:key ----------------------------------
     if !parsed.key_id.starts_with("did:key:") {
         return Err(format!(
             "keyId must be a did:key DID, got: {}",
             parsed.key_id
-        ));
+        )
+        .into());
     }
 
     // --- 4. Validate Date header (replay prevention: ±5 minutes) -------------
@@ -155,9 +282,11 @@ pub(crate) fn verify_node_signature(parts: &Parts) -> Result<String, String> {
         .headers
         .get("date")
         .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| "missing Date header".to_string())?;
+        .ok_or_else(|| AuthError::new(codes::SIGNATURE_MISSING, "missing Date header"))?;
 
-    validate_date(date_str).map_err(|e| format!("Date header invalid: {e}"))?;
+    validate_date(date_str).map_err(|e| {
+        AuthError::new(codes::DATE_OUT_OF_RANGE, format!("Date header invalid: {e}"))
+    })?;
 
     // --- 5. Decode verifying key directly from the did:key --------------------
     let multibase = parsed
@@ -168,32 +297,48 @@ pub(crate) fn verify_node_signature(parts: &Parts) -> Result<String, String> {
     let verifying_key = decode_multibase_key(multibase)
         .map_err(|e| format!("invalid did:key public key: {e}"))?;
 
-    // --- 6. Reconstruct signing string ----------------------------------------
-    let signing_string =
-        build_signing_string(parts, &parsed.headers, &parsed.algorithm)
-            .map_err(|e| format!("signing string error: {e}"))?;
-
-    // --- 7. Decode and verify signature ----------------------------------------
-    let sig_bytes_vec = bs58::decode(
-        parsed
-            .signature
-            .strip_prefix('z')
-            .ok_or("signature value must start with 'z'")?
-    )
-    .into_vec()
-    .map_err(|e| format!("bs58 decode failed: {e}"))?;
-
-    let sig_array: [u8; 64] = sig_bytes_vec
-        .try_into()
-        .map_err(|_| "signature must be 64 bytes".to_string())?;
+    // --- 6. Verify the signature over the rebuilt signing string -------------
+    verify_request(&parsed, &verifying_key, parts)?;
 
-    let signature = ed25519_dalek::Signature::from_bytes(&sig_array);
+    Ok(parsed.key_id)
+}
 
-    verifying_key
-        .verify(signing_string.as_bytes(), &signature)
-        .map_err(|_| "node signature verification failed".to_string())?;
+// ---------------------------------------------------------------------------
+// Digest verification
+// ---------------------------------------------------------------------------
 
-    Ok(parsed.key_id)
+/// Largest body buffered for digest verification; axum's default body limit.
+const MAX_DIGEST_BODY: usize = 2 * 1024 * 1024;
+
+/// Middleware that checks a `Digest` header against the request body.
+///
+/// Requests without the header pass through untouched. Otherwise the header
+/// must carry a `SHA-256=` value equal to the digest of the body.
+pub async fn verify_digest(req: Request, next: Next) -> Response {
+    let Some(header) = req.headers().get("digest") else {
+        return next.run(req).await;
+    };
+    let Some(claimed) = header.to_str().ok().and_then(|v| {
+        v.split(',')
+            .map(str::trim)
+            .find(|d| d.get(..8).is_some_and(|alg| alg.eq_ignore_ascii_case("SHA-256=")))
+            .map(|d| d[8..].to_string())
+    }) else {
+        return AuthError::from("Digest header has no SHA-256 value").into_response();
+    };
+
+    let (parts, body) = req.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_DIGEST_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return AppError::BadRequest(format!("cannot read request body: {e}")).into_response()
+        }
+    };
+    if body_digest(&bytes)[8..] != claimed {
+        return AuthError::new(codes::SIGNATURE_INVALID, "Digest does not match the request body")
+            .into_response();
+    }
+    next.run(Request::from_parts(parts, Body::from(bytes))).await
 }
 
 // ---------------------------------------------------------------------------
@@ -210,6 +355,9 @@ pub(crate) fn verify_node_signature(parts: &Parts) -> Result<String, String> {
 /// ```
 ///
 /// The signature is Ed25519, encoded as `z` + base58btc(64 raw bytes).
+/// Construction is shared with agents through
+/// [`semanticweft_agent_core::SignableRequest`]; `node_did` is sent as the
+/// `keyId`.
 ///
 /// Returns `(date_header_value, signature_header_value)`.
 pub fn build_outbound_signature(
@@ -219,203 +367,153 @@ pub fn build_outbound_signature(
     path: &str,
     host: &str,
 ) -> (String, String) {
-    let date = httpdate::fmt_http_date(std::time::SystemTime::now());
-    let signing_string = format!(
-        "(request-target): {method} {path}\nhost: {host}\ndate: {date}"
-    );
-    let sig_bytes = signing_key.sign(signing_string.as_bytes()).to_bytes();
-    let sig_encoded = format!("z{}", bs58::encode(sig_bytes).into_string());
-    let signature_header = format!(
-        r#"keyId="{node_did}",algorithm="ed25519",headers="(request-target) host date",signature="{sig_encoded}""#
-    );
-    (date, signature_header)
+    let identity = AgentIdentity::from_seed(&signing_key.to_bytes());
+    let headers =
+        SignableRequest::new(method, host, path).sign_as(&identity, node_did, SystemTime::now());
+    (headers.date, headers.signature)
 }
 
 // ---------------------------------------------------------------------------
 // Core verification logic (agent auth — looks up key from storage)
 // ---------------------------------------------------------------------------
 
-/// Parse and verify an HTTP Signature, returning the caller DID on success.
+/// Parse and verify an HTTP Signature, returning the caller DID on success,
+/// together with the delegation token if the request was signed by a
+/// delegate.
 ///
-/// Returns `Err(String)` with a human-readable reason on any failure.
+/// Returns an [`AuthError`] with a human-readable reason on any failure.
 async fn verify_http_signature(
     parts: &Parts,
     storage: &Arc<dyn Storage>,
-) -> Result<String, String> {
+) -> Result<(String, Option<Delegation>), AuthError> {
     // --- 1. Extract Signature header ------------------------------------------
     let sig_header = parts
         .headers
         .get("signature")
         .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| "missing Signature header".to_string())?;
+        .ok_or_else(|| AuthError::new(codes::SIGNATURE_MISSING, "missing Signature header"))?;
 
     // --- 2. Parse the Signature header ----------------------------------------
-    let parsed = parse_signature_header(sig_header)
-        .map_err(|e| format!("invalid Signature header: {e}"))?;
+    let parsed = SignatureParams::parse(sig_header).map_err(|e| e.to_string())?;
 
     // --- 3. Validate Date header (replay prevention: ±5 minutes) -------------
     let date_str = parts
         .headers
         .get("date")
         .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| "missing Date header".to_string())?;
-
-    validate_date(date_str).map_err(|e| format!("Date header invalid: {e}"))?;
-
-    // --- 4. Look up agent's public key from storage ---------------------------
-    let profile = storage
-        .get_agent(&parsed.key_id)
-        .await
-        .map_err(|e| format!("storage error: {e}"))?
-        .ok_or_else(|| format!("agent {} not registered", parsed.key_id))?;
-
-    let public_key_multibase = profile
-        .public_key
-        .ok_or_else(|| format!("agent {} has no public key", parsed.key_id))?;
-
-    let verifying_key = decode_multibase_key(&public_key_multibase)
-        .map_err(|e| format!("invalid public key for {}: {e}", parsed.key_id))?;
-
-    // --- 5. Reconstruct signing string ----------------------------------------
-    let signing_string =
-        build_signing_string(parts, &parsed.headers, &parsed.algorithm)
-            .map_err(|e| format!("signing string error: {e}"))?;
-
-    // --- 6. Decode and verify signature ----------------------------------------
-    let sig_bytes_vec = bs58::decode(
-        parsed
-            .signature
-            .strip_prefix('z')
-            .ok_or("signature value must start with 'z'")?
-    )
-    .into_vec()
-    .map_err(|e| format!("bs58 decode failed: {e}"))?;
-
-    let sig_array: [u8; 64] = sig_bytes_vec
-        .try_into()
-        .map_err(|_| "signature must be 64 bytes".to_string())?;
-
-    let signature = ed25519_dalek::Signature::from_bytes(&sig_array);
-
-    verifying_key
-        .verify(signing_string.as_bytes(), &signature)
-        .map_err(|_| "signature verification failed".to_string())?;
-
-    Ok(parsed.key_id)
-}
+        .ok_or_else(|| AuthError::new(codes::SIGNATURE_MISSING, "missing Date header"))?;
+
+    validate_date(date_str).map_err(|e| {
+        AuthError::new(codes::DATE_OUT_OF_RANGE, format!("Date header invalid: {e}"))
+    })?;
+
+    // --- 4. Resolve the verifying key -----------------------------------------
+    let delegation = parse_delegation_header(parts, &parsed)?;
+    let (caller, verifying_key) = match &delegation {
+        None => {
+            // The caller's key is the one it registered.
+            let profile = active_profile(storage, &parsed.key_id).await?;
+            let public_key_multibase = profile
+                .public_key
+                .ok_or_else(|| format!("agent {} has no public key", parsed.key_id))?;
+            let verifying_key = decode_multibase_key(&public_key_multibase)
+                .map_err(|e| format!("invalid public key for {}: {e}", parsed.key_id))?;
+            (parsed.key_id.clone(), verifying_key)
+        }
+        Some(delegation) => {
+            // The delegate signs with the key in its own did:key; the token
+            // names it and must be valid now, for a registered issuer, and
+            // cover this endpoint.
+            if delegation.audience != parsed.key_id {
+                return Err(format!(
+                    "delegation was issued to {}, not {}",
+                    delegation.audience, parsed.key_id
+                )
+                .into());
+            }
+            delegation
+                .verify(SystemTime::now())
+                .map_err(|e| e.to_string())?;
+            active_profile(storage, &delegation.issuer).await?;
+            let path = parts.uri.path();
+            let path = urlencoding::decode(path).map_err(|e| format!("invalid path: {e}"))?;
+            if !delegation
+                .scope
+                .allows_request(&delegation.issuer, parts.method.as_str(), &path)
+            {
+                return Err(format!(
+                    "delegation does not cover {} {path}",
+                    parts.method
+                )
+                .into());
+            }
+            let multibase = parsed
+                .key_id
+                .strip_prefix("did:key:")
+                .ok_or_else(|| format!("delegate keyId must be a did:key, got: {}", parsed.key_id))?;
+            let verifying_key = decode_multibase_key(multibase)
+                .map_err(|e| format!("invalid did:key public key: {e}"))?;
+            (delegation.issuer.clone(), verifying_key)
+        }
+    };
 
-// ---------------------------------------------------------------------------
-// Parsed Signature header
-// ---------------------------------------------------------------------------
+    // --- 5. Verify the signature over the rebuilt signing string -------------
+    verify_request(&parsed, &verifying_key, parts)?;
 
-pub(crate) struct ParsedSignature {
-    key_id: String,
-    algorithm: String,
-    headers: Vec<String>,
-    signature: String,
+    Ok((caller, delegation))
 }
 
-/// Parse `Signature: keyId="...",algorithm="...",headers="...",signature="z..."`
-pub(crate) fn parse_signature_header(header: &str) -> Result<ParsedSignature, String> {
-    let mut key_id = None;
-    let mut algorithm = None;
-    let mut headers = None;
-    let mut signature = None;
-
-    // Split by comma, but be careful of commas inside quoted strings.
-    // Simple approach: split on `",` (closing quote then comma) boundaries.
-    for part in split_signature_params(header) {
-        let part = part.trim().to_string();
-        let part = part.as_str();
-        if let Some(rest) = part.strip_prefix("keyId=") {
-            key_id = Some(unquote(rest)?);
-        } else if let Some(rest) = part.strip_prefix("algorithm=") {
-            algorithm = Some(unquote(rest)?);
-        } else if let Some(rest) = part.strip_prefix("headers=") {
-            let h = unquote(rest)?;
-            headers = Some(h.split(' ').map(String::from).collect::<Vec<_>>());
-        } else if let Some(rest) = part.strip_prefix("signature=") {
-            signature = Some(unquote(rest)?);
-        }
-    }
-
-    Ok(ParsedSignature {
-        key_id: key_id.ok_or("missing keyId")?,
-        algorithm: algorithm.unwrap_or_else(|| "ed25519".into()),
-        headers: headers.unwrap_or_else(|| vec!["date".into()]),
-        signature: signature.ok_or("missing signature")?,
-    })
+/// Check `parsed` against `key` for the request in `parts`, rebuilding the
+/// signing string the way agent-core's signer builds it.
+fn verify_request(
+    parsed: &SignatureParams,
+    key: &ed25519_dalek::VerifyingKey,
+    parts: &Parts,
+) -> Result<(), AuthError> {
+    let target = parts.uri.path_and_query().map(|pq| pq.as_str()).unwrap_or("/");
+    parsed
+        .verify(key, parts.method.as_str(), target, |name| {
+            parts.headers.get(name).and_then(|v| v.to_str().ok())
+        })
+        .map_err(|e| match e {
+            SignatureError::Invalid => AuthError::new(codes::SIGNATURE_INVALID, e.to_string()),
+            e => e.to_string().into(),
+        })
 }
 
-/// Split a `Signature` header value at param boundaries.
-///
-/// Params are separated by `,` but the separator only appears OUTSIDE of
-/// double-quoted strings. We scan character by character to handle this.
-fn split_signature_params(s: &str) -> Vec<String> {
-    let mut parts = Vec::new();
-    let mut current = String::new();
-    let mut in_quotes = false;
-
-    for ch in s.chars() {
-        match ch {
-            '"' => {
-                in_quotes = !in_quotes;
-                current.push(ch);
-            }
-            ',' if !in_quotes => {
-                parts.push(current.trim().to_string());
-                current = String::new();
-            }
-            _ => current.push(ch),
-        }
-    }
-    if !current.trim().is_empty() {
-        parts.push(current.trim().to_string());
-    }
-    parts
-}
+/// Look up a registered agent whose key still speaks for it.
+async fn active_profile(storage: &Arc<dyn Storage>, did: &str) -> Result<AgentProfile, AuthError> {
+    let profile = storage
+        .get_agent(did)
+        .await
+        .map_err(|e| format!("storage error: {e}"))?
+        .ok_or_else(|| AuthError::new(codes::UNKNOWN_AGENT, format!("agent {did} not registered")))?;
 
-/// Strip surrounding double-quotes from a parameter value.
-fn unquote(s: &str) -> Result<String, String> {
-    let s = s.trim();
-    if s.starts_with('"') && s.ends_with('"') && s.len() >= 2 {
-        Ok(s[1..s.len() - 1].to_string())
-    } else {
-        Err(format!("expected quoted string, got: {s:?}"))
+    // A rotated key no longer speaks for the agent (spec §8.7).
+    if let Some(successor) = &profile.successor {
+        return Err(format!("agent {did} rotated its key to {successor}").into());
     }
+    Ok(profile)
 }
 
-// ---------------------------------------------------------------------------
-// Signing string construction
-// ---------------------------------------------------------------------------
-
-/// Build the signing string from request parts and the header list.
-///
-/// Each entry is `header-name: header-value`, joined by `\n`.
-/// `(request-target)` is replaced by `method path` (lowercase method).
-pub(crate) fn build_signing_string(
+/// Decode the `Delegation` header, if present. The signature must cover it,
+/// so a captured request cannot be replayed under another token.
+fn parse_delegation_header(
     parts: &Parts,
-    headers: &[String],
-    _algorithm: &str,
-) -> Result<String, String> {
-    let mut lines = Vec::new();
-
-    for header_name in headers {
-        if header_name == "(request-target)" {
-            let method = parts.method.as_str().to_lowercase();
-            let path = parts.uri.path_and_query().map(|pq| pq.as_str()).unwrap_or("/");
-            lines.push(format!("(request-target): {method} {path}"));
-        } else {
-            let value = parts
-                .headers
-                .get(header_name.as_str())
-                .and_then(|v| v.to_str().ok())
-                .ok_or_else(|| format!("missing header: {header_name}"))?;
-            lines.push(format!("{header_name}: {value}"));
-        }
+    parsed: &SignatureParams,
+) -> Result<Option<Delegation>, String> {
+    let Some(value) = parts.headers.get("delegation") else {
+        return Ok(None);
+    };
+    if !parsed.covers("delegation") {
+        return Err("Delegation header must be covered by the signature".into());
     }
-
-    Ok(lines.join("\n"))
+    let value = value
+        .to_str()
+        .map_err(|_| "Delegation header is not valid ASCII".to_string())?;
+    serde_json::from_str(value)
+        .map(Some)
+        .map_err(|e| format!("invalid Delegation header: {e}"))
 }
 
 // ---------------------------------------------------------------------------
@@ -494,7 +592,7 @@ mod tests {
         http::{Request, StatusCode},
         Router,
     };
-    use ed25519_dalek::{Signer, SigningKey};
+    use ed25519_dalek::{Signer, SigningKey, Verifier};
     use rand::rngs::OsRng;
     use tower::ServiceExt;
 
@@ -522,6 +620,8 @@ mod tests {
             status: semanticweft_node_api::AgentStatus::Full,
             contribution_count: 0,
             reputation: 0.5,
+            predecessor: None,
+            successor: None,
         };
         storage.put_agent(&profile).await.unwrap();
 
@@ -605,10 +705,10 @@ mod tests {
 
     #[tokio::test]
     async fn missing_signature_header() {
-        // Test that parse_signature_header returns error when header is missing.
+        // Test that SignatureParams::parse returns error when header is missing.
         // We test the internal function directly.
         // This is a unit test of the parsing logic.
-        let result = parse_signature_header("");
+        let result = SignatureParams::parse("");
         // Empty string won't have keyId, algorithm, headers, signature
         assert!(result.is_err() || result.is_ok()); // just ensure it doesn't panic
     }
@@ -644,7 +744,6 @@ mod tests {
         );
 
         // Build a fake Parts to pass to verify_node_signature
-        use axum::http::{Method, Uri, Version};
         let req = Request::builder()
             .method("POST")
             .uri(path)
@@ -659,9 +758,246 @@ mod tests {
         assert_eq!(result.unwrap(), node_did);
     }
 
+    #[tokio::test]
+    async fn agent_core_signature_with_digest_accepted() {
+        let (signing_key, did, _multibase) = make_key_and_did();
+        let identity = AgentIdentity::from_seed(&signing_key.to_bytes());
+        let path = "/v1/agents/did:key:zSomeAgent/inbox?x=1";
+        let signed = SignableRequest::from_url("POST", &format!("https://remotehost{path}"))
+            .unwrap()
+            .body(br#"{"id":"u"}"#)
+            .sign(&identity, SystemTime::now());
+
+        let mut req = Request::builder().method("POST").uri(path);
+        for (name, value) in signed.pairs() {
+            req = req.header(name, value);
+        }
+        let (parts, _) = req.body(()).unwrap().into_parts();
+        assert_eq!(verify_node_signature(&parts), Ok(did));
+    }
+
+    #[tokio::test]
+    async fn digest_must_match_the_body() {
+        use semanticweft::{SemanticUnit, UnitType, Visibility};
+
+        let (signing_key, did, multibase) = make_key_and_did();
+        let (app, storage) = registered_app(&signing_key, &did, &multibase).await;
+        let identity = AgentIdentity::from_seed(&signing_key.to_bytes());
+        let submit = |signed_body: &[u8], sent_body: Vec<u8>| {
+            let signed = SignableRequest::from_url("POST", "http://localhost/v1/units")
+                .unwrap()
+                .body(signed_body)
+                .sign(&identity, SystemTime::now());
+            let mut req = Request::builder()
+                .method("POST")
+                .uri("/v1/units")
+                .header("content-type", "application/json");
+            for (name, value) in signed.pairs() {
+                req = req.header(name, value);
+            }
+            req.body(Body::from(sent_body)).unwrap()
+        };
+
+        let mut unit = SemanticUnit::new(UnitType::Assertion, "signed body", &did);
+        unit.visibility = Some(Visibility::Network);
+        let body = serde_json::to_vec(&unit).unwrap();
+        let mut swapped = unit.clone();
+        swapped.content = "swapped body".into();
+        let resp = app
+            .clone()
+            .oneshot(submit(&body, serde_json::to_vec(&swapped).unwrap()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert!(storage.get_unit(&unit.id).await.unwrap().is_none());
+
+        let resp = app.oneshot(submit(&body, body.clone())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+
+    fn delegated_submit(
+        worker: &AgentIdentity,
+        token: &Delegation,
+        unit: &semanticweft::SemanticUnit,
+    ) -> Request<Body> {
+        let body = serde_json::to_vec(unit).unwrap();
+        let token = serde_json::to_string(token).unwrap();
+        let signed = SignableRequest::from_url("POST", "http://localhost/v1/units")
+            .unwrap()
+            .header("delegation", token.as_str())
+            .body(&body)
+            .sign(worker, SystemTime::now());
+        let mut req = Request::builder()
+            .method("POST")
+            .uri("/v1/units")
+            .header("content-type", "application/json")
+            .header("delegation", token.as_str());
+        for (name, value) in signed.pairs() {
+            req = req.header(name, value);
+        }
+        req.body(Body::from(body)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn delegated_request_acts_for_issuer_within_scope() {
+        use semanticweft::{DelegationScope, SemanticUnit, UnitType, Visibility};
+        use std::time::Duration;
+
+        let (root_key, root, multibase) = make_key_and_did();
+        let (app, storage) = registered_app(&root_key, &root, &multibase).await;
+        let root_identity = AgentIdentity::from_seed(&root_key.to_bytes());
+        let worker = AgentIdentity::generate();
+        let now = SystemTime::now();
+        let token = root_identity.delegate(
+            worker.did(),
+            DelegationScope {
+                unit_types: vec![UnitType::Assertion],
+                endpoints: vec!["POST /v1/units".into()],
+                ..Default::default()
+            },
+            now - Duration::from_secs(60),
+            now + Duration::from_secs(3600),
+        );
+
+        // A network unit needs the caller to be its author: the worker
+        // authenticates as the root.
+        let mut unit = SemanticUnit::new(UnitType::Assertion, "delegated", &root);
+        unit.visibility = Some(Visibility::Network);
+        let resp = app.clone().oneshot(delegated_submit(&worker, &token, &unit)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        assert!(storage.get_unit(&unit.id).await.unwrap().is_some());
+
+        // Out of the token's unit scope.
+        let mut question = SemanticUnit::new(UnitType::Question, "why?", &root);
+        question.visibility = Some(Visibility::Network);
+        let resp = app.clone().oneshot(delegated_submit(&worker, &token, &question)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        // A token for another key does not let this one in.
+        let other = AgentIdentity::generate();
+        let resp = app.oneshot(delegated_submit(&other, &token, &unit)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn delegated_request_outside_endpoint_scope_rejected() {
+        use semanticweft::DelegationScope;
+        use std::time::Duration;
+
+        let (root_key, root, multibase) = make_key_and_did();
+        let (_app, storage) = registered_app(&root_key, &root, &multibase).await;
+        let worker = AgentIdentity::generate();
+        let now = SystemTime::now();
+        let token = AgentIdentity::from_seed(&root_key.to_bytes()).delegate(
+            worker.did(),
+            DelegationScope {
+                endpoints: vec!["GET /v1/agents/{did}/inbox".into()],
+                ..Default::default()
+            },
+            now,
+            now + Duration::from_secs(600),
+        );
+        let token_json = serde_json::to_string(&token).unwrap();
+
+        let request = |path: &str, cover: bool| {
+            let mut signable = SignableRequest::from_url("GET", &format!("http://localhost{path}")).unwrap();
+            if cover {
+                signable = signable.header("delegation", token_json.as_str());
+            }
+            let signed = signable.sign(&worker, SystemTime::now());
+            let mut req = Request::builder().method("GET").uri(path).header("delegation", token_json.as_str());
+            for (name, value) in signed.pairs() {
+                req = req.header(name, value);
+            }
+            req.body(()).unwrap().into_parts().0
+        };
+
+        let inbox = format!("/v1/agents/{}/inbox", urlencoding::encode(&root));
+        assert_eq!(
+            verify_http_signature(&request(&inbox, true), &storage).await,
+            Ok((root.clone(), Some(token.clone())))
+        );
+        assert!(verify_http_signature(&request("/v1/agents/did:key:zOther/inbox", true), &storage)
+            .await
+            .is_err());
+        assert!(verify_http_signature(&request(&inbox, false), &storage).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn delegates_cannot_manage_the_issuer() {
+        use semanticweft::{DelegationScope, SemanticUnit, UnitType, Visibility};
+        use std::time::Duration;
+
+        let (root_key, root, multibase) = make_key_and_did();
+        let (app, storage) = registered_app(&root_key, &root, &multibase).await;
+        let root_identity = AgentIdentity::from_seed(&root_key.to_bytes());
+        let worker = AgentIdentity::generate();
+        let now = SystemTime::now();
+        let delegate = |endpoints: Vec<String>| {
+            root_identity.delegate(
+                worker.did(),
+                DelegationScope {
+                    endpoints,
+                    ..Default::default()
+                },
+                now,
+                now + Duration::from_secs(600),
+            )
+        };
+        let everything = delegate(vec!["* /*".into()]);
+        let token_json = serde_json::to_string(&everything).unwrap();
+
+        let delegated = |method: &str, path: &str, body: serde_json::Value| {
+            let body = serde_json::to_vec(&body).unwrap();
+            let signed = SignableRequest::from_url(method, &format!("http://localhost{path}"))
+                .unwrap()
+                .header("delegation", token_json.as_str())
+                .body(&body)
+                .sign(&worker, SystemTime::now());
+            let mut req = Request::builder()
+                .method(method)
+                .uri(path)
+                .header("content-type", "application/json")
+                .header("delegation", token_json.as_str());
+            for (name, value) in signed.pairs() {
+                req = req.header(name, value);
+            }
+            req.body(Body::from(body)).unwrap()
+        };
+
+        let agent = format!("/v1/agents/{root}");
+        let takeover = serde_json::json!({
+            "did": root,
+            "inbox_url": "https://attacker.example/inbox",
+            "public_key": worker.public_key_multibase(),
+        });
+        let follow = serde_json::json!({ "target": "did:key:z6MkTarget" });
+        for (method, path, body) in [
+            ("POST", agent.clone(), takeover),
+            ("DELETE", agent.clone(), serde_json::Value::Null),
+            ("POST", format!("{agent}/following"), follow),
+            ("DELETE", format!("{agent}/following/did:key:z6MkTarget"), serde_json::Value::Null),
+            ("POST", format!("/v1/agents/{}/apply", worker.did()), serde_json::Value::Null),
+        ] {
+            let resp = app.clone().oneshot(delegated(method, &path, body)).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN, "{method} {path}");
+        }
+        let stored = storage.get_agent(&root).await.unwrap().unwrap();
+        assert_eq!(stored.public_key.as_deref(), Some(multibase.as_str()));
+
+        // A token without endpoints lets the worker sign, not call the node.
+        let mut unit = SemanticUnit::new(UnitType::Assertion, "delegated", &root);
+        unit.visibility = Some(Visibility::Network);
+        let resp = app
+            .oneshot(delegated_submit(&worker, &delegate(vec![]), &unit))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
     #[tokio::test]
     async fn node_auth_wrong_key_rejected() {
-        let (signing_key, node_did, _multibase) = make_key_and_did();
+        let (_signing_key, node_did, _multibase) = make_key_and_did();
         let (wrong_key, _, _) = make_key_and_did();
         let date = http_date_now();
         let path = "/v1/agents/did:key:zSomeAgent/inbox";
@@ -683,6 +1019,38 @@ mod tests {
         assert!(result.is_err(), "wrong key should be rejected");
     }
 
+    #[tokio::test]
+    async fn auth_failures_carry_specific_codes() {
+        let (signing_key, did, multibase) = make_key_and_did();
+        let (_, storage) = registered_app(&signing_key, &did, &multibase).await;
+        let (stranger_key, stranger, _) = make_key_and_did();
+        let path = format!("/v1/agents/{did}/inbox");
+        let parts = |key: &SigningKey, key_id: &str, date: Option<&str>| {
+            let date = date.map(str::to_string).unwrap_or_else(http_date_now);
+            let sig = make_signature_header(key, key_id, "get", &path, &date, "localhost");
+            let (parts, _) = Request::builder()
+                .uri(&path)
+                .header("host", "localhost")
+                .header("date", &date)
+                .header("signature", &sig)
+                .body(())
+                .unwrap()
+                .into_parts();
+            parts
+        };
+        let code = |parts: Parts| {
+            let storage = Arc::clone(&storage);
+            async move { verify_http_signature(&parts, &storage).await.unwrap_err().code }
+        };
+
+        let (unsigned, _) = Request::builder().uri(&path).body(()).unwrap().into_parts();
+        assert_eq!(code(unsigned).await, codes::SIGNATURE_MISSING);
+        let stale = parts(&signing_key, &did, Some("Thu, 01 Jan 2020 00:00:00 GMT"));
+        assert_eq!(code(stale).await, codes::DATE_OUT_OF_RANGE);
+        assert_eq!(code(parts(&stranger_key, &stranger, None)).await, codes::UNKNOWN_AGENT);
+        assert_eq!(code(parts(&stranger_key, &did, None)).await, codes::SIGNATURE_INVALID);
+    }
+
     #[tokio::test]
     async fn build_outbound_signature_produces_verifiable_sig() {
         let signing_key = SigningKey::generate(&mut OsRng);
@@ -701,7 +1069,7 @@ mod tests {
         let signing_string = format!(
             "(request-target): post /v1/agents/did:key:z123/inbox\nhost: example.com\ndate: {date}"
         );
-        let parsed = parse_signature_header(&sig_header).unwrap();
+        let parsed = SignatureParams::parse(&sig_header).unwrap();
         let sig_bytes_vec = bs58::decode(
             parsed.signature.strip_prefix('z').unwrap()
         ).into_vec().unwrap();
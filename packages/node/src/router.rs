@@ -13,8 +13,11 @@ use semanticweft::SemanticUnit;
 
 use crate::{
     config::NodeConfig,
-    handlers::{agents, follows, node, peers, units, webfinger, AppState, SSE_CHANNEL_CAPACITY},
-    middleware::rate_limit::{rate_limit_middleware, RateLimiter},
+    handlers::{agents, follows, node, peers, questions, units, webfinger, AppState, SSE_CHANNEL_CAPACITY},
+    middleware::{
+        auth::verify_digest,
+        rate_limit::{rate_limit_middleware, RateLimiter},
+    },
     storage::Storage,
 };
 
@@ -54,8 +57,10 @@ pub fn build_router(
         .route("/v1/units", post(units::submit).get(units::list))
         .route("/v1/units/{id}", get(units::get_by_id))
         .route("/v1/units/{id}/subgraph", get(units::subgraph))
+        .route("/v1/units/{id}/similar", get(units::similar))
         // Sync (node-to-node federation pull)
         .route("/v1/sync", get(units::sync))
+        .route("/v1/questions", get(questions::list))
         // Peers
         .route("/v1/peers", get(peers::list).post(peers::add))
         .route("/v1/peers/{node_id}", patch(peers::update_reputation))
@@ -65,6 +70,10 @@ pub fn build_router(
             post(agents::register).get(agents::get_agent).delete(agents::delete_agent),
         )
         .route("/v1/agents/{did}/apply", post(agents::apply))
+        .route(
+            "/v1/agents/{did}/succession",
+            post(agents::succeed).get(agents::get_succession),
+        )
         .route(
             "/v1/agents/{did}/reputation",
             patch(agents::update_reputation),
@@ -84,6 +93,8 @@ pub fn build_router(
             delete(follows::unfollow),
         )
         .with_state(state)
+        // Check `Digest` headers against the body before any handler sees it.
+        .layer(axum::middleware::from_fn(verify_digest))
         // Rate limiting layer applied after routing so it can see the full request.
         .layer(axum::middleware::from_fn(move |req, next| {
             rate_limit_middleware(Arc::clone(&rate_limiter), req, next)
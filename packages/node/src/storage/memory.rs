@@ -11,8 +11,8 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::RwLock;
 
 use async_trait::async_trait;
-use semanticweft::{SemanticUnit, Visibility};
-use semanticweft_node_api::{AgentProfile, AgentStatus, PeerInfo};
+use semanticweft::{MinHash, SemanticUnit, SimilarityIndex, Visibility};
+use semanticweft_node_api::{AgentProfile, AgentStatus, PeerInfo, SuccessionStatement};
 
 use super::{ReputationStats, Storage, StorageError, UnitFilter};
 
@@ -24,8 +24,12 @@ struct Inner {
     units: BTreeMap<String, SemanticUnit>,
     /// Receiver-computed credibility scores for units (unit_id → credibility).
     unit_credibility: HashMap<String, f32>,
+    /// LSH index of unit content signatures, maintained by `put_unit`.
+    similarity: SimilarityIndex,
     agents: HashMap<String, AgentProfile>,
     follows: HashSet<(String, String)>,
+    /// Accepted succession statements, keyed by predecessor DID.
+    successions: HashMap<String, SuccessionStatement>,
     peers: HashMap<String, PeerInfo>,
     cursors: HashMap<String, String>,
     node_config: HashMap<String, String>,
@@ -39,8 +43,10 @@ impl Inner {
         Self {
             units: BTreeMap::new(),
             unit_credibility: HashMap::new(),
+            similarity: SimilarityIndex::new(),
             agents: HashMap::new(),
             follows: HashSet::new(),
+            successions: HashMap::new(),
             peers: HashMap::new(),
             cursors: HashMap::new(),
             node_config: HashMap::new(),
@@ -88,6 +94,7 @@ impl Storage for MemoryStorage {
                 unit.id
             )));
         }
+        inner.similarity.insert_unit(unit);
         inner.units.insert(unit.id.clone(), unit.clone());
         Ok(())
     }
@@ -97,6 +104,11 @@ impl Storage for MemoryStorage {
         Ok(inner.units.get(id).cloned())
     }
 
+    async fn get_units(&self, ids: &[String]) -> Result<Vec<SemanticUnit>, StorageError> {
+        let inner = self.inner.read().unwrap();
+        Ok(ids.iter().filter_map(|id| inner.units.get(id).cloned()).collect())
+    }
+
     async fn list_units(
         &self,
         filter: &UnitFilter,
@@ -135,6 +147,17 @@ impl Storage for MemoryStorage {
                         return false;
                     }
                 }
+                // Triple-pattern filter
+                if filter.subject.is_some() || filter.predicate.is_some() {
+                    let triples = semanticweft::triples::unit_triples(u).unwrap_or_default();
+                    let matched = triples.iter().any(|t| {
+                        filter.subject.as_ref().is_none_or(|s| *s == t.subject)
+                            && filter.predicate.as_ref().is_none_or(|p| *p == t.predicate)
+                    });
+                    if !matched {
+                        return false;
+                    }
+                }
                 // Visibility filter
                 if !filter.visibilities.is_empty() {
                     let vis = u.visibility.as_ref().unwrap_or(&Visibility::Public);
@@ -142,10 +165,11 @@ impl Storage for MemoryStorage {
                         return false;
                     }
                     // For network units, further restrict to authors the caller follows.
-                    if *vis == Visibility::Network && !filter.network_for_authors.is_empty() {
-                        if !filter.network_for_authors.contains(&u.author) {
-                            return false;
-                        }
+                    if *vis == Visibility::Network
+                        && !filter.network_for_authors.is_empty()
+                        && !filter.network_for_authors.contains(&u.author)
+                    {
+                        return false;
                     }
                 }
                 true
@@ -170,7 +194,7 @@ impl Storage for MemoryStorage {
             .filter(|u| {
                 u.references
                     .as_ref()
-                    .map_or(false, |refs| refs.iter().any(|r| r.id == id))
+                    .is_some_and(|refs| refs.iter().any(|r| r.id == id))
             })
             .cloned()
             .collect();
@@ -192,6 +216,26 @@ impl Storage for MemoryStorage {
         Ok(())
     }
 
+    // --- Similarity index ----------------------------------------------------
+
+    async fn get_unit_signature(&self, id: &str) -> Result<Option<MinHash>, StorageError> {
+        let inner = self.inner.read().unwrap();
+        Ok(inner.similarity.signature(id).cloned())
+    }
+
+    async fn similarity_candidates(
+        &self,
+        signature: &MinHash,
+    ) -> Result<Vec<(String, MinHash)>, StorageError> {
+        let inner = self.inner.read().unwrap();
+        Ok(inner
+            .similarity
+            .candidates(signature)
+            .into_iter()
+            .map(|(id, sig)| (id.to_string(), sig.clone()))
+            .collect())
+    }
+
     // --- Agents --------------------------------------------------------------
 
     async fn put_agent(&self, profile: &AgentProfile) -> Result<(), StorageError> {
@@ -249,12 +293,13 @@ impl Storage for MemoryStorage {
 
     async fn agent_reputation_stats(&self) -> Result<ReputationStats, StorageError> {
         let inner = self.inner.read().unwrap();
-        let count = inner.agents.len();
+        let active = || inner.agents.values().filter(|a| a.successor.is_none());
+        let count = active().count();
         if count == 0 {
             return Ok(ReputationStats { mean: 0.0, stddev: 0.0 });
         }
-        let sum: f64 = inner.agents.values().map(|a| a.reputation as f64).sum();
-        let sum_sq: f64 = inner.agents.values().map(|a| (a.reputation as f64).powi(2)).sum();
+        let sum: f64 = active().map(|a| a.reputation as f64).sum();
+        let sum_sq: f64 = active().map(|a| (a.reputation as f64).powi(2)).sum();
         let n = count as f64;
         let mean = sum / n;
         let variance = (sum_sq / n - mean * mean).max(0.0);
@@ -265,6 +310,55 @@ impl Storage for MemoryStorage {
         })
     }
 
+    async fn apply_succession(
+        &self,
+        statement: &SuccessionStatement,
+        successor: &AgentProfile,
+    ) -> Result<(), StorageError> {
+        let old = statement.predecessor.as_str();
+        let new = statement.successor.as_str();
+        let mut inner = self.inner.write().unwrap();
+
+        match inner.agents.get_mut(old) {
+            Some(profile) if profile.successor.is_none() => {
+                profile.successor = Some(new.to_string());
+            }
+            _ => {
+                return Err(StorageError::Conflict(format!(
+                    "agent {old} is not registered or was already rotated"
+                )))
+            }
+        }
+        inner.agents.insert(successor.did.clone(), successor.clone());
+
+        let follows = std::mem::take(&mut inner.follows);
+        inner.follows = follows
+            .into_iter()
+            .map(|(follower, followee)| {
+                let rename = |did: String| if did == old { new.to_string() } else { did };
+                (rename(follower), rename(followee))
+            })
+            .filter(|(follower, followee)| follower != followee)
+            .collect();
+
+        if let Some(items) = inner.inbox.remove(old) {
+            inner.inbox.entry(new.to_string()).or_default().extend(items);
+        }
+
+        inner
+            .successions
+            .insert(old.to_string(), statement.clone());
+        Ok(())
+    }
+
+    async fn get_succession(
+        &self,
+        predecessor: &str,
+    ) -> Result<Option<SuccessionStatement>, StorageError> {
+        let inner = self.inner.read().unwrap();
+        Ok(inner.successions.get(predecessor).cloned())
+    }
+
     // --- Follows -------------------------------------------------------------
 
     async fn add_follow(&self, follower: &str, followee: &str) -> Result<(), StorageError> {
@@ -499,6 +593,8 @@ mod tests {
             status: AgentStatus::Full,
             contribution_count: 0,
             reputation: 0.5,
+            predecessor: None,
+            successor: None,
         }
     }
 
@@ -520,6 +616,66 @@ mod tests {
         assert!(matches!(err, StorageError::Conflict(_)));
     }
 
+    #[tokio::test]
+    async fn put_unit_indexes_similarity_signature() {
+        let s = MemoryStorage::new();
+        let mut a = unit("019526b2-f68a-7c3e-a0b4-000000000001");
+        a.content = "Water boils at 100 degrees Celsius at sea level.".into();
+        let mut b = unit("019526b2-f68a-7c3e-a0b4-000000000002");
+        b.content = "Water boils at 100 degrees Celsius at sea level!".into();
+        let mut c = unit("019526b2-f68a-7c3e-a0b4-000000000003");
+        c.content = "Cats sleep for most of the day.".into();
+        for u in [&a, &b, &c] {
+            s.put_unit(u).await.unwrap();
+        }
+
+        let sig = s.get_unit_signature(&a.id).await.unwrap().unwrap();
+        assert_eq!(sig, MinHash::of(&a.content));
+        assert!(s.get_unit_signature("missing").await.unwrap().is_none());
+
+        let mut ids: Vec<String> = s
+            .similarity_candidates(&sig)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![a.id, b.id]);
+    }
+
+    #[tokio::test]
+    async fn list_units_filters_by_triple_pattern() {
+        let s = MemoryStorage::new();
+        let triple = |subject: &str, predicate: &str| {
+            serde_json::json!([{ "subject": subject, "predicate": predicate,
+                                  "object": { "kind": "entity", "id": "o" } }])
+        };
+        let mut a = unit("019526b2-f68a-7c3e-a0b4-000000000001");
+        a.extensions.insert("x-org.semanticweft.triples".into(), triple("water", "boils-at"));
+        let mut b = unit("019526b2-f68a-7c3e-a0b4-000000000002");
+        b.extensions.insert("x-org.semanticweft.triples".into(), triple("water", "freezes-at"));
+        let c = unit("019526b2-f68a-7c3e-a0b4-000000000003");
+        for u in [&a, &b, &c] {
+            s.put_unit(u).await.unwrap();
+        }
+
+        let ids = |page: Vec<SemanticUnit>| page.into_iter().map(|u| u.id).collect::<Vec<_>>();
+        let filter = UnitFilter {
+            limit: 10,
+            subject: Some("water".into()),
+            ..Default::default()
+        };
+        assert_eq!(ids(s.list_units(&filter).await.unwrap().0), vec![a.id.clone(), b.id.clone()]);
+        let filter = UnitFilter {
+            limit: 10,
+            subject: Some("water".into()),
+            predicate: Some("freezes-at".into()),
+            ..Default::default()
+        };
+        assert_eq!(ids(s.list_units(&filter).await.unwrap().0), vec![b.id.clone()]);
+    }
+
     #[tokio::test]
     async fn list_units_pagination() {
         let s = MemoryStorage::new();
@@ -565,6 +721,36 @@ mod tests {
         assert!(s.get_agent("did:key:z6MkNobody").await.unwrap().is_none());
     }
 
+    #[tokio::test]
+    async fn apply_succession_applies_only_the_first_rotation() {
+        let s = MemoryStorage::new();
+        s.put_agent(&agent("old")).await.unwrap();
+        let statement = |successor: &str| SuccessionStatement {
+            predecessor: "old".into(),
+            successor: successor.into(),
+            created_at: "2026-03-01T12:00:00Z".into(),
+            reason: None,
+            predecessor_signature: "zOld".into(),
+            successor_signature: "zNew".into(),
+        };
+        let successor = |did: &str| AgentProfile {
+            predecessor: Some("old".into()),
+            ..agent(did)
+        };
+        s.apply_succession(&statement("b"), &successor("b")).await.unwrap();
+        let err = s
+            .apply_succession(&statement("c"), &successor("c"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StorageError::Conflict(_)), "{err}");
+        assert_eq!(
+            s.get_agent("old").await.unwrap().unwrap().successor.as_deref(),
+            Some("b")
+        );
+        assert!(s.get_agent("c").await.unwrap().is_none());
+        assert_eq!(s.get_succession("old").await.unwrap(), Some(statement("b")));
+    }
+
     #[tokio::test]
     async fn peer_roundtrip_with_reputation() {
         let s = MemoryStorage::new();
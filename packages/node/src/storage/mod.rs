@@ -18,8 +18,8 @@ pub mod memory;
 pub mod sqlite;
 
 use async_trait::async_trait;
-use semanticweft::{SemanticUnit, UnitType, Visibility};
-use semanticweft_node_api::{AgentProfile, PeerInfo};
+use semanticweft::{MinHash, SemanticUnit, UnitType, Visibility};
+use semanticweft_node_api::{AgentProfile, PeerInfo, SuccessionStatement};
 
 // ---------------------------------------------------------------------------
 // StorageError
@@ -63,6 +63,14 @@ pub struct UnitFilter {
     /// Include only units whose `created_at >= since` (ISO 8601 string).
     pub since: Option<String>,
 
+    /// Include only units carrying a claim triple with this subject
+    /// (see [`semanticweft::triples`]). Combined with `predicate`, both must
+    /// match the same triple.
+    pub subject: Option<String>,
+
+    /// Include only units carrying a claim triple with this predicate.
+    pub predicate: Option<String>,
+
     /// Cursor for keyset pagination: include only units whose `id > after`
     /// (UUIDv7 lexicographic order). `None` means start from the beginning.
     pub after: Option<String>,
@@ -122,6 +130,10 @@ pub trait Storage: Send + Sync + 'static {
     /// Retrieve a unit by its UUIDv7 `id`. Returns `None` if not found.
     async fn get_unit(&self, id: &str) -> Result<Option<SemanticUnit>, StorageError>;
 
+    /// Retrieve the units with the given IDs in one call. IDs that are not
+    /// stored are skipped; the order of the result is unspecified.
+    async fn get_units(&self, ids: &[String]) -> Result<Vec<SemanticUnit>, StorageError>;
+
     /// Return a page of units matching `filter`, ordered by `id` ascending.
     ///
     /// Returns `(units, has_more)` where `has_more` is `true` when there are
@@ -150,6 +162,27 @@ pub trait Storage: Send + Sync + 'static {
         credibility: f32,
     ) -> Result<(), StorageError>;
 
+    // --- Similarity index ----------------------------------------------------
+
+    /// The MinHash signature of unit `id`'s content, or `None` if the unit is
+    /// not stored.
+    ///
+    /// Implementations compute and index the signature in [`put_unit`], so
+    /// the similarity index never lags behind the unit store.
+    ///
+    /// [`put_unit`]: Storage::put_unit
+    async fn get_unit_signature(&self, id: &str) -> Result<Option<MinHash>, StorageError>;
+
+    /// IDs and signatures of every stored unit that shares at least one LSH
+    /// band with `signature` (see [`semanticweft::similarity`]).
+    ///
+    /// Candidates are unscored and unfiltered by visibility; handlers rank
+    /// them and decide what the caller may see.
+    async fn similarity_candidates(
+        &self,
+        signature: &MinHash,
+    ) -> Result<Vec<(String, MinHash)>, StorageError>;
+
     // --- Agents --------------------------------------------------------------
 
     /// Register or update an agent profile (upsert by `did`).
@@ -191,6 +224,31 @@ pub trait Storage: Send + Sync + 'static {
     /// `threshold = max(0.0, mean − σ_factor × stddev)`.
     async fn agent_reputation_stats(&self) -> Result<ReputationStats, StorageError>;
 
+    /// Apply a verified key rotation (spec §8.7) in one step.
+    ///
+    /// Stores `successor` as a new profile, sets the predecessor profile's
+    /// `successor` field, re-points every follow edge and inbox item from
+    /// `statement.predecessor` to `statement.successor` (dropping edges that
+    /// would become self-follows), and keeps `statement` for
+    /// [`get_succession`]. Signature checks are the handler's job.
+    ///
+    /// Returns [`StorageError::Conflict`], changing nothing, unless the
+    /// predecessor is registered and not yet rotated, so of two concurrent
+    /// rotations of one agent only the first applies.
+    ///
+    /// [`get_succession`]: Storage::get_succession
+    async fn apply_succession(
+        &self,
+        statement: &SuccessionStatement,
+        successor: &AgentProfile,
+    ) -> Result<(), StorageError>;
+
+    /// The succession statement that retired `predecessor`, if any.
+    async fn get_succession(
+        &self,
+        predecessor: &str,
+    ) -> Result<Option<SuccessionStatement>, StorageError>;
+
     // --- Follows -------------------------------------------------------------
 
     /// Record that `follower` follows `followee`. Idempotent.
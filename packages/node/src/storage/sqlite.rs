@@ -8,8 +8,11 @@
 //!
 //! - `units` — full JSON blob plus indexed columns for filtering.
 //! - `unit_references` — denormalised edge index for inbound subgraph traversal.
+//! - `unit_signatures` / `unit_lsh_bands` — MinHash near-duplicate index.
+//! - `unit_triples` — subject/predicate index over claim triples.
 //! - `agents` — registered agent profiles.
 //! - `follows` — (follower, followee) edges.
+//! - `successions` — accepted key-rotation statements, by predecessor DID.
 //! - `peers` — known peer nodes with reputation and last_seen (ADR-0008).
 //! - `sync_cursors` — last-seen UUIDv7 per peer, for incremental federation.
 //! - `node_config` — key-value store for node-level settings (e.g. identity seed).
@@ -18,8 +21,8 @@ use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use rusqlite::{params, Connection};
-use semanticweft::{SemanticUnit, Visibility};
-use semanticweft_node_api::{AgentProfile, AgentStatus, PeerInfo};
+use semanticweft::{MinHash, SemanticUnit, Visibility};
+use semanticweft_node_api::{AgentProfile, AgentStatus, PeerInfo, SuccessionStatement};
 
 use super::{ReputationStats, Storage, StorageError, UnitFilter};
 
@@ -51,6 +54,33 @@ CREATE TABLE IF NOT EXISTS unit_references (
 );
 CREATE INDEX IF NOT EXISTS idx_unit_refs_referenced ON unit_references(referenced_id);
 
+-- MinHash signature of each unit's content, as a JSON array of integers.
+-- Populated on unit insert (and backfilled by migrate for older databases).
+CREATE TABLE IF NOT EXISTS unit_signatures (
+    unit_id   TEXT PRIMARY KEY,
+    signature TEXT NOT NULL
+);
+
+-- LSH buckets: one row per (unit, band). Units sharing a (band, bucket) pair
+-- are near-duplicate candidates; see semanticweft::similarity.
+CREATE TABLE IF NOT EXISTS unit_lsh_bands (
+    band    INTEGER NOT NULL,
+    bucket  INTEGER NOT NULL,
+    unit_id TEXT NOT NULL,
+    PRIMARY KEY (band, bucket, unit_id)
+);
+
+-- Claim-triple index: one row per (unit, subject, predicate) in the unit's
+-- x-org.semanticweft.triples extension. Populated on unit insert.
+CREATE TABLE IF NOT EXISTS unit_triples (
+    unit_id   TEXT NOT NULL,
+    subject   TEXT NOT NULL,
+    predicate TEXT NOT NULL,
+    PRIMARY KEY (unit_id, subject, predicate)
+);
+CREATE INDEX IF NOT EXISTS idx_unit_triples_sp ON unit_triples(subject, predicate);
+CREATE INDEX IF NOT EXISTS idx_unit_triples_p  ON unit_triples(predicate);
+
 CREATE TABLE IF NOT EXISTS agents (
     did                TEXT PRIMARY KEY,
     inbox_url          TEXT NOT NULL,
@@ -58,7 +88,9 @@ CREATE TABLE IF NOT EXISTS agents (
     public_key         TEXT,
     status             TEXT NOT NULL DEFAULT 'full',
     contribution_count INTEGER NOT NULL DEFAULT 0,
-    reputation         REAL NOT NULL DEFAULT 0.5
+    reputation         REAL NOT NULL DEFAULT 0.5,
+    predecessor        TEXT,
+    successor          TEXT
 );
 
 CREATE TABLE IF NOT EXISTS follows (
@@ -68,6 +100,13 @@ CREATE TABLE IF NOT EXISTS follows (
 );
 CREATE INDEX IF NOT EXISTS idx_follows_followee ON follows(followee);
 
+-- Accepted succession statements (spec §8.7); data is the statement JSON.
+CREATE TABLE IF NOT EXISTS successions (
+    predecessor TEXT PRIMARY KEY,
+    successor   TEXT NOT NULL,
+    data        TEXT NOT NULL
+);
+
 CREATE TABLE IF NOT EXISTS peers (
     node_id    TEXT PRIMARY KEY,
     api_base   TEXT NOT NULL,
@@ -134,7 +173,8 @@ impl SqliteStorage {
 
     /// Apply additive migrations for existing databases that pre-date schema
     /// additions. Each `ALTER TABLE` is attempted and the error for "duplicate
-    /// column name" is swallowed — this is intentional and safe.
+    /// column name" is swallowed — this is intentional and safe. Index
+    /// backfills are numbered and tracked in `PRAGMA user_version`.
     fn migrate(conn: &Connection) -> Result<(), rusqlite::Error> {
         // peers: reputation and last_seen columns (added in this version)
         let _ = conn.execute(
@@ -157,6 +197,9 @@ impl SqliteStorage {
             "ALTER TABLE agents ADD COLUMN reputation REAL NOT NULL DEFAULT 0.5",
             [],
         );
+        // agents: key rotation links (spec §8.7).
+        let _ = conn.execute("ALTER TABLE agents ADD COLUMN predecessor TEXT", []);
+        let _ = conn.execute("ALTER TABLE agents ADD COLUMN successor TEXT", []);
 
         // units: credibility column (receiver-computed score).
         let _ = conn.execute(
@@ -165,10 +208,105 @@ impl SqliteStorage {
         );
 
         // node_config table is covered by CREATE TABLE IF NOT EXISTS in SCHEMA.
+
+        // Backfills below run once per database: `user_version` records the
+        // last one applied, and each commits together with its bump.
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+        // 1. unit_signatures / unit_lsh_bands: backfill units stored before
+        //    the similarity index existed.
+        if version < 1 {
+            let tx = conn.unchecked_transaction()?;
+            let missing: Vec<(String, String)> = tx
+                .prepare(
+                    "SELECT id, data FROM units
+                     WHERE id NOT IN (SELECT unit_id FROM unit_signatures)",
+                )?
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<_, _>>()?;
+            for (id, data) in missing {
+                if let Ok(unit) = serde_json::from_str::<SemanticUnit>(&data) {
+                    index_signature(&tx, &id, &MinHash::of(&unit.content))?;
+                }
+            }
+            tx.execute_batch("PRAGMA user_version = 1")?;
+            tx.commit()?;
+        }
+
+        // 2. unit_triples: backfill units carrying triples stored before the
+        //    index existed. Re-indexing is idempotent (INSERT OR IGNORE).
+        if version < 2 {
+            let tx = conn.unchecked_transaction()?;
+            let with_triples: Vec<String> = tx
+                .prepare("SELECT data FROM units WHERE data LIKE '%x-org.semanticweft.triples%'")?
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<_, _>>()?;
+            for data in with_triples {
+                if let Ok(unit) = serde_json::from_str::<SemanticUnit>(&data) {
+                    index_triples(&tx, &unit)?;
+                }
+            }
+            tx.execute_batch("PRAGMA user_version = 2")?;
+            tx.commit()?;
+        }
+
+        // 3. unit_signatures / unit_lsh_bands: rebuild every entry for the
+        //    longer signatures and 4-row bands, which change all band keys.
+        if version < 3 {
+            let tx = conn.unchecked_transaction()?;
+            tx.execute_batch("DELETE FROM unit_lsh_bands; DELETE FROM unit_signatures")?;
+            let units: Vec<(String, String)> = tx
+                .prepare("SELECT id, data FROM units")?
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<_, _>>()?;
+            for (id, data) in units {
+                if let Ok(unit) = serde_json::from_str::<SemanticUnit>(&data) {
+                    index_signature(&tx, &id, &MinHash::of(&unit.content))?;
+                }
+            }
+            tx.execute_batch("PRAGMA user_version = 3")?;
+            tx.commit()?;
+        }
         Ok(())
     }
 }
 
+/// Record the (subject, predicate) pairs of `unit`'s claim triples.
+/// Units whose triples extension is malformed are not indexed.
+fn index_triples(conn: &Connection, unit: &SemanticUnit) -> Result<(), rusqlite::Error> {
+    for t in semanticweft::triples::unit_triples(unit).unwrap_or_default() {
+        conn.execute(
+            "INSERT OR IGNORE INTO unit_triples (unit_id, subject, predicate) VALUES (?1, ?2, ?3)",
+            params![unit.id, t.subject, t.predicate],
+        )?;
+    }
+    Ok(())
+}
+
+/// Store `signature` for `id` and file it under each of its LSH band buckets.
+fn index_signature(conn: &Connection, id: &str, signature: &MinHash) -> Result<(), rusqlite::Error> {
+    let json = serde_json::to_string(signature)
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+    conn.execute(
+        "INSERT OR REPLACE INTO unit_signatures (unit_id, signature) VALUES (?1, ?2)",
+        params![id, json],
+    )?;
+    for (band, key) in signature.band_keys().into_iter().enumerate() {
+        conn.execute(
+            "INSERT OR IGNORE INTO unit_lsh_bands (band, bucket, unit_id) VALUES (?1, ?2, ?3)",
+            // SQLite integers are signed; the cast is a lossless bit reinterpretation.
+            params![band as i64, key as i64, id],
+        )?;
+    }
+    Ok(())
+}
+
+fn parse_signature(json: &str) -> Result<MinHash, StorageError> {
+    let values: Vec<u64> = serde_json::from_str(json).map_err(map_json_err)?;
+    MinHash::from_values(values)
+        .ok_or_else(|| StorageError::Internal("malformed unit signature".into()))
+}
+
 // ---------------------------------------------------------------------------
 // Error conversions
 // ---------------------------------------------------------------------------
@@ -240,7 +378,10 @@ impl Storage for SqliteStorage {
                 .unwrap_or(&Visibility::Public)
                 .to_string();
 
-            conn.execute(
+            // The unit and its index rows land together: a unit stored
+            // without them could never be indexed, since a retry conflicts.
+            let tx = conn.unchecked_transaction().map_err(map_err)?;
+            tx.execute(
                 "INSERT INTO units (id, unit_type, author, created_at, visibility, data)
                  VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
                 params![
@@ -257,7 +398,7 @@ impl Storage for SqliteStorage {
             // Populate the reference index.
             if let Some(refs) = &unit.references {
                 for r in refs {
-                    conn.execute(
+                    tx.execute(
                         "INSERT OR IGNORE INTO unit_references (referencing_id, referenced_id)
                          VALUES (?1, ?2)",
                         params![unit.id, r.id],
@@ -266,6 +407,11 @@ impl Storage for SqliteStorage {
                 }
             }
 
+            // Keep the similarity and triple indexes in step with the unit store.
+            index_signature(&tx, &unit.id, &MinHash::of(&unit.content)).map_err(map_err)?;
+            index_triples(&tx, &unit).map_err(map_err)?;
+            tx.commit().map_err(map_err)?;
+
             Ok(())
         })
         .await
@@ -297,6 +443,33 @@ impl Storage for SqliteStorage {
         .map_err(|e| StorageError::Internal(format!("task join error: {e}")))?
     }
 
+    async fn get_units(&self, ids: &[String]) -> Result<Vec<SemanticUnit>, StorageError> {
+        let conn = Arc::clone(&self.conn);
+        let ids = ids.to_vec();
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            let mut units = Vec::new();
+            // Chunked to stay well under SQLite's bound-parameter limit.
+            for chunk in ids.chunks(500) {
+                let placeholders = vec!["?"; chunk.len()].join(", ");
+                let mut stmt = conn
+                    .prepare(&format!("SELECT data FROM units WHERE id IN ({placeholders})"))
+                    .map_err(map_err)?;
+                let rows = stmt
+                    .query_map(rusqlite::params_from_iter(chunk), |row| row.get::<_, String>(0))
+                    .map_err(map_err)?;
+                for data in rows {
+                    let data = data.map_err(map_err)?;
+                    units.push(serde_json::from_str(&data).map_err(map_json_err)?);
+                }
+            }
+            Ok(units)
+        })
+        .await
+        .map_err(|e| StorageError::Internal(format!("task join error: {e}")))?
+    }
+
     async fn list_units(
         &self,
         filter: &UnitFilter,
@@ -332,6 +505,19 @@ impl Storage for SqliteStorage {
                 params_vec.push(SqlParam::Text(since.clone()));
             }
 
+            if filter.subject.is_some() || filter.predicate.is_some() {
+                sql.push_str(" AND id IN (SELECT unit_id FROM unit_triples WHERE 1=1");
+                if let Some(subject) = &filter.subject {
+                    sql.push_str(" AND subject = ?");
+                    params_vec.push(SqlParam::Text(subject.clone()));
+                }
+                if let Some(predicate) = &filter.predicate {
+                    sql.push_str(" AND predicate = ?");
+                    params_vec.push(SqlParam::Text(predicate.clone()));
+                }
+                sql.push(')');
+            }
+
             if let Some(after) = &filter.after {
                 sql.push_str(" AND id > ?");
                 params_vec.push(SqlParam::Text(after.clone()));
@@ -451,6 +637,68 @@ impl Storage for SqliteStorage {
         .map_err(|e| StorageError::Internal(format!("task join error: {e}")))?
     }
 
+    // --- Similarity index ----------------------------------------------------
+
+    async fn get_unit_signature(&self, id: &str) -> Result<Option<MinHash>, StorageError> {
+        let conn = Arc::clone(&self.conn);
+        let id = id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            let result = conn.query_row(
+                "SELECT signature FROM unit_signatures WHERE unit_id = ?1",
+                params![id],
+                |row| row.get::<_, String>(0),
+            );
+            match result {
+                Ok(json) => parse_signature(&json).map(Some),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(map_err(e)),
+            }
+        })
+        .await
+        .map_err(|e| StorageError::Internal(format!("task join error: {e}")))?
+    }
+
+    async fn similarity_candidates(
+        &self,
+        signature: &MinHash,
+    ) -> Result<Vec<(String, MinHash)>, StorageError> {
+        let conn = Arc::clone(&self.conn);
+        let keys = signature.band_keys();
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            let mut stmt = conn
+                .prepare(
+                    "SELECT s.unit_id, s.signature FROM unit_lsh_bands b
+                     JOIN unit_signatures s ON s.unit_id = b.unit_id
+                     WHERE b.band = ?1 AND b.bucket = ?2",
+                )
+                .map_err(map_err)?;
+
+            let mut seen = std::collections::HashSet::new();
+            let mut out = Vec::new();
+            for (band, key) in keys.into_iter().enumerate() {
+                let rows = stmt
+                    .query_map(params![band as i64, key as i64], |row| {
+                        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                    })
+                    .map_err(map_err)?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(map_err)?;
+                for (id, json) in rows {
+                    if seen.insert(id.clone()) {
+                        out.push((id, parse_signature(&json)?));
+                    }
+                }
+            }
+            Ok(out)
+        })
+        .await
+        .map_err(|e| StorageError::Internal(format!("task join error: {e}")))?
+    }
+
     // --- Agents --------------------------------------------------------------
 
     async fn put_agent(&self, profile: &AgentProfile) -> Result<(), StorageError> {
@@ -464,15 +712,17 @@ impl Storage for SqliteStorage {
             };
             let conn = conn.lock().unwrap();
             conn.execute(
-                "INSERT INTO agents (did, inbox_url, display_name, public_key, status, contribution_count, reputation)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                "INSERT INTO agents (did, inbox_url, display_name, public_key, status, contribution_count, reputation, predecessor, successor)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
                  ON CONFLICT(did) DO UPDATE SET
                    inbox_url          = excluded.inbox_url,
                    display_name       = excluded.display_name,
                    public_key         = excluded.public_key,
                    status             = excluded.status,
                    contribution_count = excluded.contribution_count,
-                   reputation         = excluded.reputation",
+                   reputation         = excluded.reputation,
+                   predecessor        = excluded.predecessor,
+                   successor          = excluded.successor",
                 params![
                     profile.did,
                     profile.inbox_url,
@@ -481,6 +731,8 @@ impl Storage for SqliteStorage {
                     status_str,
                     profile.contribution_count,
                     profile.reputation as f64,
+                    profile.predecessor,
+                    profile.successor,
                 ],
             )
             .map_err(map_err)?;
@@ -497,7 +749,8 @@ impl Storage for SqliteStorage {
         tokio::task::spawn_blocking(move || {
             let conn = conn.lock().unwrap();
             let result = conn.query_row(
-                "SELECT did, inbox_url, display_name, public_key, status, contribution_count, reputation
+                "SELECT did, inbox_url, display_name, public_key, status, contribution_count, reputation,
+                        predecessor, successor
                  FROM agents WHERE did = ?1",
                 params![did],
                 |row| {
@@ -514,6 +767,8 @@ impl Storage for SqliteStorage {
                         },
                         contribution_count: row.get(5)?,
                         reputation: row.get::<_, f64>(6)? as f32,
+                        predecessor: row.get(7)?,
+                        successor: row.get(8)?,
                     })
                 },
             );
@@ -620,7 +875,8 @@ impl Storage for SqliteStorage {
         tokio::task::spawn_blocking(move || {
             let conn = conn.lock().unwrap();
             let result = conn.query_row(
-                "SELECT COUNT(*), AVG(reputation), AVG(reputation * reputation) FROM agents",
+                "SELECT COUNT(*), AVG(reputation), AVG(reputation * reputation) FROM agents
+                 WHERE successor IS NULL",
                 [],
                 |row| {
                     let count: i64 = row.get(0)?;
@@ -646,6 +902,121 @@ impl Storage for SqliteStorage {
         .map_err(|e| StorageError::Internal(format!("task join error: {e}")))?
     }
 
+    async fn apply_succession(
+        &self,
+        statement: &SuccessionStatement,
+        successor: &AgentProfile,
+    ) -> Result<(), StorageError> {
+        let conn = Arc::clone(&self.conn);
+        let statement = statement.clone();
+        let successor = successor.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let status_str = match successor.status {
+                AgentStatus::Full => "full",
+                AgentStatus::Probationary => "probationary",
+            };
+            let data = serde_json::to_string(&statement)
+                .map_err(|e| StorageError::Internal(e.to_string()))?;
+            let old = statement.predecessor.as_str();
+            let new = statement.successor.as_str();
+
+            let conn = conn.lock().unwrap();
+            let tx = conn.unchecked_transaction().map_err(map_err)?;
+            // Claim the predecessor first; dropping `tx` rolls back.
+            let claimed = tx
+                .execute(
+                    "UPDATE agents SET successor = ?1 WHERE did = ?2 AND successor IS NULL",
+                    params![new, old],
+                )
+                .map_err(map_err)?;
+            if claimed != 1 {
+                return Err(StorageError::Conflict(format!(
+                    "agent {old} is not registered or was already rotated"
+                )));
+            }
+            tx.execute(
+                "INSERT INTO agents (did, inbox_url, display_name, public_key, status, contribution_count, reputation, predecessor, successor)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    successor.did,
+                    successor.inbox_url,
+                    successor.display_name,
+                    successor.public_key,
+                    status_str,
+                    successor.contribution_count,
+                    successor.reputation as f64,
+                    successor.predecessor,
+                    successor.successor,
+                ],
+            )
+            .map_err(map_err)?;
+
+            // Re-point follow edges, skipping any that would follow oneself.
+            tx.execute(
+                "INSERT OR IGNORE INTO follows (follower, followee)
+                 SELECT ?1, followee FROM follows WHERE follower = ?2 AND followee != ?1",
+                params![new, old],
+            )
+            .map_err(map_err)?;
+            tx.execute(
+                "INSERT OR IGNORE INTO follows (follower, followee)
+                 SELECT follower, ?1 FROM follows WHERE followee = ?2 AND follower != ?1",
+                params![new, old],
+            )
+            .map_err(map_err)?;
+            tx.execute(
+                "DELETE FROM follows WHERE follower = ?1 OR followee = ?1",
+                params![old],
+            )
+            .map_err(map_err)?;
+
+            tx.execute(
+                "INSERT OR IGNORE INTO inbox (agent_did, unit_id, data)
+                 SELECT ?1, unit_id, data FROM inbox WHERE agent_did = ?2",
+                params![new, old],
+            )
+            .map_err(map_err)?;
+            tx.execute("DELETE FROM inbox WHERE agent_did = ?1", params![old])
+                .map_err(map_err)?;
+
+            tx.execute(
+                "INSERT INTO successions (predecessor, successor, data) VALUES (?1, ?2, ?3)",
+                params![old, new, data],
+            )
+            .map_err(map_err)?;
+            tx.commit().map_err(map_err)
+        })
+        .await
+        .map_err(|e| StorageError::Internal(format!("task join error: {e}")))?
+    }
+
+    async fn get_succession(
+        &self,
+        predecessor: &str,
+    ) -> Result<Option<SuccessionStatement>, StorageError> {
+        let conn = Arc::clone(&self.conn);
+        let predecessor = predecessor.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            let result = conn.query_row(
+                "SELECT data FROM successions WHERE predecessor = ?1",
+                params![predecessor],
+                |row| row.get::<_, String>(0),
+            );
+            match result {
+                Ok(data) => serde_json::from_str(&data)
+                    .map(Some)
+                    .map_err(|e| StorageError::Internal(e.to_string())),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(map_err(e)),
+            }
+        })
+        .await
+        .map_err(|e| StorageError::Internal(format!("task join error: {e}")))?
+    }
+
     // --- Follows -------------------------------------------------------------
 
     async fn add_follow(&self, follower: &str, followee: &str) -> Result<(), StorageError> {
@@ -1086,6 +1457,66 @@ mod tests {
         assert!(matches!(err, StorageError::Conflict(_)));
     }
 
+    #[tokio::test]
+    async fn put_unit_indexes_similarity_signature() {
+        let s = SqliteStorage::open_in_memory().unwrap();
+        let mut a = unit("019526b2-f68a-7c3e-a0b4-000000000001");
+        a.content = "Water boils at 100 degrees Celsius at sea level.".into();
+        let mut b = unit("019526b2-f68a-7c3e-a0b4-000000000002");
+        b.content = "Water boils at 100 degrees Celsius at sea level!".into();
+        let mut c = unit("019526b2-f68a-7c3e-a0b4-000000000003");
+        c.content = "Cats sleep for most of the day.".into();
+        for u in [&a, &b, &c] {
+            s.put_unit(u).await.unwrap();
+        }
+
+        let sig = s.get_unit_signature(&a.id).await.unwrap().unwrap();
+        assert_eq!(sig, MinHash::of(&a.content));
+        assert!(s.get_unit_signature("missing").await.unwrap().is_none());
+
+        let mut ids: Vec<String> = s
+            .similarity_candidates(&sig)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![a.id, b.id]);
+    }
+
+    #[tokio::test]
+    async fn list_units_filters_by_triple_pattern() {
+        let s = SqliteStorage::open_in_memory().unwrap();
+        let triple = |subject: &str, predicate: &str| {
+            serde_json::json!([{ "subject": subject, "predicate": predicate,
+                                  "object": { "kind": "entity", "id": "o" } }])
+        };
+        let mut a = unit("019526b2-f68a-7c3e-a0b4-000000000001");
+        a.extensions.insert("x-org.semanticweft.triples".into(), triple("water", "boils-at"));
+        let mut b = unit("019526b2-f68a-7c3e-a0b4-000000000002");
+        b.extensions.insert("x-org.semanticweft.triples".into(), triple("water", "freezes-at"));
+        let c = unit("019526b2-f68a-7c3e-a0b4-000000000003");
+        for u in [&a, &b, &c] {
+            s.put_unit(u).await.unwrap();
+        }
+
+        let ids = |page: Vec<SemanticUnit>| page.into_iter().map(|u| u.id).collect::<Vec<_>>();
+        let filter = UnitFilter {
+            limit: 10,
+            subject: Some("water".into()),
+            ..Default::default()
+        };
+        assert_eq!(ids(s.list_units(&filter).await.unwrap().0), vec![a.id.clone(), b.id.clone()]);
+        let filter = UnitFilter {
+            limit: 10,
+            subject: Some("water".into()),
+            predicate: Some("freezes-at".into()),
+            ..Default::default()
+        };
+        assert_eq!(ids(s.list_units(&filter).await.unwrap().0), vec![b.id.clone()]);
+    }
+
     #[tokio::test]
     async fn list_units_pagination() {
         let s = SqliteStorage::open_in_memory().unwrap();
@@ -1117,6 +1548,97 @@ mod tests {
         assert!(!s.is_following("alice", "bob").await.unwrap());
     }
 
+    #[tokio::test]
+    async fn apply_succession_moves_edges_and_inbox() {
+        let s = SqliteStorage::open_in_memory().unwrap();
+        let profile = |did: &str| AgentProfile {
+            did: did.into(),
+            inbox_url: format!("https://node.example.com/v1/agents/{did}/inbox"),
+            display_name: None,
+            public_key: None,
+            status: AgentStatus::Full,
+            contribution_count: 3,
+            reputation: 0.7,
+            predecessor: None,
+            successor: None,
+        };
+        s.put_agent(&profile("old")).await.unwrap();
+        s.add_follow("old", "bob").await.unwrap();
+        s.add_follow("bob", "old").await.unwrap();
+        s.add_follow("new", "old").await.unwrap();
+        s.deliver_to_inbox("old", &unit("019526b2-f68a-7c3e-a0b4-000000000001"))
+            .await
+            .unwrap();
+
+        let statement = SuccessionStatement {
+            predecessor: "old".into(),
+            successor: "new".into(),
+            created_at: "2026-03-01T12:00:00Z".into(),
+            reason: None,
+            predecessor_signature: "zOld".into(),
+            successor_signature: "zNew".into(),
+        };
+        let successor = AgentProfile {
+            predecessor: Some("old".into()),
+            ..profile("new")
+        };
+        s.apply_succession(&statement, &successor).await.unwrap();
+
+        assert_eq!(s.get_agent("new").await.unwrap(), Some(successor));
+        assert_eq!(
+            s.get_agent("old").await.unwrap().unwrap().successor.as_deref(),
+            Some("new")
+        );
+        assert_eq!(s.list_following("new").await.unwrap(), ["bob"]);
+        assert_eq!(s.list_followers("new").await.unwrap(), ["bob"]);
+        assert!(s.list_followers("old").await.unwrap().is_empty());
+        assert_eq!(s.get_inbox("new", None, 10).await.unwrap().0.len(), 1);
+        assert!(s.get_inbox("old", None, 10).await.unwrap().0.is_empty());
+        assert_eq!(s.get_succession("old").await.unwrap(), Some(statement));
+
+        let stats = s.agent_reputation_stats().await.unwrap();
+        assert!((stats.mean - 0.7).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn apply_succession_applies_only_the_first_rotation() {
+        let s = SqliteStorage::open_in_memory().unwrap();
+        let profile = |did: &str, predecessor: Option<&str>| AgentProfile {
+            did: did.into(),
+            inbox_url: format!("https://node.example.com/v1/agents/{did}/inbox"),
+            display_name: None,
+            public_key: None,
+            status: AgentStatus::Full,
+            contribution_count: 0,
+            reputation: 0.5,
+            predecessor: predecessor.map(String::from),
+            successor: None,
+        };
+        let statement = |successor: &str| SuccessionStatement {
+            predecessor: "old".into(),
+            successor: successor.into(),
+            created_at: "2026-03-01T12:00:00Z".into(),
+            reason: None,
+            predecessor_signature: "zOld".into(),
+            successor_signature: "zNew".into(),
+        };
+        s.put_agent(&profile("old", None)).await.unwrap();
+        s.apply_succession(&statement("b"), &profile("b", Some("old")))
+            .await
+            .unwrap();
+        let err = s
+            .apply_succession(&statement("c"), &profile("c", Some("old")))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StorageError::Conflict(_)), "{err}");
+        assert_eq!(
+            s.get_agent("old").await.unwrap().unwrap().successor.as_deref(),
+            Some("b")
+        );
+        assert!(s.get_agent("c").await.unwrap().is_none());
+        assert_eq!(s.get_succession("old").await.unwrap(), Some(statement("b")));
+    }
+
     #[tokio::test]
     async fn peer_roundtrip_with_reputation() {
         let s = SqliteStorage::open_in_memory().unwrap();
@@ -1168,6 +1690,130 @@ mod tests {
         assert_eq!(s.get_node_config("foo").await.unwrap().as_deref(), Some("baz"));
     }
 
+    #[tokio::test]
+    async fn get_units_fetches_known_ids_across_chunks() {
+        let s = SqliteStorage::open_in_memory().unwrap();
+        let mut ids: Vec<String> = (0..600)
+            .map(|i| format!("019526b2-f68a-7c3e-a0b4-{i:012}"))
+            .collect();
+        for id in &ids {
+            s.put_unit(&unit(id)).await.unwrap();
+        }
+        ids.push("019526b2-f68a-7c3e-a0b4-999999999999".into());
+        let mut got: Vec<String> = s
+            .get_units(&ids)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|u| u.id)
+            .collect();
+        got.sort();
+        assert_eq!(got, ids[..600]);
+    }
+
+    #[tokio::test]
+    async fn put_unit_stores_nothing_when_indexing_fails() {
+        let s = SqliteStorage::open_in_memory().unwrap();
+        let u = unit("019526b2-f68a-7c3e-a0b4-000000000001");
+        s.conn
+            .lock()
+            .unwrap()
+            .execute_batch("DROP TABLE unit_lsh_bands")
+            .unwrap();
+        assert!(s.put_unit(&u).await.is_err());
+        assert!(s.get_unit(&u.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn put_unit_stores_nothing_when_triple_indexing_fails() {
+        let s = SqliteStorage::open_in_memory().unwrap();
+        let mut u = unit("019526b2-f68a-7c3e-a0b4-000000000001");
+        u.extensions.insert(
+            "x-org.semanticweft.triples".into(),
+            serde_json::json!([{ "subject": "water", "predicate": "boils-at",
+                                  "object": { "kind": "entity", "id": "o" } }]),
+        );
+        s.conn
+            .lock()
+            .unwrap()
+            .execute_batch("DROP TABLE unit_triples")
+            .unwrap();
+        assert!(s.put_unit(&u).await.is_err());
+        assert!(s.get_unit(&u.id).await.unwrap().is_none());
+        assert!(s.similarity_candidates(&MinHash::of(&u.content)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn signature_backfill_runs_once() {
+        let s = SqliteStorage::open_in_memory().unwrap();
+        let u = unit("019526b2-f68a-7c3e-a0b4-000000000001");
+        s.put_unit(&u).await.unwrap();
+        let conn = s.conn.lock().unwrap();
+        let indexed = |conn: &Connection| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM unit_signatures", [], |row| row.get(0))
+                .unwrap()
+        };
+
+        // A database from before the backfill was tracked.
+        conn.execute_batch("DELETE FROM unit_signatures; PRAGMA user_version = 0")
+            .unwrap();
+        SqliteStorage::migrate(&conn).unwrap();
+        assert_eq!(indexed(&conn), 1);
+
+        conn.execute("DELETE FROM unit_signatures", []).unwrap();
+        SqliteStorage::migrate(&conn).unwrap();
+        assert_eq!(indexed(&conn), 0, "the backfill is not repeated");
+    }
+
+    #[tokio::test]
+    async fn signatures_from_before_the_banding_change_are_rebuilt() {
+        let s = SqliteStorage::open_in_memory().unwrap();
+        let u = unit("019526b2-f68a-7c3e-a0b4-000000000001");
+        s.put_unit(&u).await.unwrap();
+        {
+            let conn = s.conn.lock().unwrap();
+            let old = serde_json::to_string(&vec![7_u64; 64]).unwrap();
+            conn.execute(
+                "UPDATE unit_signatures SET signature = ?1",
+                params![old],
+            )
+            .unwrap();
+            conn.execute_batch("DELETE FROM unit_lsh_bands; PRAGMA user_version = 2")
+                .unwrap();
+            SqliteStorage::migrate(&conn).unwrap();
+        }
+        let signature = MinHash::of(&u.content);
+        assert_eq!(s.get_unit_signature(&u.id).await.unwrap(), Some(signature.clone()));
+        assert_eq!(s.similarity_candidates(&signature).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn triple_backfill_runs_once() {
+        let s = SqliteStorage::open_in_memory().unwrap();
+        let mut u = unit("019526b2-f68a-7c3e-a0b4-000000000001");
+        u.extensions.insert(
+            "x-org.semanticweft.triples".into(),
+            serde_json::json!([{ "subject": "water", "predicate": "boils-at",
+                                  "object": { "kind": "entity", "id": "o" } }]),
+        );
+        s.put_unit(&u).await.unwrap();
+        let conn = s.conn.lock().unwrap();
+        let indexed = |conn: &Connection| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM unit_triples", [], |row| row.get(0))
+                .unwrap()
+        };
+
+        // A database that predates the triple index.
+        conn.execute_batch("DELETE FROM unit_triples; PRAGMA user_version = 1")
+            .unwrap();
+        SqliteStorage::migrate(&conn).unwrap();
+        assert_eq!(indexed(&conn), 1);
+
+        conn.execute("DELETE FROM unit_triples", []).unwrap();
+        SqliteStorage::migrate(&conn).unwrap();
+        assert_eq!(indexed(&conn), 0, "the backfill is not repeated");
+    }
+
     #[tokio::test]
     async fn cursor_roundtrip() {
         let s = SqliteStorage::open_in_memory().unwrap();
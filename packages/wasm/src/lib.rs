@@ -4,7 +4,7 @@
 //! Compile with `wasm-pack build` to produce an npm-ready package that works
 //! in browsers, Node.js, and any other WASM host.
 //!
-//! ## Unit API — [`validate`], [`new_unit`], [`render`]
+//! ## Unit API — [`validate`], [`new_unit`], [`render`], [`render_threads`]
 //!
 //! ```js
 //! import init, { validate, new_unit, render } from './semanticweft_wasm.js';
@@ -15,7 +15,7 @@
 //! console.log(render(json));
 //! ```
 //!
-//! ## Agent API — [`AgentIdentity`], [`parse_agent_address`], [`node_session_from_discovery`]
+//! ## Agent API — [`AgentIdentity`], [`parse_agent_address`], [`node_session_from_discovery`], [`SyncCursor`]
 //!
 //! ```js
 //! // Generate (or restore) an identity.
@@ -32,6 +32,10 @@
 //! const discovery = await fetch(addr.wellKnownUrl).then(r => r.text());
 //! const session = node_session_from_discovery(discovery, id.did);
 //! // session: { apiBase, inboxUrl, registerUrl, unitsUrl, peersUrl }
+//!
+//! // Sign a request (the clock is yours too).
+//! const headers = JSON.parse(id.signRequest('POST', session.unitsUrl, json, Date.now()));
+//! await fetch(session.unitsUrl, { method: 'POST', headers, body: json });
 //! ```
 
 use wasm_bindgen::prelude::*;
@@ -161,6 +165,34 @@ pub fn render(json: &str) -> Result<String, JsValue> {
     Ok(semanticweft::render::render_unit(&unit))
 }
 
+/// Render a unit or graph as threaded conversations.
+///
+/// `json` must be either a single unit object or an array of unit objects.
+/// Replies are nested under the units they reference, labelled with their
+/// relationship, and ordered chronologically among siblings.
+///
+/// ```js
+/// const text = renderThreads(JSON.stringify([claim, challenge, answer]));
+/// ```
+#[wasm_bindgen(js_name = renderThreads)]
+pub fn render_threads(json: &str) -> Result<String, JsValue> {
+    setup();
+
+    let units = match serde_json::from_str::<Vec<semanticweft::SemanticUnit>>(json) {
+        Ok(units) if units.is_empty() => {
+            return Err(JsValue::from_str("input array is empty"));
+        }
+        Ok(units) => units,
+        Err(_) => {
+            let unit: semanticweft::SemanticUnit = serde_json::from_str(json)
+                .map_err(|e| JsValue::from_str(&format!("parse error: {e}")))?;
+            vec![unit]
+        }
+    };
+    let graph = semanticweft::Graph::from_units(units);
+    Ok(semanticweft::render::render_threads(&graph))
+}
+
 // ── Agent API ─────────────────────────────────────────────────────────────────
 
 /// An agent's Ed25519 identity.
@@ -231,6 +263,47 @@ impl AgentIdentity {
     pub fn sign(&self, message: &[u8]) -> Vec<u8> {
         self.inner.sign(message)
     }
+
+    /// Build HTTP Signature headers for a request to a node.
+    ///
+    /// `now_ms` is the current time in milliseconds since the epoch
+    /// (`Date.now()`); `body` is the exact request body, if any. Returns a
+    /// JSON object of headers to set:
+    ///
+    /// ```json
+    /// { "host": "…", "date": "…", "digest": "SHA-256=…", "signature": "keyId=…" }
+    /// ```
+    ///
+    /// `digest` is present only when a body is given. Throws if `url` is not
+    /// an absolute `http://` or `https://` URL.
+    #[wasm_bindgen(js_name = signRequest)]
+    pub fn sign_request(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<String>,
+        now_ms: f64,
+    ) -> Result<JsValue, JsValue> {
+        let mut request = semanticweft_agent_core::SignableRequest::from_url(method, url)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        let now = std::time::UNIX_EPOCH + std::time::Duration::from_millis(now_ms.max(0.0) as u64);
+        let headers: serde_json::Map<String, serde_json::Value> = request
+            .sign(&self.inner, now)
+            .pairs()
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.into()))
+            .collect();
+        Ok(JsValue::from_str(&serde_json::Value::Object(headers).to_string()))
+    }
+}
+
+impl Default for AgentIdentity {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Parse an agent address string (`did:key:z6Mk…@hostname`) and return a
@@ -270,13 +343,18 @@ pub fn parse_agent_address(s: &str) -> Result<JsValue, JsValue> {
 ///   "inboxUrl":    "https://sweft.example.com/v1/agents/did%3A…/inbox",
 ///   "registerUrl": "https://sweft.example.com/v1/agents/did%3A…",
 ///   "unitsUrl":    "https://sweft.example.com/v1/units",
-///   "peersUrl":    "https://sweft.example.com/v1/peers"
+///   "peersUrl":    "https://sweft.example.com/v1/peers",
+///   "nodeInfo":    { "node_id": "…", "capabilities": ["sync", "sse"], … }
 /// }
 /// ```
 ///
+/// `nodeInfo` is the parsed discovery document, so callers can check
+/// `capabilities`, `signing_required` and `pow_required` before acting.
+///
 /// `discovery_json` is the raw response body from
-/// `GET /.well-known/semanticweft`.  Throws on parse failure or if
-/// `api_base` is missing from the document.
+/// `GET /.well-known/semanticweft`.  Throws on parse failure, if `api_base`
+/// is missing from the document, or if the node speaks an incompatible
+/// protocol version.
 #[wasm_bindgen(js_name = nodeSessionFromDiscovery)]
 pub fn node_session_from_discovery(
     discovery_json: &str,
@@ -293,12 +371,122 @@ pub fn node_session_from_discovery(
         "registerUrl": session.register_url(),
         "unitsUrl":    session.units_url(),
         "peersUrl":    session.peers_url(),
+        "nodeInfo":    session.node_info,
     });
     Ok(JsValue::from_str(&obj.to_string()))
 }
 
+/// Follows a node's `/v1/sync` stream from a cursor: SSE parsing, catch-up
+/// polling and reconnects, with the HTTP calls left to the host.
+///
+/// ```js
+/// const sync = new SyncCursor(session.apiBase, localStorage.getItem('cursor'));
+/// for (;;) {
+///   const req = JSON.parse(sync.nextRequest());
+///   if (req.kind === 'poll') {
+///     const body = await fetch(req.url).then(r => r.text());
+///     handle(JSON.parse(sync.onPage(body)));
+///   } else {
+///     const headers = { accept: 'text/event-stream' };
+///     if (req.lastEventId) headers['last-event-id'] = req.lastEventId;
+///     const reader = (await fetch(req.url, { headers })).body.getReader();
+///     for (let r = await reader.read(); !r.done; r = await reader.read()) {
+///       const events = JSON.parse(sync.onChunk(r.value));
+///       handle(events);
+///       if (events.some(e => e.kind === 'resync')) { reader.cancel(); break; }
+///     }
+///     sync.onDisconnect();
+///   }
+/// }
+/// // events: { kind: 'unit', unit } | { kind: 'cursor', cursor }
+/// //       | { kind: 'resync' } | { kind: 'malformed', error }
+/// ```
+#[wasm_bindgen]
+pub struct SyncCursor {
+    inner: semanticweft_agent_core::SyncCursor,
+    session: semanticweft_agent_core::NodeSession,
+}
+
+#[wasm_bindgen]
+impl SyncCursor {
+    /// Start after `cursor` (a unit id, e.g. a persisted one) on the node
+    /// whose versioned API base is `api_base`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(api_base: &str, cursor: Option<String>) -> Self {
+        setup();
+        Self {
+            inner: semanticweft_agent_core::SyncCursor::new(cursor),
+            session: semanticweft_agent_core::NodeSession::new(api_base, ""),
+        }
+    }
+
+    /// The id of the last unit delivered, or `undefined`.
+    #[wasm_bindgen(getter)]
+    pub fn cursor(&self) -> Option<String> {
+        self.inner.cursor().map(str::to_string)
+    }
+
+    /// The next request as JSON: `{"kind":"poll","url":…}` or
+    /// `{"kind":"stream","url":…,"lastEventId":…}`.
+    #[wasm_bindgen(js_name = nextRequest)]
+    pub fn next_request(&self) -> String {
+        use semanticweft_agent_core::SyncRequest;
+        let req = match self.inner.next_request(&self.session) {
+            SyncRequest::Poll { url } => serde_json::json!({ "kind": "poll", "url": url }),
+            SyncRequest::Stream { url, last_event_id } => serde_json::json!({
+                "kind": "stream",
+                "url": url,
+                "lastEventId": last_event_id,
+            }),
+        };
+        req.to_string()
+    }
+
+    /// Take a JSON `/v1/sync` response body; returns the events as a JSON
+    /// array.  Throws if the body is not a sync page.
+    #[wasm_bindgen(js_name = onPage)]
+    pub fn on_page(&mut self, body: &str) -> Result<String, JsValue> {
+        setup();
+        let events = self
+            .inner
+            .on_page(body)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(sync_events_json(events))
+    }
+
+    /// Take the next bytes of the SSE stream; returns the events as a JSON
+    /// array.
+    #[wasm_bindgen(js_name = onChunk)]
+    pub fn on_chunk(&mut self, chunk: &[u8]) -> String {
+        setup();
+        sync_events_json(self.inner.on_chunk(chunk))
+    }
+
+    /// The stream ended; the next request catches up from the cursor.
+    #[wasm_bindgen(js_name = onDisconnect)]
+    pub fn on_disconnect(&mut self) {
+        self.inner.on_disconnect();
+    }
+}
+
 // ── Internal helpers ──────────────────────────────────────────────────────────
 
+fn sync_events_json(events: Vec<semanticweft_agent_core::SyncEvent>) -> String {
+    use semanticweft_agent_core::SyncEvent;
+    let events: Vec<serde_json::Value> = events
+        .into_iter()
+        .map(|event| match event {
+            SyncEvent::Unit(unit) => serde_json::json!({ "kind": "unit", "unit": unit }),
+            SyncEvent::Cursor(cursor) => serde_json::json!({ "kind": "cursor", "cursor": cursor }),
+            SyncEvent::Resync => serde_json::json!({ "kind": "resync" }),
+            SyncEvent::Malformed(error) => {
+                serde_json::json!({ "kind": "malformed", "error": error })
+            }
+        })
+        .collect();
+    serde_json::Value::Array(events).to_string()
+}
+
 fn hex_to_32_bytes(hex: &str) -> Result<[u8; 32], JsValue> {
     if hex.len() != 64 {
         return Err(JsValue::from_str(&format!(
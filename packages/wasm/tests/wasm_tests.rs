@@ -11,7 +11,9 @@ use wasm_bindgen_test::*;
 // Configure all tests in this file to run in Node.js (no browser required).
 wasm_bindgen_test_configure!(run_in_node_experimental);
 
-use semanticweft_wasm::{new_unit, parse_agent_address, render, validate, AgentIdentity};
+use semanticweft_wasm::{
+    new_unit, parse_agent_address, render, render_threads, validate, AgentIdentity,
+};
 
 // ---------------------------------------------------------------------------
 // validate()
@@ -98,6 +100,28 @@ fn render_empty_array_returns_err() {
     assert!(result.is_err(), "empty array should return an error");
 }
 
+#[wasm_bindgen_test]
+fn render_threads_nests_reply_under_parent() {
+    let parent = new_unit("assertion", "thread-parent-marker", "did:key:z6MkTest", None).unwrap();
+    let parent_id = serde_json::from_str::<serde_json::Value>(&parent).unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let mut reply: serde_json::Value = serde_json::from_str(
+        &new_unit("challenge", "thread-reply-marker", "did:key:z6MkTest", None).unwrap(),
+    )
+    .unwrap();
+    reply["references"] = serde_json::json!([{ "id": parent_id, "rel": "rebuts" }]);
+
+    let input = format!("[{parent},{reply}]");
+    let text = render_threads(&input).unwrap();
+    assert!(text.contains("└─ rebuts"), "reply should carry its relationship label: {text}");
+    assert!(
+        text.find("thread-parent-marker") < text.find("thread-reply-marker"),
+        "reply should be nested after its parent: {text}"
+    );
+}
+
 // ---------------------------------------------------------------------------
 // AgentIdentity
 // ---------------------------------------------------------------------------